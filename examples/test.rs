@@ -1,5 +1,5 @@
 
-use micro_elf::*;
+use micro_elf::elf::ELFObject;
 
 use std::io::prelude::*;
 
@@ -16,7 +16,8 @@ fn main() {
 
 	reader.read_to_end(&mut data).unwrap();
 
-	let elf = micro_elf::parse(&data);
-
-	println!("{}", elf);
+	match ELFObject::parse(data) {
+		Ok(elf) => println!("{}", elf),
+		Err(e) => println!("Failed to parse the ELF file: {}", e),
+	}
 }
\ No newline at end of file
@@ -0,0 +1,94 @@
+//! Fuzzes the ELF parser.
+//! Any input must either parse or return an error, never panic.
+
+#![no_main]
+
+
+
+use libfuzzer_sys::fuzz_target;
+
+use micro_elf::{
+    elf::{
+        ELFObject,
+        data::AddressSpace,
+    },
+    image::{ HexOptions, MemoryImage, SrecOptions, Uf2Options },
+};
+
+use std::sync::Arc;
+
+
+
+fuzz_target!(|data: &[u8]| {
+    // Decode the raw data as a UF2 file.
+    let _ = MemoryImage::from_uf2( data, None );
+
+    // Parse the raw data.
+    let elf = match ELFObject::parse( Arc::<[u8]>::from(data) ) {
+        Ok(elf) => elf,
+        _ => return,
+    };
+
+    // Pretty print the whole object.
+    let _ = format!("{}", elf);
+
+    // Access the contents of all sections and symbols.
+    for section in elf.sections() {
+        let _ = elf.content( section.as_ref() );
+    }
+
+    for index in 0..elf.sections().len() {
+        let _ = elf.decompressed_content( index );
+    }
+
+    for symbol in elf.symbols() {
+        let _ = elf.content( symbol.as_ref() );
+    }
+
+    // Look up all symbols by name.
+    for symbol in elf.symbols() {
+        let _ = elf.symbol( symbol.name() );
+        let _ = elf.symbol_in_file( "", symbol.name() );
+        let _ = elf.symbol_demangled( &symbol.demangled(false) );
+    }
+
+    // Read the memory of all symbols in both address spaces.
+    for symbol in elf.symbols() {
+        let _ = elf.read_at( u64::from( symbol.value() ), 16, AddressSpace::Virtual );
+        let _ = elf.read_cstr_at( u64::from( symbol.value() ), AddressSpace::Physical );
+    }
+
+    // Build the memory image of the loadable segments and write it as Intel HEX, S-records and UF2.
+    let _ = elf.memory_image();
+    let _ = elf.to_ihex( &HexOptions::default() );
+    let _ = elf.to_srec( &SrecOptions::default() );
+    let _ = elf.to_uf2( &Uf2Options::default() );
+
+    // Symbolize the address of all symbols.
+    let symbolizer = elf.symbolizer();
+
+    for symbol in elf.symbols() {
+        let _ = symbolizer.describe( u64::from( symbol.value() ) );
+        let _ = symbolizer.describe_demangled( u64::from( symbol.value() ) );
+    }
+
+    // Decode all the relocations and apply them.
+    let _ = elf.relocations();
+    let _ = elf.link( 0x2000_0000, &Default::default() );
+
+    // Decode the dynamic section and look up the dynamic symbols through the hash table.
+    let _ = elf.dynamic();
+
+    for symbol in elf.dynamic_symbols() {
+        let _ = elf.lookup_dynamic( symbol.name() );
+        let _ = symbol.versioned_name();
+    }
+
+    // Decode all the notes.
+    for note in elf.notes().flatten() {
+        let _ = note.decode();
+    }
+
+    // Write the object back.
+    let _ = elf.to_bytes();
+});
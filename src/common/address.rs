@@ -32,6 +32,15 @@ impl Address {
             Address::U64(_) => 8,
         }
     }
+
+    /// Returns an address of the same width with the given value.
+    /// The value is truncated to the width of the address.
+    pub fn with(&self, value: u64) -> Address {
+        match self {
+            Address::U32(_) => Address::U32( value as u32 ),
+            Address::U64(_) => Address::U64( value ),
+        }
+    }
 }
 
 impl core::convert::From<Address> for usize {
@@ -43,6 +52,15 @@ impl core::convert::From<Address> for usize {
     }
 }
 
+impl core::convert::From<Address> for u64 {
+    fn from(a: Address) -> u64 {
+        match a {
+            Address::U32(u) => u as u64,
+            Address::U64(u) => u,
+        }
+    }
+}
+
 impl core::fmt::Display for Address {
     fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
         match self {
@@ -74,3 +92,17 @@ pub fn read32<B: ByteOrder>(buf: &[u8]) -> Address {
 pub fn read64<B: ByteOrder>(buf: &[u8]) -> Address {
     Address::U64( B::read_u64(buf) )
 }
+
+
+
+/// Internal function to write an address as a `u32`.
+pub fn write32<B: ByteOrder>(buf: &mut [u8], a: Address) {
+    B::write_u32( buf, u64::from(a) as u32 )
+}
+
+
+
+/// Internal function to write an address as a `u64`.
+pub fn write64<B: ByteOrder>(buf: &mut [u8], a: Address) {
+    B::write_u64( buf, u64::from(a) )
+}
@@ -24,3 +24,24 @@ pub fn read32<B: ByteOrder>(buf: &[u8]) -> u32 {
 pub fn read64<B: ByteOrder>(buf: &[u8]) -> u64 {
     B::read_u64(buf)
 }
+
+
+
+/// Internal function to write a `u16`.
+pub fn write16<B: ByteOrder>(buf: &mut [u8], n: u16) {
+    B::write_u16(buf, n)
+}
+
+
+
+/// Internal function to write a `u32`.
+pub fn write32<B: ByteOrder>(buf: &mut [u8], n: u32) {
+    B::write_u32(buf, n)
+}
+
+
+
+/// Internal function to write a `u64`.
+pub fn write64<B: ByteOrder>(buf: &mut [u8], n: u64) {
+    B::write_u64(buf, n)
+}
@@ -25,7 +25,7 @@ impl SubSlice {
     }
 
     /// Creates an iterator for the data.
-    pub fn iter<'a>(&'a self) -> impl Iterator<Item = &'a u8> {
+    pub fn iter(&self) -> impl Iterator<Item = &u8> {
         self.arc[self.start..self.end].iter()
     }
 
@@ -33,6 +33,11 @@ impl SubSlice {
     pub fn len(&self) -> usize {
         self.end - self.start
     }
+
+    /// Returns `true` if the subslice is empty.
+    pub fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
 }
 
 impl<I: core::slice::SliceIndex<[u8]>> core::ops::Index<I> for SubSlice {
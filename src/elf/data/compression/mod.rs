@@ -0,0 +1,189 @@
+//! Compressed sections.
+//! Parsing of the compression header (`Elf_Chdr`) of the `SHF_COMPRESSED` sections and of the
+//! legacy `.zdebug_*` sections, and decompression of their contents.
+
+
+
+use crate::{
+    common::address::Address,
+    error::{ Error, Table },
+};
+
+use core::convert::TryFrom;
+
+
+
+/// Magic number of the legacy GNU compressed sections (`.zdebug_*`).
+pub const ZDEBUG_MAGIC: &[u8; 4] = b"ZLIB";
+
+/// Prefix of the names of the legacy GNU compressed sections.
+pub const ZDEBUG_PREFIX: &str = ".zdebug";
+
+
+
+/// The compression algorithm of a section (`ch_type`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompressionType {
+    /// zlib (`ELFCOMPRESS_ZLIB`).
+    Zlib,
+
+    /// Zstandard (`ELFCOMPRESS_ZSTD`).
+    Zstd,
+
+    /// Other compression algorithm.
+    Other(u32),
+}
+
+impl From<u32> for CompressionType {
+    fn from(value: u32) -> Self {
+        match value {
+            1 => CompressionType::Zlib,
+            2 => CompressionType::Zstd,
+            _ => CompressionType::Other(value),
+        }
+    }
+}
+
+impl From<CompressionType> for u32 {
+    fn from(value: CompressionType) -> u32 {
+        match value {
+            CompressionType::Zlib => 1,
+            CompressionType::Zstd => 2,
+            CompressionType::Other(value) => value,
+        }
+    }
+}
+
+impl core::fmt::Display for CompressionType {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match *self {
+            CompressionType::Zlib => write!(f, "zlib"),
+            CompressionType::Zstd => write!(f, "zstd"),
+            CompressionType::Other(value) => write!(f, "Unknown (0x{:X})", value),
+        }
+    }
+}
+
+
+
+/// The compression header of a compressed section.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CompressionHeader {
+    /// Compression algorithm.
+    pub(super) kind: CompressionType,
+
+    /// Size in bytes of the uncompressed data.
+    pub(super) size: u64,
+
+    /// Alignment of the uncompressed data.
+    pub(super) alignment: u64,
+
+    /// Size in bytes of the header, which precedes the compressed data.
+    pub(super) headersize: usize,
+}
+
+impl CompressionHeader {
+    /// Parses the compression header (`Elf_Chdr`) at the start of the given section contents.
+    /// `index` and `offset` locate the section, and are used to report errors.
+    pub fn parse<const INC: usize>(raw: &[u8], index: usize, offset: usize, read: fn(&[u8]) -> Address, read32: fn(&[u8]) -> u32) -> Result<Self, Error> {
+        // The 64 bit header has a reserved word after the type.
+        let headersize = 3 * INC;
+
+        if raw.len() < headersize {
+            return Err( Error::OutOfBounds { table: Table::Compression, index, offset } );
+        }
+
+        // Read the fields.
+        let kind = CompressionType::from( read32( &raw[0..4] ) );
+        let size = u64::from( read( &raw[INC..2*INC] ) );
+        let alignment = u64::from( read( &raw[2*INC..3*INC] ) );
+
+        Ok( Self { kind, size, alignment, headersize } )
+    }
+
+    /// Parses the header of a legacy GNU compressed section (`.zdebug_*`): the magic `ZLIB`
+    /// followed by the big endian size of the uncompressed data.
+    /// The alignment of the uncompressed data is the alignment of the section.
+    /// Returns `None` if the contents do not start with the magic, as they are not compressed.
+    pub fn legacy(raw: &[u8], alignment: u64) -> Option<Self> {
+        let header = raw.get(0..12).filter(|header| header.starts_with(ZDEBUG_MAGIC))?;
+
+        let mut size = [0u8; 8];
+        size.copy_from_slice( &header[4..12] );
+
+        Some( Self { kind: CompressionType::Zlib, size: u64::from_be_bytes(size), alignment, headersize: 12 } )
+    }
+
+    /// Returns the compression algorithm.
+    pub fn kind(&self) -> CompressionType {
+        self.kind
+    }
+
+    /// Returns the size in bytes of the uncompressed data.
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    /// Returns the alignment of the uncompressed data.
+    pub fn alignment(&self) -> u64 {
+        self.alignment
+    }
+
+    /// Returns the size in bytes of the header, which precedes the compressed data.
+    pub fn headersize(&self) -> usize {
+        self.headersize
+    }
+}
+
+
+
+/// Decompresses the given data (after the compression header) of the section at the given index.
+/// The decompressed data must have the size given by the header.
+pub(super) fn decompress(header: &CompressionHeader, data: &[u8], index: usize, offset: usize) -> Result<Vec<u8>, Error> {
+    // The uncompressed size must be addressable.
+    let size = usize::try_from( header.size ).map_err(|_| Error::BadCompressedData { index, offset })?;
+
+    // Decompress with the enabled decoders.
+    let output = match header.kind {
+        CompressionType::Zlib if cfg!(feature = "zlib") => zlib(data, size),
+        CompressionType::Zstd if cfg!(feature = "zstd") => zstd(data, size),
+        kind => return Err( Error::UnsupportedCompression( u32::from(kind) ) ),
+    };
+
+    match output {
+        Some(output) if output.len() == size => Ok( output ),
+        _ => Err( Error::BadCompressedData { index, offset } ),
+    }
+}
+
+/// Decompresses a zlib stream, up to the expected size.
+#[cfg(feature = "zlib")]
+fn zlib(data: &[u8], size: usize) -> Option<Vec<u8>> {
+    miniz_oxide::inflate::decompress_to_vec_zlib_with_limit(data, size).ok()
+}
+
+/// The zlib decoder is not enabled.
+#[cfg(not(feature = "zlib"))]
+fn zlib(_: &[u8], _: usize) -> Option<Vec<u8>> {
+    None
+}
+
+/// Decompresses a Zstandard frame, reading at most one byte more than the expected size.
+/// The output grows with the decompressed data, so a bad size in the header cannot exhaust the memory.
+#[cfg(feature = "zstd")]
+fn zstd(data: &[u8], size: usize) -> Option<Vec<u8>> {
+    use std::io::Read;
+
+    let decoder = ruzstd::decoding::StreamingDecoder::new(data).ok()?;
+
+    let mut output = Vec::new();
+    decoder.take( (size as u64).saturating_add(1) ).read_to_end(&mut output).ok()?;
+
+    Some( output )
+}
+
+/// The Zstandard decoder is not enabled.
+#[cfg(not(feature = "zstd"))]
+fn zstd(_: &[u8], _: usize) -> Option<Vec<u8>> {
+    None
+}
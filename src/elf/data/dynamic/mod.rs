@@ -0,0 +1,355 @@
+//! Dynamic linking information of an ELF file.
+//! Decoding of the entries of the dynamic section (`PT_DYNAMIC` / `.dynamic`).
+
+
+
+/// An entry of the dynamic section.
+/// Strings are resolved through the dynamic string table. Addresses are virtual addresses.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DynamicEntry {
+    /// Marks the end of the dynamic section (`DT_NULL`).
+    Null,
+
+    /// Name of a needed library (`DT_NEEDED`).
+    Needed(String),
+
+    /// Size in bytes of the PLT relocations (`DT_PLTRELSZ`).
+    PltRelSize(u64),
+
+    /// Address of the PLT or GOT (`DT_PLTGOT`).
+    PltGot(u64),
+
+    /// Address of the SysV symbol hash table (`DT_HASH`).
+    Hash(u64),
+
+    /// Address of the dynamic string table (`DT_STRTAB`).
+    StrTab(u64),
+
+    /// Address of the dynamic symbol table (`DT_SYMTAB`).
+    SymTab(u64),
+
+    /// Address of the relocations with addends (`DT_RELA`).
+    Rela(u64),
+
+    /// Size in bytes of the relocations with addends (`DT_RELASZ`).
+    RelaSize(u64),
+
+    /// Size in bytes of a relocation with addend (`DT_RELAENT`).
+    RelaEntry(u64),
+
+    /// Size in bytes of the dynamic string table (`DT_STRSZ`).
+    StrSize(u64),
+
+    /// Size in bytes of a dynamic symbol (`DT_SYMENT`).
+    SymEntry(u64),
+
+    /// Address of the initialization function (`DT_INIT`).
+    Init(u64),
+
+    /// Address of the termination function (`DT_FINI`).
+    Fini(u64),
+
+    /// Name of the shared object (`DT_SONAME`).
+    SoName(String),
+
+    /// Library search path, deprecated (`DT_RPATH`).
+    RPath(String),
+
+    /// Start the symbol search in the shared object itself (`DT_SYMBOLIC`).
+    Symbolic,
+
+    /// Address of the relocations without addends (`DT_REL`).
+    Rel(u64),
+
+    /// Size in bytes of the relocations without addends (`DT_RELSZ`).
+    RelSize(u64),
+
+    /// Size in bytes of a relocation without addend (`DT_RELENT`).
+    RelEntry(u64),
+
+    /// Type of the PLT relocations, `DT_REL` or `DT_RELA` (`DT_PLTREL`).
+    PltRel(u64),
+
+    /// Reserved for debugging (`DT_DEBUG`).
+    Debug(u64),
+
+    /// Relocations may modify a non-writable segment (`DT_TEXTREL`).
+    TextRel,
+
+    /// Address of the PLT relocations (`DT_JMPREL`).
+    JmpRel(u64),
+
+    /// Process all relocations before executing (`DT_BIND_NOW`).
+    BindNow,
+
+    /// Address of the array of initialization functions (`DT_INIT_ARRAY`).
+    InitArray(u64),
+
+    /// Address of the array of termination functions (`DT_FINI_ARRAY`).
+    FiniArray(u64),
+
+    /// Size in bytes of the array of initialization functions (`DT_INIT_ARRAYSZ`).
+    InitArraySize(u64),
+
+    /// Size in bytes of the array of termination functions (`DT_FINI_ARRAYSZ`).
+    FiniArraySize(u64),
+
+    /// Library search path (`DT_RUNPATH`).
+    RunPath(String),
+
+    /// Flags of the object (`DT_FLAGS`).
+    Flags(u64),
+
+    /// Address of the array of pre-initialization functions (`DT_PREINIT_ARRAY`).
+    PreInitArray(u64),
+
+    /// Size in bytes of the array of pre-initialization functions (`DT_PREINIT_ARRAYSZ`).
+    PreInitArraySize(u64),
+
+    /// Address of the extended section indices of the dynamic symbols (`DT_SYMTAB_SHNDX`).
+    SymTabShndx(u64),
+
+    /// Size in bytes of the relative relocations (`DT_RELRSZ`).
+    RelrSize(u64),
+
+    /// Address of the relative relocations (`DT_RELR`).
+    Relr(u64),
+
+    /// Size in bytes of a relative relocation (`DT_RELRENT`).
+    RelrEntry(u64),
+
+    /// Address of the GNU symbol hash table (`DT_GNU_HASH`).
+    GnuHash(u64),
+
+    /// Address of the symbol version table (`DT_VERSYM`).
+    VerSym(u64),
+
+    /// Number of relative relocations with addends (`DT_RELACOUNT`).
+    RelaCount(u64),
+
+    /// Number of relative relocations without addends (`DT_RELCOUNT`).
+    RelCount(u64),
+
+    /// Extended flags of the object (`DT_FLAGS_1`).
+    Flags1(u64),
+
+    /// Address of the version definitions (`DT_VERDEF`).
+    VerDef(u64),
+
+    /// Number of version definitions (`DT_VERDEFNUM`).
+    VerDefNum(u64),
+
+    /// Address of the needed versions (`DT_VERNEED`).
+    VerNeed(u64),
+
+    /// Number of needed versions (`DT_VERNEEDNUM`).
+    VerNeedNum(u64),
+
+    /// Name of the shared object whose symbols take precedence (`DT_AUXILIARY`).
+    Auxiliary(String),
+
+    /// Name of the shared object filtered by this one (`DT_FILTER`).
+    Filter(String),
+
+    /// Entry with a tag without a decoding.
+    Other {
+        /// Raw tag of the entry.
+        tag: u64,
+
+        /// Raw value of the entry.
+        value: u64,
+    },
+}
+
+impl DynamicEntry {
+    /// Decodes an entry given its raw tag and value.
+    /// String values are read from the given dynamic string table.
+    /// Returns `None` if a string is not in the string table.
+    pub fn new(tag: u64, value: u64, strings: &[u8]) -> Option<Self> {
+        use DynamicEntry::*;
+
+        // Reads a string of the dynamic string table.
+        let string = || -> Option<String> {
+            let raw = strings.get(value as usize..)?;
+            let cstr = core::ffi::CStr::from_bytes_until_nul(raw).ok()?;
+
+            Some( cstr.to_string_lossy().into_owned() )
+        };
+
+        let entry = match tag {
+            0  => Null,
+            1  => Needed( string()? ),
+            2  => PltRelSize(value),
+            3  => PltGot(value),
+            4  => Hash(value),
+            5  => StrTab(value),
+            6  => SymTab(value),
+            7  => Rela(value),
+            8  => RelaSize(value),
+            9  => RelaEntry(value),
+            10 => StrSize(value),
+            11 => SymEntry(value),
+            12 => Init(value),
+            13 => Fini(value),
+            14 => SoName( string()? ),
+            15 => RPath( string()? ),
+            16 => Symbolic,
+            17 => Rel(value),
+            18 => RelSize(value),
+            19 => RelEntry(value),
+            20 => PltRel(value),
+            21 => Debug(value),
+            22 => TextRel,
+            23 => JmpRel(value),
+            24 => BindNow,
+            25 => InitArray(value),
+            26 => FiniArray(value),
+            27 => InitArraySize(value),
+            28 => FiniArraySize(value),
+            29 => RunPath( string()? ),
+            30 => Flags(value),
+            32 => PreInitArray(value),
+            33 => PreInitArraySize(value),
+            34 => SymTabShndx(value),
+            35 => RelrSize(value),
+            36 => Relr(value),
+            37 => RelrEntry(value),
+
+            0x6FFF_FEF5 => GnuHash(value),
+            0x6FFF_FFF0 => VerSym(value),
+            0x6FFF_FFF9 => RelaCount(value),
+            0x6FFF_FFFA => RelCount(value),
+            0x6FFF_FFFB => Flags1(value),
+            0x6FFF_FFFC => VerDef(value),
+            0x6FFF_FFFD => VerDefNum(value),
+            0x6FFF_FFFE => VerNeed(value),
+            0x6FFF_FFFF => VerNeedNum(value),
+            0x7FFF_FFFD => Auxiliary( string()? ),
+            0x7FFF_FFFF => Filter( string()? ),
+
+            _ => Other { tag, value },
+        };
+
+        Some( entry )
+    }
+
+    /// Returns `true` if the value of the entry with the given raw tag is an offset in the dynamic string table.
+    pub fn is_string(tag: u64) -> bool {
+        matches!(tag, 1 | 14 | 15 | 29 | 0x7FFF_FFFD | 0x7FFF_FFFF)
+    }
+
+    /// Returns the raw tag of the entry.
+    pub fn tag(&self) -> u64 {
+        use DynamicEntry::*;
+
+        match *self {
+            Null                => 0,
+            Needed(_)           => 1,
+            PltRelSize(_)       => 2,
+            PltGot(_)           => 3,
+            Hash(_)             => 4,
+            StrTab(_)           => 5,
+            SymTab(_)           => 6,
+            Rela(_)             => 7,
+            RelaSize(_)         => 8,
+            RelaEntry(_)        => 9,
+            StrSize(_)          => 10,
+            SymEntry(_)         => 11,
+            Init(_)             => 12,
+            Fini(_)             => 13,
+            SoName(_)           => 14,
+            RPath(_)            => 15,
+            Symbolic            => 16,
+            Rel(_)              => 17,
+            RelSize(_)          => 18,
+            RelEntry(_)         => 19,
+            PltRel(_)           => 20,
+            Debug(_)            => 21,
+            TextRel             => 22,
+            JmpRel(_)           => 23,
+            BindNow             => 24,
+            InitArray(_)        => 25,
+            FiniArray(_)        => 26,
+            InitArraySize(_)    => 27,
+            FiniArraySize(_)    => 28,
+            RunPath(_)          => 29,
+            Flags(_)            => 30,
+            PreInitArray(_)     => 32,
+            PreInitArraySize(_) => 33,
+            SymTabShndx(_)      => 34,
+            RelrSize(_)         => 35,
+            Relr(_)             => 36,
+            RelrEntry(_)        => 37,
+            GnuHash(_)          => 0x6FFF_FEF5,
+            VerSym(_)           => 0x6FFF_FFF0,
+            RelaCount(_)        => 0x6FFF_FFF9,
+            RelCount(_)         => 0x6FFF_FFFA,
+            Flags1(_)           => 0x6FFF_FFFB,
+            VerDef(_)           => 0x6FFF_FFFC,
+            VerDefNum(_)        => 0x6FFF_FFFD,
+            VerNeed(_)          => 0x6FFF_FFFE,
+            VerNeedNum(_)       => 0x6FFF_FFFF,
+            Auxiliary(_)        => 0x7FFF_FFFD,
+            Filter(_)           => 0x7FFF_FFFF,
+            Other { tag, .. }   => tag,
+        }
+    }
+}
+
+impl core::fmt::Display for DynamicEntry {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        use DynamicEntry::*;
+
+        match self {
+            Null                => write!(f, "NULL"),
+            Needed(s)           => write!(f, "NEEDED [{}]", s),
+            PltRelSize(v)       => write!(f, "PLTRELSZ {} Bytes", v),
+            PltGot(a)           => write!(f, "PLTGOT 0x{:X}", a),
+            Hash(a)             => write!(f, "HASH 0x{:X}", a),
+            StrTab(a)           => write!(f, "STRTAB 0x{:X}", a),
+            SymTab(a)           => write!(f, "SYMTAB 0x{:X}", a),
+            Rela(a)             => write!(f, "RELA 0x{:X}", a),
+            RelaSize(v)         => write!(f, "RELASZ {} Bytes", v),
+            RelaEntry(v)        => write!(f, "RELAENT {} Bytes", v),
+            StrSize(v)          => write!(f, "STRSZ {} Bytes", v),
+            SymEntry(v)         => write!(f, "SYMENT {} Bytes", v),
+            Init(a)             => write!(f, "INIT 0x{:X}", a),
+            Fini(a)             => write!(f, "FINI 0x{:X}", a),
+            SoName(s)           => write!(f, "SONAME [{}]", s),
+            RPath(s)            => write!(f, "RPATH [{}]", s),
+            Symbolic            => write!(f, "SYMBOLIC"),
+            Rel(a)              => write!(f, "REL 0x{:X}", a),
+            RelSize(v)          => write!(f, "RELSZ {} Bytes", v),
+            RelEntry(v)         => write!(f, "RELENT {} Bytes", v),
+            PltRel(v)           => write!(f, "PLTREL {}", v),
+            Debug(v)            => write!(f, "DEBUG 0x{:X}", v),
+            TextRel             => write!(f, "TEXTREL"),
+            JmpRel(a)           => write!(f, "JMPREL 0x{:X}", a),
+            BindNow             => write!(f, "BIND_NOW"),
+            InitArray(a)        => write!(f, "INIT_ARRAY 0x{:X}", a),
+            FiniArray(a)        => write!(f, "FINI_ARRAY 0x{:X}", a),
+            InitArraySize(v)    => write!(f, "INIT_ARRAYSZ {} Bytes", v),
+            FiniArraySize(v)    => write!(f, "FINI_ARRAYSZ {} Bytes", v),
+            RunPath(s)          => write!(f, "RUNPATH [{}]", s),
+            Flags(v)            => write!(f, "FLAGS 0x{:X}", v),
+            PreInitArray(a)     => write!(f, "PREINIT_ARRAY 0x{:X}", a),
+            PreInitArraySize(v) => write!(f, "PREINIT_ARRAYSZ {} Bytes", v),
+            SymTabShndx(a)      => write!(f, "SYMTAB_SHNDX 0x{:X}", a),
+            RelrSize(v)         => write!(f, "RELRSZ {} Bytes", v),
+            Relr(a)             => write!(f, "RELR 0x{:X}", a),
+            RelrEntry(v)        => write!(f, "RELRENT {} Bytes", v),
+            GnuHash(a)          => write!(f, "GNU_HASH 0x{:X}", a),
+            VerSym(a)           => write!(f, "VERSYM 0x{:X}", a),
+            RelaCount(v)        => write!(f, "RELACOUNT {}", v),
+            RelCount(v)         => write!(f, "RELCOUNT {}", v),
+            Flags1(v)           => write!(f, "FLAGS_1 0x{:X}", v),
+            VerDef(a)           => write!(f, "VERDEF 0x{:X}", a),
+            VerDefNum(v)        => write!(f, "VERDEFNUM {}", v),
+            VerNeed(a)          => write!(f, "VERNEED 0x{:X}", a),
+            VerNeedNum(v)       => write!(f, "VERNEEDNUM {}", v),
+            Auxiliary(s)        => write!(f, "AUXILIARY [{}]", s),
+            Filter(s)           => write!(f, "FILTER [{}]", s),
+            Other { tag, value } => write!(f, "0x{:X} 0x{:X}", tag, value),
+        }
+    }
+}
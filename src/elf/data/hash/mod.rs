@@ -0,0 +1,283 @@
+//! Symbol hash tables.
+//! Parsing of the SysV (`.hash`) and GNU (`.gnu.hash`) hash tables of the dynamic symbols and
+//! lookup of the symbols with the same algorithm as the dynamic loader.
+
+
+
+use crate::{
+    common::address::Address,
+    error::{ Error, Table },
+};
+
+use super::Symbol;
+
+use std::sync::Arc;
+
+
+
+/// A hash table of the dynamic symbols.
+#[derive(Debug, Clone)]
+pub enum HashTable {
+    /// SysV hash table (`SHT_HASH`, `DT_HASH`).
+    SysV(SysVHash),
+
+    /// GNU hash table (`SHT_GNU_HASH`, `DT_GNU_HASH`).
+    Gnu(GnuHash),
+}
+
+impl HashTable {
+    /// Returns the number of symbols in the dynamic symbol table, as described by the hash table.
+    pub fn count(&self) -> usize {
+        match self {
+            HashTable::SysV(table) => table.count(),
+            HashTable::Gnu(table) => table.count(),
+        }
+    }
+
+    /// Looks up the given name in the hash table and returns the index of the defined symbol with that name.
+    pub fn lookup(&self, name: &str, symbols: &[Arc<Symbol>]) -> Option<usize> {
+        match self {
+            HashTable::SysV(table) => table.lookup(name, symbols),
+            HashTable::Gnu(table) => table.lookup(name, symbols),
+        }
+    }
+}
+
+
+
+/// A SysV hash table.
+#[derive(Debug, Clone)]
+pub struct SysVHash {
+    /// Index of the first symbol of each bucket.
+    pub(super) buckets: Vec<u32>,
+
+    /// Index of the next symbol of the chain of each symbol.
+    pub(super) chains: Vec<u32>,
+}
+
+impl SysVHash {
+    /// Parses the given slice of data into a SysV hash table.
+    /// `offset` is the offset of the table in the file image, used to report errors.
+    pub fn parse(raw: &[u8], offset: usize, read32: fn(&[u8]) -> u32) -> Result<Self, Error> {
+        // Read the header.
+        if raw.len() < 8 {
+            return Err( Error::OutOfBounds { table: Table::Hash, index: 0, offset } );
+        }
+
+        let nbuckets = read32( &raw[0..4] ) as usize;
+        let nchains  = read32( &raw[4..8] ) as usize;
+
+        // Check that the whole table is in the data.
+        let words = nbuckets.checked_add(nchains).filter(|words| words.checked_mul(4).and_then(|size| size.checked_add(8)).is_some_and(|size| size <= raw.len()));
+
+        if words.is_none() {
+            return Err( Error::OutOfBounds { table: Table::Hash, index: 1, offset: offset.saturating_add(4) } );
+        }
+
+        // Read the buckets and the chains.
+        let mut words = raw[8..].chunks_exact(4).map(read32);
+
+        let buckets = words.by_ref().take(nbuckets).collect();
+        let chains = words.take(nchains).collect();
+
+        Ok( Self { buckets, chains } )
+    }
+
+    /// Computes the SysV hash of the given name.
+    pub fn hash(name: &[u8]) -> u32 {
+        name.iter().fold(0u32, |h, c| {
+            let h = (h << 4).wrapping_add( u32::from(*c) );
+            let g = h & 0xF000_0000;
+
+            (h ^ (g >> 24)) & !g
+        })
+    }
+
+    /// Returns the number of symbols in the dynamic symbol table.
+    pub fn count(&self) -> usize {
+        self.chains.len()
+    }
+
+    /// Looks up the given name and returns the index of the defined symbol with that name.
+    pub fn lookup(&self, name: &str, symbols: &[Arc<Symbol>]) -> Option<usize> {
+        if self.buckets.is_empty() {
+            return None;
+        }
+
+        // Get the start of the chain.
+        let mut index = self.buckets[ Self::hash( name.as_bytes() ) as usize % self.buckets.len() ] as usize;
+
+        // Walk the chain, which is never longer than the symbol table.
+        for _ in 0..self.chains.len() {
+            if index == 0 {
+                return None;
+            }
+
+            if defined(symbols, index, name) {
+                return Some( index );
+            }
+
+            index = *self.chains.get(index)? as usize;
+        }
+
+        None
+    }
+
+    /// Returns the index of the first symbol of each bucket.
+    pub fn buckets(&self) -> &Vec<u32> {
+        &self.buckets
+    }
+
+    /// Returns the index of the next symbol of the chain of each symbol.
+    pub fn chains(&self) -> &Vec<u32> {
+        &self.chains
+    }
+}
+
+
+
+/// A GNU hash table.
+#[derive(Debug, Clone)]
+pub struct GnuHash {
+    /// Index of the first symbol in the table.
+    pub(super) symoffset: u32,
+
+    /// Shift of the second hash of the bloom filter.
+    pub(super) shift: u32,
+
+    /// Number of bits of a bloom filter word (the size of an address).
+    pub(super) bits: u32,
+
+    /// Words of the bloom filter.
+    pub(super) bloom: Vec<u64>,
+
+    /// Index of the first symbol of each bucket.
+    pub(super) buckets: Vec<u32>,
+
+    /// Hash of each symbol from `symoffset`, with the lowest bit set at the end of a chain.
+    pub(super) chains: Vec<u32>,
+}
+
+impl GnuHash {
+    /// Parses the given slice of data into a GNU hash table.
+    /// The bloom filter words have the size of an address.
+    /// `offset` is the offset of the table in the file image, used to report errors.
+    pub fn parse<const INC: usize>(raw: &[u8], offset: usize, read: fn(&[u8]) -> Address, read32: fn(&[u8]) -> u32) -> Result<Self, Error> {
+        // Read a word of the table.
+        let word = |index: usize| match index.checked_mul(4).and_then(|start| raw.get(start..start.checked_add(4)?)) {
+            Some(bytes) => Ok( read32(bytes) ),
+            _ => Err( Error::OutOfBounds { table: Table::Hash, index, offset: offset.saturating_add( index.saturating_mul(4) ) } ),
+        };
+
+        // Read the header.
+        let nbuckets  = word(0)? as usize;
+        let symoffset = word(1)?;
+        let nbloom    = word(2)? as usize;
+        let shift     = word(3)?;
+
+        // Read the bloom filter.
+        let start = 16;
+        let end = nbloom.checked_mul(INC).and_then(|size| size.checked_add(start)).filter(|end| *end <= raw.len());
+
+        let bloom = match end {
+            Some(end) => raw[start..end].chunks_exact(INC).map(|chunk| u64::from( read(chunk) )).collect::<Vec<_>>(),
+            _ => return Err( Error::OutOfBounds { table: Table::Hash, index: 2, offset: offset.saturating_add(8) } ),
+        };
+
+        // Read the buckets.
+        let first = (16 + (nbloom * INC)) / 4;
+
+        let buckets = match first.checked_add(nbuckets).filter(|end| end.saturating_mul(4) <= raw.len()) {
+            Some(_) => (first..first + nbuckets).map(word).collect::<Result<Vec<_>, _>>()?,
+            _ => return Err( Error::OutOfBounds { table: Table::Hash, index: 0, offset: offset.saturating_add(first * 4) } ),
+        };
+
+        // Read the chains up to the end of the chain of the last symbol.
+        let base = first + nbuckets;
+        let mut chains = Vec::new();
+
+        if let Some(last) = buckets.iter().copied().filter(|index| *index >= symoffset).max() {
+            for index in base.. {
+                let hash = word(index)?;
+                chains.push( hash );
+
+                if (index - base >= (last - symoffset) as usize) && ((hash & 1) != 0) {
+                    break;
+                }
+            }
+        }
+
+        Ok( Self { symoffset, shift, bits: 8 * INC as u32, bloom, buckets, chains } )
+    }
+
+    /// Computes the GNU hash of the given name.
+    pub fn hash(name: &[u8]) -> u32 {
+        name.iter().fold(5381u32, |h, c| h.wrapping_mul(33).wrapping_add( u32::from(*c) ))
+    }
+
+    /// Returns the number of symbols in the dynamic symbol table.
+    pub fn count(&self) -> usize {
+        self.symoffset as usize + self.chains.len()
+    }
+
+    /// Looks up the given name and returns the index of the defined symbol with that name.
+    pub fn lookup(&self, name: &str, symbols: &[Arc<Symbol>]) -> Option<usize> {
+        if self.bloom.is_empty() || self.buckets.is_empty() {
+            return None;
+        }
+
+        let hash = Self::hash( name.as_bytes() );
+
+        // Reject the name if any of its two bits is not set in the bloom filter.
+        let word = self.bloom[ (hash / self.bits) as usize % self.bloom.len() ];
+        let mask = (1u64 << (hash % self.bits)) | (1u64 << (hash.checked_shr(self.shift).unwrap_or(0) % self.bits));
+
+        if (word & mask) != mask {
+            return None;
+        }
+
+        // Get the start of the chain.
+        let mut index = self.buckets[ hash as usize % self.buckets.len() ];
+
+        if index < self.symoffset {
+            return None;
+        }
+
+        // Walk the chain, comparing the hashes before the names.
+        loop {
+            let chain = *self.chains.get( (index - self.symoffset) as usize )?;
+
+            if ((chain | 1) == (hash | 1)) && defined(symbols, index as usize, name) {
+                return Some( index as usize );
+            }
+
+            if (chain & 1) != 0 {
+                return None;
+            }
+
+            index = index.checked_add(1)?;
+        }
+    }
+
+    /// Returns the index of the first symbol in the table.
+    pub fn symoffset(&self) -> u32 {
+        self.symoffset
+    }
+
+    /// Returns the words of the bloom filter.
+    pub fn bloom(&self) -> &Vec<u64> {
+        &self.bloom
+    }
+
+    /// Returns the index of the first symbol of each bucket.
+    pub fn buckets(&self) -> &Vec<u32> {
+        &self.buckets
+    }
+}
+
+
+
+/// Returns `true` if the symbol at the given index is defined and has the given name.
+fn defined(symbols: &[Arc<Symbol>], index: usize, name: &str) -> bool {
+    symbols.get(index).is_some_and(|symbol| (symbol.shidx != 0) && (symbol.name == name))
+}
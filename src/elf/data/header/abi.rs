@@ -48,6 +48,9 @@ pub enum TargetOS {
 
     OpenVOS(u8),
 
+    /// OS ABI not known by this crate, with its ABI version.
+    Unknown(u8, u8),
+
     /// Unknown / Undefined ABI.
 	None,
 }
@@ -81,7 +84,37 @@ impl core::convert::From<(u8, u8)> for TargetOS {
 			0x11 => CloudABI(v),
 			0x12 => OpenVOS(v),
 
-			_ => None,
+			_ => Unknown(os, v),
+		}
+	}
+}
+
+impl core::convert::From<TargetOS> for (u8, u8) {
+	fn from(os: TargetOS) -> (u8, u8) {
+		use TargetOS::*;
+
+		match os {
+			None             => (0x00, 0),
+			SystemV(v)       => (0x00, v),
+			HPUX(v)          => (0x01, v),
+			NetBSD(v)        => (0x02, v),
+			Linux(v)         => (0x03, v),
+			GNUHurd(v)       => (0x04, v),
+			Solaris(v)       => (0x06, v),
+			AIX(v)           => (0x07, v),
+			IRIX(v)          => (0x08, v),
+			FreeBSD(v)       => (0x09, v),
+			Tru64(v)         => (0x0A, v),
+			NovellModesto(v) => (0x0B, v),
+			OpenBSD(v)       => (0x0C, v),
+			OpenVMS(v)       => (0x0D, v),
+			NonStopKernel(v) => (0x0E, v),
+			AROS(v)          => (0x0F, v),
+			FenixOS(v)       => (0x10, v),
+			CloudABI(v)      => (0x11, v),
+			OpenVOS(v)       => (0x12, v),
+
+			Unknown(os, v)   => (os, v),
 		}
 	}
 }
@@ -110,7 +143,7 @@ impl core::convert::From<u8> for TargetOS {
 			0x11 => CloudABI(0),
 			0x12 => OpenVOS(0),
 
-			_ => None,
+			_ => Unknown(os, 0),
 		}
 	}
 }
@@ -140,6 +173,8 @@ impl core::fmt::Display for TargetOS {
 			CloudABI(v)      => format!("Cload ABI - rev {}", v),
 			OpenVOS(v)       => format!("Stratus Technologies OpenVOS - rev {}", v),
 
+			Unknown(os, v)   => format!("Unknown OS ABI 0x{:02X} - rev {}", os, v),
+
 			None => String::from("No OS ABI"),
 		};
 
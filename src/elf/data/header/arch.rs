@@ -141,6 +141,7 @@ impl core::convert::From<u16> for Architecture {
             0x014 => PowerPC,
             0x015 => PowerPCX64,
             0x016 => IBM390,
+            0x017 => IBMSPU,
 
             0x024 => V800,
             0x025 => FR20,
@@ -155,6 +156,8 @@ impl core::convert::From<u16> for Architecture {
             0x02E => H8300,
             0x02F => H8300H,
 
+            0x030 => H8S,
+            0x031 => H8500,
             0x032 => IA64,
             0x033 => MIPSX,
             0x034 => MColdFire,
@@ -22,8 +22,11 @@ pub enum FileType {
     /// Process specific file.
     Process(u16),
 
-    /// Unknown or undefined file type.
+    /// Undefined file type.
     None,
+
+    /// Unknown file type, with its raw value.
+    Other(u16),
 }
 
 impl core::convert::From<u16> for FileType {
@@ -38,7 +41,23 @@ impl core::convert::From<u16> for FileType {
 
             0xFF00..=0xFFFF => FileType::Process(d),
 
-            _ => FileType::None,
+            0x00 => FileType::None,
+
+            _ => FileType::Other(d),
+        }
+    }
+}
+
+impl core::convert::From<FileType> for u16 {
+    fn from(t: FileType) -> u16 {
+        match t {
+            FileType::None => 0x00,
+            FileType::Relocatable => 0x01,
+            FileType::Executable => 0x02,
+            FileType::Dynamic => 0x03,
+            FileType::Core => 0x04,
+
+            FileType::OS(d) | FileType::Process(d) | FileType::Other(d) => d,
         }
     }
 }
@@ -57,6 +76,8 @@ impl core::fmt::Display for FileType {
             OS(d) => format!("OS Specific 0x{:08X}", d),
 
             Process(d) => format!("Process Specific 0x{:08X}", d),
+
+            Other(d) => format!("Unknown 0x{:04X}", d),
         };
 
         write!(f, "{}", arg)
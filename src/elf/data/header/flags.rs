@@ -0,0 +1,520 @@
+//! Architecture specific flags of the file header (`e_flags`).
+
+
+
+use super::Architecture;
+
+
+
+/// Mask of the EABI version of ARM (`EF_ARM_EABIMASK`).
+const EF_ARM_EABIMASK: u32 = 0xFF00_0000;
+
+/// Flag of ARM code in BE8 byte order (`EF_ARM_BE8`).
+const EF_ARM_BE8: u32 = 0x0080_0000;
+
+/// Flag of the ARM hard float ABI (`EF_ARM_ABI_FLOAT_HARD`).
+const EF_ARM_ABI_FLOAT_HARD: u32 = 0x0400;
+
+/// Flag of the ARM soft float ABI (`EF_ARM_ABI_FLOAT_SOFT`).
+const EF_ARM_ABI_FLOAT_SOFT: u32 = 0x0200;
+
+/// Flag of position independent ARM code (`EF_ARM_PIC`).
+const EF_ARM_PIC: u32 = 0x0020;
+
+/// Flag of RISC-V code with compressed instructions (`EF_RISCV_RVC`).
+const EF_RISCV_RVC: u32 = 0x0001;
+
+/// Mask of the RISC-V float ABI (`EF_RISCV_FLOAT_ABI`).
+const EF_RISCV_FLOAT_ABI: u32 = 0x0006;
+
+/// Flag of the RISC-V embedded base ISA (`EF_RISCV_RVE`).
+const EF_RISCV_RVE: u32 = 0x0008;
+
+/// Flag of the RISC-V total store ordering memory model (`EF_RISCV_TSO`).
+const EF_RISCV_TSO: u32 = 0x0010;
+
+/// Mask of the MIPS architecture level (`EF_MIPS_ARCH`).
+const EF_MIPS_ARCH: u32 = 0xF000_0000;
+
+/// Mask of the MIPS ABI (`EF_MIPS_ABI`).
+const EF_MIPS_ABI: u32 = 0x0000_F000;
+
+/// Flag of the MIPS N32 ABI (`EF_MIPS_ABI2`).
+const EF_MIPS_ABI2: u32 = 0x0000_0020;
+
+/// Mask of the AVR device family (`EF_AVR_MACH`).
+const EF_AVR_MACH: u32 = 0x0000_007F;
+
+
+
+/// The decoded flags of the file header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArchFlags {
+    /// Flags of 32 bit ARM.
+    Arm(ArmFlags),
+
+    /// Flags of RISC-V.
+    RiscV(RiscVFlags),
+
+    /// Flags of MIPS.
+    Mips(MipsFlags),
+
+    /// Flags of AVR.
+    Avr(AvrFamily),
+
+    /// Flags of an architecture without a decoder.
+    Other(u32),
+}
+
+impl ArchFlags {
+    /// Decodes the flags of the file header for the given architecture.
+    pub fn decode(architecture: Architecture, flags: u32) -> Self {
+        match architecture {
+            Architecture::Aarch32 => ArchFlags::Arm( ArmFlags::from(flags) ),
+            Architecture::RISCV   => ArchFlags::RiscV( RiscVFlags::from(flags) ),
+            Architecture::MIPS | Architecture::MIPSRS3000 => ArchFlags::Mips( MipsFlags::from(flags) ),
+            Architecture::AVR     => ArchFlags::Avr( AvrFamily::from( (flags & EF_AVR_MACH) as u8 ) ),
+
+            _ => ArchFlags::Other(flags),
+        }
+    }
+}
+
+impl core::fmt::Display for ArchFlags {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self {
+            ArchFlags::Arm(flags) => write!(f, "{}", flags),
+            ArchFlags::RiscV(flags) => write!(f, "{}", flags),
+            ArchFlags::Mips(flags) => write!(f, "{}", flags),
+            ArchFlags::Avr(family) => write!(f, "{}", family),
+            ArchFlags::Other(flags) => write!(f, "0x{:X}", flags),
+        }
+    }
+}
+
+
+
+/// The flags of 32 bit ARM.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ArmFlags {
+    /// EABI version (0 for objects that predate the EABI).
+    pub(super) eabi: u8,
+
+    /// Float ABI.
+    pub(super) float: ArmFloat,
+
+    /// `true` if the code is in BE8 byte order.
+    pub(super) be8: bool,
+
+    /// `true` if the code is position independent.
+    pub(super) pic: bool,
+}
+
+impl ArmFlags {
+    /// Returns the EABI version (0 for objects that predate the EABI).
+    pub fn eabi(&self) -> u8 {
+        self.eabi
+    }
+
+    /// Returns the float ABI.
+    pub fn float(&self) -> ArmFloat {
+        self.float
+    }
+
+    /// Returns `true` if the code is in BE8 byte order.
+    pub fn be8(&self) -> bool {
+        self.be8
+    }
+
+    /// Returns `true` if the code is position independent.
+    pub fn pic(&self) -> bool {
+        self.pic
+    }
+}
+
+impl From<u32> for ArmFlags {
+    fn from(flags: u32) -> Self {
+        let float = match (flags & EF_ARM_ABI_FLOAT_HARD != 0, flags & EF_ARM_ABI_FLOAT_SOFT != 0) {
+            (true, _) => ArmFloat::Hard,
+            (_, true) => ArmFloat::Soft,
+            _ => ArmFloat::Unspecified,
+        };
+
+        Self {
+            eabi: ((flags & EF_ARM_EABIMASK) >> 24) as u8,
+            float,
+            be8: (flags & EF_ARM_BE8) != 0,
+            pic: (flags & EF_ARM_PIC) != 0,
+        }
+    }
+}
+
+impl core::fmt::Display for ArmFlags {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self.eabi {
+            0 => write!(f, "GNU EABI")?,
+            eabi => write!(f, "Version{} EABI", eabi)?,
+        }
+
+        match self.float {
+            ArmFloat::Hard => write!(f, ", hard-float ABI")?,
+            ArmFloat::Soft => write!(f, ", soft-float ABI")?,
+            ArmFloat::Unspecified => (),
+        }
+
+        if self.be8 {
+            write!(f, ", BE8")?;
+        }
+
+        if self.pic {
+            write!(f, ", position independent")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The float ABI of 32 bit ARM.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArmFloat {
+    /// Floating point arguments are passed in integer registers.
+    Soft,
+
+    /// Floating point arguments are passed in VFP registers.
+    Hard,
+
+    /// The object does not state its float ABI.
+    Unspecified,
+}
+
+
+
+/// The flags of RISC-V.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RiscVFlags {
+    /// `true` if the code uses compressed instructions.
+    pub(super) rvc: bool,
+
+    /// Float ABI.
+    pub(super) float: RiscVFloat,
+
+    /// `true` if the code targets the embedded base ISA (RV32E).
+    pub(super) rve: bool,
+
+    /// `true` if the code requires the total store ordering memory model.
+    pub(super) tso: bool,
+}
+
+impl RiscVFlags {
+    /// Returns `true` if the code uses compressed instructions.
+    pub fn rvc(&self) -> bool {
+        self.rvc
+    }
+
+    /// Returns the float ABI.
+    pub fn float(&self) -> RiscVFloat {
+        self.float
+    }
+
+    /// Returns `true` if the code targets the embedded base ISA (RV32E).
+    pub fn rve(&self) -> bool {
+        self.rve
+    }
+
+    /// Returns `true` if the code requires the total store ordering memory model.
+    pub fn tso(&self) -> bool {
+        self.tso
+    }
+}
+
+impl From<u32> for RiscVFlags {
+    fn from(flags: u32) -> Self {
+        let float = match flags & EF_RISCV_FLOAT_ABI {
+            0x0 => RiscVFloat::Soft,
+            0x2 => RiscVFloat::Single,
+            0x4 => RiscVFloat::Double,
+            _   => RiscVFloat::Quad,
+        };
+
+        Self {
+            rvc: (flags & EF_RISCV_RVC) != 0,
+            float,
+            rve: (flags & EF_RISCV_RVE) != 0,
+            tso: (flags & EF_RISCV_TSO) != 0,
+        }
+    }
+}
+
+impl core::fmt::Display for RiscVFlags {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        let float = match self.float {
+            RiscVFloat::Soft => "soft-float ABI",
+            RiscVFloat::Single => "single-float ABI",
+            RiscVFloat::Double => "double-float ABI",
+            RiscVFloat::Quad => "quad-float ABI",
+        };
+
+        if self.rvc {
+            write!(f, "RVC, ")?;
+        }
+
+        write!(f, "{}", float)?;
+
+        if self.rve {
+            write!(f, ", RVE")?;
+        }
+
+        if self.tso {
+            write!(f, ", TSO")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The float ABI of RISC-V.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RiscVFloat {
+    /// Floating point arguments are passed in integer registers.
+    Soft,
+
+    /// Single precision arguments are passed in floating point registers.
+    Single,
+
+    /// Single and double precision arguments are passed in floating point registers.
+    Double,
+
+    /// Arguments up to quad precision are passed in floating point registers.
+    Quad,
+}
+
+
+
+/// The flags of MIPS.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MipsFlags {
+    /// Architecture level.
+    pub(super) arch: MipsArch,
+
+    /// ABI.
+    pub(super) abi: MipsAbi,
+}
+
+impl MipsFlags {
+    /// Returns the architecture level.
+    pub fn arch(&self) -> MipsArch {
+        self.arch
+    }
+
+    /// Returns the ABI.
+    pub fn abi(&self) -> MipsAbi {
+        self.abi
+    }
+}
+
+impl From<u32> for MipsFlags {
+    fn from(flags: u32) -> Self {
+        let arch = match (flags & EF_MIPS_ARCH) >> 28 {
+            0x0 => MipsArch::Mips1,
+            0x1 => MipsArch::Mips2,
+            0x2 => MipsArch::Mips3,
+            0x3 => MipsArch::Mips4,
+            0x4 => MipsArch::Mips5,
+            0x5 => MipsArch::Mips32,
+            0x6 => MipsArch::Mips64,
+            0x7 => MipsArch::Mips32R2,
+            0x8 => MipsArch::Mips64R2,
+            0x9 => MipsArch::Mips32R6,
+            0xA => MipsArch::Mips64R6,
+            level => MipsArch::Other(level as u8),
+        };
+
+        let abi = match ((flags & EF_MIPS_ABI) >> 12, flags & EF_MIPS_ABI2 != 0) {
+            (0x0, true) => MipsAbi::N32,
+            (0x0, _) => MipsAbi::Unspecified,
+            (0x1, _) => MipsAbi::O32,
+            (0x2, _) => MipsAbi::O64,
+            (0x3, _) => MipsAbi::EABI32,
+            (0x4, _) => MipsAbi::EABI64,
+            (abi, _) => MipsAbi::Other(abi as u8),
+        };
+
+        Self { arch, abi }
+    }
+}
+
+impl core::fmt::Display for MipsFlags {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "{}", self.arch)?;
+
+        match self.abi {
+            MipsAbi::Unspecified => Ok(()),
+            abi => write!(f, ", {}", abi),
+        }
+    }
+}
+
+/// The architecture level of MIPS.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MipsArch {
+    Mips1,
+    Mips2,
+    Mips3,
+    Mips4,
+    Mips5,
+    Mips32,
+    Mips64,
+    Mips32R2,
+    Mips64R2,
+    Mips32R6,
+    Mips64R6,
+
+    /// Unknown architecture level.
+    Other(u8),
+}
+
+impl core::fmt::Display for MipsArch {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        use MipsArch::*;
+
+        match *self {
+            Mips1    => write!(f, "mips1"),
+            Mips2    => write!(f, "mips2"),
+            Mips3    => write!(f, "mips3"),
+            Mips4    => write!(f, "mips4"),
+            Mips5    => write!(f, "mips5"),
+            Mips32   => write!(f, "mips32"),
+            Mips64   => write!(f, "mips64"),
+            Mips32R2 => write!(f, "mips32r2"),
+            Mips64R2 => write!(f, "mips64r2"),
+            Mips32R6 => write!(f, "mips32r6"),
+            Mips64R6 => write!(f, "mips64r6"),
+            Other(level) => write!(f, "Unknown architecture level {}", level),
+        }
+    }
+}
+
+/// The ABI of MIPS.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MipsAbi {
+    /// The original 32 bit ABI.
+    O32,
+
+    /// The original ABI extended to 64 bit registers.
+    O64,
+
+    /// The 32 bit embedded ABI.
+    EABI32,
+
+    /// The 64 bit embedded ABI.
+    EABI64,
+
+    /// The 64 bit ABI with 32 bit pointers.
+    N32,
+
+    /// The object does not state its ABI: O32 in 32 bit files and N64 in 64 bit files.
+    Unspecified,
+
+    /// Unknown ABI.
+    Other(u8),
+}
+
+impl core::fmt::Display for MipsAbi {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        use MipsAbi::*;
+
+        match *self {
+            O32 => write!(f, "o32"),
+            O64 => write!(f, "o64"),
+            EABI32 => write!(f, "eabi32"),
+            EABI64 => write!(f, "eabi64"),
+            N32 => write!(f, "n32"),
+            Unspecified => write!(f, "Unspecified ABI"),
+            Other(abi) => write!(f, "Unknown ABI {}", abi),
+        }
+    }
+}
+
+
+
+/// The device family of AVR.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AvrFamily {
+    Avr1,
+    Avr2,
+    Avr25,
+    Avr3,
+    Avr31,
+    Avr35,
+    Avr4,
+    Avr5,
+    Avr51,
+    Avr6,
+    AvrTiny,
+    XMega1,
+    XMega2,
+    XMega3,
+    XMega4,
+    XMega5,
+    XMega6,
+    XMega7,
+
+    /// Unknown device family.
+    Other(u8),
+}
+
+impl From<u8> for AvrFamily {
+    fn from(mach: u8) -> Self {
+        use AvrFamily::*;
+
+        match mach {
+            1   => Avr1,
+            2   => Avr2,
+            25  => Avr25,
+            3   => Avr3,
+            31  => Avr31,
+            35  => Avr35,
+            4   => Avr4,
+            5   => Avr5,
+            51  => Avr51,
+            6   => Avr6,
+            100 => AvrTiny,
+            101 => XMega1,
+            102 => XMega2,
+            103 => XMega3,
+            104 => XMega4,
+            105 => XMega5,
+            106 => XMega6,
+            107 => XMega7,
+            _   => Other(mach),
+        }
+    }
+}
+
+impl core::fmt::Display for AvrFamily {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        use AvrFamily::*;
+
+        match *self {
+            Avr1    => write!(f, "avr1"),
+            Avr2    => write!(f, "avr2"),
+            Avr25   => write!(f, "avr25"),
+            Avr3    => write!(f, "avr3"),
+            Avr31   => write!(f, "avr31"),
+            Avr35   => write!(f, "avr35"),
+            Avr4    => write!(f, "avr4"),
+            Avr5    => write!(f, "avr5"),
+            Avr51   => write!(f, "avr51"),
+            Avr6    => write!(f, "avr6"),
+            AvrTiny => write!(f, "avrtiny"),
+            XMega1  => write!(f, "avrxmega1"),
+            XMega2  => write!(f, "avrxmega2"),
+            XMega3  => write!(f, "avrxmega3"),
+            XMega4  => write!(f, "avrxmega4"),
+            XMega5  => write!(f, "avrxmega5"),
+            XMega6  => write!(f, "avrxmega6"),
+            XMega7  => write!(f, "avrxmega7"),
+            Other(mach) => write!(f, "Unknown AVR family {}", mach),
+        }
+    }
+}
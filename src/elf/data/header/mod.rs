@@ -6,6 +6,7 @@
 mod abi;
 mod arch;
 mod filetype;
+mod flags;
 
 
 
@@ -13,14 +14,21 @@ pub use abi::TargetOS;
 pub use arch::Architecture;
 pub use endianness::Endianness;
 pub use filetype::FileType;
+pub use flags::{ ArchFlags, ArmFlags, ArmFloat, AvrFamily, MipsAbi, MipsArch, MipsFlags, RiscVFlags, RiscVFloat };
 
-use crate::common::address::Address;
+use crate::{
+    common::address::Address,
+    error::{ Error, Table },
+};
 
 
 
 /// A common file header structure. Will be instantiated by each implementator.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct FileHeader {
+    /// Identification bytes (`e_ident`), kept to write back the version and padding unchanged.
+    pub(super) ident: [u8; 16],
+
     /// Endianness of the target hardware.
     pub(super) endianness: Endianness,
 
@@ -33,6 +41,12 @@ pub struct FileHeader {
     /// Target architecture.
     pub(super) architecture: Architecture,
 
+    /// Raw target architecture (`e_machine`), kept to write back the unknown architectures.
+    pub(super) machine: u16,
+
+    /// Version of the object file.
+    pub(super) version: u32,
+
     /// Entry point of the program.
     pub(super) entry: Address,
 
@@ -49,21 +63,24 @@ pub struct FileHeader {
     pub(super) phtesize: u16,
 
     /// Number of Program Header Table entries.
-    pub(super) phnum: u16,
+    /// Resolved through the first section when it overflows (`PN_XNUM`).
+    pub(super) phnum: u32,
 
     /// Size of a Section Header Table entry.
     pub(super) shtesize: u16,
 
     /// Number of Section Header Table entries.
-    pub(super) shnum: u16,
+    /// Resolved through the first section when it overflows (`e_shnum == 0`).
+    pub(super) shnum: u32,
 
     /// Index of the Section Header Table entry of the section containing section names.
-    pub(super) shstrndx: u16,
+    /// Resolved through the first section when it overflows (`SHN_XINDEX`).
+    pub(super) shstrndx: u32,
 }
 
 impl FileHeader {
     /// Parses the given slice of data into an ELF file header.
-    pub fn parse<R: AsRef<[u8]>, const INC: usize>(raw: R, read: fn(&[u8]) -> Address) -> Result<Self, ()> {
+    pub fn parse<R: AsRef<[u8]>, const INC: usize>(raw: R, read: fn(&[u8]) -> Address) -> Result<Self, Error> {
         // Header size constant.
         let hsize: usize = 40 + (3 * INC);
 
@@ -72,13 +89,13 @@ impl FileHeader {
 
         // Check there is minimum length.
         if raw.len() < hsize {
-            return Err(());
+            return Err( Error::OutOfBounds { table: Table::FileHeader, index: 0, offset: 0 } );
         }
 
         // Validate the magic number.
         match raw[0x00..0x04] {
             [0x7F, 0x45, 0x4C, 0x46] => (),
-            _ => return Err( () ),
+            _ => return Err( Error::BadMagic ),
         }
 
         // Get the endianness.
@@ -86,7 +103,7 @@ impl FileHeader {
             1 => Endianness::Little,
             2 => Endianness::Big,
 
-            _ => return Err( () ),
+            e => return Err( Error::UnsupportedEndianness(e) ),
         };
 
         // Function to read a 16 bit integer.
@@ -108,7 +125,11 @@ impl FileHeader {
         let filetype = FileType::from( read16( &raw[0x10..0x12] ) );
 
         // Get the target architecture.
-        let architecture = Architecture::from( read16( &raw[0x12..0x14] ) );
+        let machine = read16( &raw[0x12..0x14] );
+        let architecture = Architecture::from( machine );
+
+        // Get the object file version.
+        let version = read32( &raw[0x14..0x18] );
 
         // Begin non standard section.
         let mut i = 0x18;
@@ -130,8 +151,10 @@ impl FileHeader {
         i += 4;
 
         // Read the size of this header.
-        if read16(&raw[i..i+2]) as usize != hsize {
-            return Err( () );
+        let ehsize = read16(&raw[i..i+2]) as usize;
+
+        if ehsize != hsize {
+            return Err( Error::BadHeaderSize { table: Table::FileHeader, expected: hsize, found: ehsize } );
         }
         i += 2;
 
@@ -140,7 +163,7 @@ impl FileHeader {
         i += 2;
 
         // Read the number of program headers.
-        let phnum = read16( &raw[i..i+2] );
+        let phnum = u32::from( read16( &raw[i..i+2] ) );
         i += 2;
 
         // Read the size of section header entries.
@@ -148,20 +171,27 @@ impl FileHeader {
         i += 2;
 
         // Read the number of section headers.
-        let shnum = read16( &raw[i..i+2] );
+        let shnum = u32::from( read16( &raw[i..i+2] ) );
         i += 2;
 
         // Read the section name section index.
-        let shstrndx = read16(&raw[i..i+2]);
+        let shstrndx = u32::from( read16(&raw[i..i+2]) );
         i += 2;
 
-        assert_eq!(i, hsize);
+        debug_assert_eq!(i, hsize);
+
+        // Keep the identification bytes.
+        let mut ident = [0u8; 16];
+        ident.copy_from_slice( &raw[0x00..0x10] );
 
         Ok(Self {
+            ident,
             endianness,
             targetos,
             filetype,
             architecture,
+            machine,
+            version,
             entry,
             phtoffset,
             shtoffset,
@@ -174,11 +204,109 @@ impl FileHeader {
         })
     }
 
+    /// Writes the file header into the given slice of data.
+    /// The slice must be at least as long as the file header of the class.
+    pub fn write<const INC: usize>(&self, raw: &mut [u8], write: fn(&mut [u8], Address)) {
+        // Header size constant.
+        let hsize: usize = 40 + (3 * INC);
+
+        // Function to write a 16 bit integer.
+        let write16: fn(&mut [u8], u16) = match self.endianness {
+            Endianness::Big    => crate::common::bytes::write16::<byteorder::BigEndian>,
+            Endianness::Little => crate::common::bytes::write16::<byteorder::LittleEndian>,
+        };
+
+        // Function to write a 32 bit integer.
+        let write32: fn(&mut [u8], u32) = match self.endianness {
+            Endianness::Big    => crate::common::bytes::write32::<byteorder::BigEndian>,
+            Endianness::Little => crate::common::bytes::write32::<byteorder::LittleEndian>,
+        };
+
+        // Write the identification, keeping the original version and padding.
+        raw[0x00..0x10].copy_from_slice( &self.ident );
+        raw[0x00..0x04].copy_from_slice( &[0x7F, 0x45, 0x4C, 0x46] );
+        raw[0x04] = (INC / 4) as u8;
+        raw[0x05] = match self.endianness {
+            Endianness::Little => 1,
+            Endianness::Big => 2,
+        };
+
+        // Write the target OS ABI.
+        let (os, v) = <(u8, u8)>::from( self.targetos );
+        raw[0x07] = os;
+        raw[0x08] = v;
+
+        // Write the ELF type, target architecture and version.
+        write16( &mut raw[0x10..0x12], u16::from(self.filetype) );
+        write16( &mut raw[0x12..0x14], self.machine );
+        write32( &mut raw[0x14..0x18], self.version );
+
+        // Begin non standard section.
+        let mut i = 0x18;
+
+        // Write entry point.
+        write( &mut raw[i..i+INC], self.entry );
+        i += INC;
+
+        // Write the program header table offset.
+        write( &mut raw[i..i+INC], self.phtoffset );
+        i += INC;
+
+        // Write the section header table offset.
+        write( &mut raw[i..i+INC], self.shtoffset );
+        i += INC;
+
+        // Write the flags.
+        write32( &mut raw[i..i+4], self.flags );
+        i += 4;
+
+        // Write the size of this header.
+        write16( &mut raw[i..i+2], hsize as u16 );
+        i += 2;
+
+        // Write the size of program header entries.
+        write16( &mut raw[i..i+2], self.phtesize );
+        i += 2;
+
+        // Write the number of program headers, escaped if it overflows.
+        write16( &mut raw[i..i+2], core::cmp::min(self.phnum, super::program::XNUM) as u16 );
+        i += 2;
+
+        // Write the size of section header entries.
+        write16( &mut raw[i..i+2], self.shtesize );
+        i += 2;
+
+        // Write the number of section headers, escaped if it overflows.
+        let shnum = match self.shnum {
+            n if n >= super::section::LORESERVE => 0,
+            n => n as u16,
+        };
+
+        write16( &mut raw[i..i+2], shnum );
+        i += 2;
+
+        // Write the section name section index, escaped if it overflows.
+        let shstrndx = match self.shstrndx {
+            n if n >= super::section::LORESERVE => super::section::XINDEX as u16,
+            n => n as u16,
+        };
+
+        write16( &mut raw[i..i+2], shstrndx );
+        i += 2;
+
+        debug_assert_eq!(i, hsize);
+    }
+
     /// Returns the target architecture.
     pub const fn architecture(&self)  -> Architecture {
         self.architecture
     }
 
+    /// Returns the raw target architecture (`e_machine`), also for the unknown architectures.
+    pub const fn machine(&self)  -> u16 {
+        self.machine
+    }
+
     /// Returns the target endianness.
     pub const fn endianness(&self)  -> Endianness {
         self.endianness
@@ -188,6 +316,27 @@ impl FileHeader {
     pub const fn os(&self)  -> TargetOS {
         self.targetos
     }
+
+    /// Returns the object file type.
+    pub const fn filetype(&self)  -> FileType {
+        self.filetype
+    }
+
+    /// Returns the entry point of the program.
+    /// The width of the address is the class of the file.
+    pub const fn entry(&self)  -> Address {
+        self.entry
+    }
+
+    /// Returns the raw architecture flags.
+    pub const fn flags(&self)  -> u32 {
+        self.flags
+    }
+
+    /// Returns the architecture flags decoded for the target architecture.
+    pub fn archflags(&self) -> ArchFlags {
+        ArchFlags::decode(self.architecture, self.flags)
+    }
 }
 
 impl FileHeader {
@@ -205,7 +354,11 @@ impl FileHeader {
         // Add the file type.
         string += &format!("  - {}\n", self.filetype);
 
-        string += &format!("  - Flags: 0x{:X}\n", self.flags);
+        // Add the flags, decoded for the known architectures.
+        match self.archflags() {
+            ArchFlags::Other(flags) => string += &format!("  - Flags: 0x{:X}\n", flags),
+            decoded => string += &format!("  - Flags: 0x{:X} ({})\n", self.flags, decoded),
+        }
 
         // Add the entry point.
         string += &format!("  - Entry: 0x{:X}\n", self.entry);
@@ -2,19 +2,45 @@
 
 
 
+pub mod compression;
+pub mod dynamic;
+pub mod hash;
 pub mod header;
+pub mod note;
 pub mod program;
+pub mod relocation;
 pub mod section;
 pub mod symbol;
+pub mod version;
 
 
 
+pub use compression::CompressionHeader;
+pub use dynamic::DynamicEntry;
+pub use hash::HashTable;
 pub use header::FileHeader;
-pub use program::ProgramHeader;
+pub use note::{ Note, NoteIterator };
+pub use program::{ AddressSpace, ProgramHeader };
+pub use relocation::{ Relocation, RelocationTable };
 pub use section::SectionHeader;
 pub use symbol::Symbol;
+pub use version::VersionRequirement;
 
-use std::sync::Arc;
+use program::ProgramType;
+use section::SectionType;
+
+use crate::{
+    common::address::Address,
+    error::{ Error, Table },
+    image::MemoryImage,
+};
+
+use std::{
+    borrow::Cow,
+    collections::HashMap,
+    ops::Range,
+    sync::Arc,
+};
 
 
 
@@ -23,173 +49,1716 @@ pub struct ELFData {
     /// File header of an ELF object.
     pub(super) header: Arc<FileHeader>,
 
-    /// List of program headers.
-    pub(super) programs: Vec<Arc<ProgramHeader>>,
+    /// List of program headers.
+    pub(super) programs: Vec<Arc<ProgramHeader>>,
+
+    /// List of section headers.
+    pub(super) sections: Vec<Arc<SectionHeader>>,
+
+    /// List of symbols.
+    pub(super) symbols: Vec<Arc<Symbol>>,
+
+    /// List of dynamic symbols.
+    pub(super) dynsyms: Vec<Arc<Symbol>>,
+
+    /// Hash table of the dynamic symbols.
+    pub(super) hash: Option<HashTable>,
+
+    /// Versions required from each library.
+    pub(super) requirements: Vec<VersionRequirement>,
+
+    /// `true` if the sections were modified and the file must be laid out again.
+    pub(super) modified: bool,
+}
+
+impl ELFData {
+    pub fn parse(raw: &[u8]) -> Result<Self, Error> {
+        // Validate the magic number before reading anything else.
+        if raw.get(0x00..0x04) != Some( &[0x7F, 0x45, 0x4C, 0x46] ) {
+            return Err( Error::BadMagic );
+        }
+
+        // Get the class and the data encoding of the file.
+        let (class, data) = match (raw.get(0x04), raw.get(0x05)) {
+            (Some(class), Some(data)) => (*class, *data),
+            _ => return Err( Error::OutOfBounds { table: Table::FileHeader, index: 0, offset: 0 } ),
+        };
+
+        // Get the read function.
+        let read: fn(&[u8]) -> Address = match (class, data) {
+            (1, 1) => crate::common::address::read32::<byteorder::LittleEndian>,
+            (1, 2) => crate::common::address::read32::<byteorder::BigEndian>,
+
+            (2, 1) => crate::common::address::read64::<byteorder::LittleEndian>,
+            (2, 2) => crate::common::address::read64::<byteorder::BigEndian>,
+
+            (1 | 2, e) => return Err( Error::UnsupportedEndianness(e) ),
+            (c, _) => return Err( Error::UnsupportedClass(c) ),
+        };
+
+        // Get the read 16 bit function.
+        let read16: fn(&[u8]) -> u16 = match data {
+            1 => crate::common::bytes::read16::<byteorder::LittleEndian>,
+            2 => crate::common::bytes::read16::<byteorder::BigEndian>,
+
+            e => return Err( Error::UnsupportedEndianness(e) ),
+        };
+
+        // Get the read 32 bit function.
+        let read32: fn(&[u8]) -> u32 = match data {
+            1 => crate::common::bytes::read32::<byteorder::LittleEndian>,
+            2 => crate::common::bytes::read32::<byteorder::BigEndian>,
+
+            e => return Err( Error::UnsupportedEndianness(e) ),
+        };
+
+        // Get the size of an address.
+        let inc: usize = match class {
+            1 => 4,
+            2 => 8,
+
+            c => return Err( Error::UnsupportedClass(c) ),
+        };
+
+        // Get the adequate File Header parse function.
+        let fparse: fn(_, _) -> Result<FileHeader, _> = match class {
+            1 => FileHeader::parse::<_, 4>,
+            2 => FileHeader::parse::<_, 8>,
+
+            c => return Err( Error::UnsupportedClass(c) ),
+        };
+
+        // Parse the header.
+        let mut header = fparse(raw, read)?;
+
+        // Create the section list.
+        let mut sections = Vec::new();
+
+        // Get the adequate Section Header parse function.
+        let sparse: fn(_, _, _) -> Result<SectionHeader, _> = match class {
+            1 => SectionHeader::parse::<_, 4>,
+            2 => SectionHeader::parse::<_, 8>,
+
+            c => return Err( Error::UnsupportedClass(c) ),
+        };
+
+        // The first section holds the overflowed values of the file header.
+        if usize::from(header.shtoffset) != 0 {
+            if let Some(chunk) = Self::chunks(raw, Table::SectionHeaders, header.shtoffset, 1usize, header.shtesize, 16 + (6 * inc))?.next() {
+                let first = sparse(chunk, read, read32)?;
+
+                if header.shnum == 0 {
+                    header.shnum = core::convert::TryFrom::try_from( usize::from(first.filesize) ).unwrap_or(u32::MAX);
+                }
+
+                if header.shstrndx == section::XINDEX {
+                    header.shstrndx = first.link;
+                }
+
+                if header.phnum == program::XNUM {
+                    header.phnum = first.info;
+                }
+            }
+        }
+
+        for chunk in Self::chunks(raw, Table::SectionHeaders, header.shtoffset, header.shnum as usize, header.shtesize, 16 + (6 * inc))? {
+            sections.push( sparse(chunk, read, read32)? );
+        }
+
+        // Put the header in ARC.
+        let header = Arc::new( header );
+
+        // Create the program list.
+        let mut programs = Vec::new();
+
+        // Get the adequate Program Header parse function.
+        let pparse: fn(_, _, _) -> Result<ProgramHeader, _> = match class {
+            1 => ProgramHeader::parse::<_, 4>,
+            2 => ProgramHeader::parse::<_, 8>,
+
+            c => return Err( Error::UnsupportedClass(c) ),
+        };
+
+        for chunk in Self::chunks(raw, Table::ProgramHeaders, header.phtoffset, header.phnum as usize, header.phtesize, 8 + (6 * inc))? {
+            programs.push( Arc::new( pparse(chunk, read, read32)? ) );
+        }
+
+        // Load the names of all the sections if there is a section name table.
+        if header.shstrndx != 0 {
+            // Check if the .shstrtab section is present.
+            if sections.len() <= header.shstrndx as usize {
+                return Err( Error::MissingStringTable { table: Table::SectionHeaders, index: header.shstrndx as usize } );
+            }
+
+            // Get the .shstrtab section.
+            let shstrtab = &sections[ header.shstrndx as usize ];
+
+            // Get the offset and range of raw data of this section.
+            let offset = usize::from( shstrtab.offset );
+            let names = Self::contents(raw, shstrtab, Table::SectionHeaders, header.shstrndx as usize)?;
+
+            // Rename all the sections.
+            for (index, section) in sections.iter_mut().enumerate() {
+                if !section.rename( names ) {
+                    return Err( Error::BadNameIndex { table: Table::SectionHeaders, index, offset: offset.saturating_add(section.nameidx as usize) } );
+                }
+            }
+        }
+
+        // Mark the sections mapped by a segment in the file, which keep their offsets when the file is laid out again.
+        for section in sections.iter_mut().skip(1) {
+            let start = usize::from( section.offset );
+
+            let end = match section.sectiontype {
+                SectionType::Bss => start,
+                _ => start.saturating_add( usize::from(section.filesize) ),
+            };
+
+            section.mapped = programs.iter()
+                .filter(|program| usize::from(program.filesize) != 0)
+                .filter_map(|program| usize::from(program.offset).checked_add( usize::from(program.filesize) ).filter(|end| *end <= raw.len()).map(|end| (usize::from(program.offset), end)))
+                .any(|(pstart, pend)| (pstart <= start) && (end <= pend));
+        }
+
+        // Put all sections in ARC.
+        let sections: Vec<Arc<SectionHeader>> = sections.into_iter()
+            .map(Arc::new)
+            .collect();
+
+        // Get the adequate Symbol parse function.
+        let sparse: fn(_, _, _, _) -> Result<Symbol, _> = match class {
+            1 => Symbol::parse::<_, 4>,
+            2 => Symbol::parse::<_, 8>,
+
+            c => return Err( Error::UnsupportedClass(c) ),
+        };
+
+        // Load the symbol table, if the file has not been stripped.
+        let symbols = match sections.iter().position(|section| section.sectiontype == SectionType::SymbolTable) {
+            Some(index) => Self::symbols(raw, &sections, index, 8 + (2 * inc), |chunk| sparse(chunk, read, read16, read32), read32)?,
+            _ => Vec::new(),
+        };
+
+        let mut data = Self {
+            header,
+            programs,
+            sections,
+            symbols,
+            dynsyms: Vec::new(),
+            hash: None,
+            requirements: Vec::new(),
+            modified: false,
+        };
+
+        // Get the adequate GNU hash table parse function.
+        let gparse: fn(&[u8], _, _, _) -> Result<hash::GnuHash, _> = match class {
+            1 => hash::GnuHash::parse::<4>,
+            2 => hash::GnuHash::parse::<8>,
+
+            c => return Err( Error::UnsupportedClass(c) ),
+        };
+
+        // The tables of the dynamic symbols are optional: a malformed table is left empty instead of failing
+        // the whole parse, so that the headers and the sections can still be read.
+        // Decode the dynamic section once, to find the tables when the sections are stripped.
+        let entries = data.dynamic(raw).unwrap_or_default();
+
+        // Load the hash table of the dynamic symbols.
+        data.hash = data.hashtable(raw, &entries, |raw, offset| gparse(raw, offset, read, read32), read32).unwrap_or(None);
+
+        // Load the dynamic symbol table through its section or, if the sections were stripped, through the dynamic section.
+        let dynsyms = match data.sections.iter().position(|section| section.sectiontype == SectionType::DynamicSymbol) {
+            Some(index) => Self::symbols(raw, &data.sections, index, 8 + (2 * inc), |chunk| sparse(chunk, read, read16, read32), read32),
+            _ => data.tagged_symbols(raw, &entries, inc, |chunk| sparse(chunk, read, read16, read32)),
+        };
+
+        data.dynsyms = dynsyms.unwrap_or_default();
+
+        // Load the versions of the dynamic symbols.
+        if let Ok((versions, requirements)) = data.versions(raw, &entries, read16, read32) {
+            for (symbol, version) in data.dynsyms.iter_mut().zip( versions ) {
+                if version.is_some() {
+                    Arc::make_mut( symbol ).version = version;
+                }
+            }
+
+            data.requirements = requirements;
+        }
+
+        Ok( data )
+    }
+
+    /// Serializes the metadata into a file image in its original class and endianness.
+    /// The contents of the segments and sections are copied from the given raw data.
+    pub fn write(&self, raw: &[u8]) -> Vec<u8> {
+        use header::Endianness;
+
+        // Get the write function.
+        let write: fn(&mut [u8], Address) = match (self.header.entry, self.header.endianness) {
+            (Address::U32(_), Endianness::Little) => crate::common::address::write32::<byteorder::LittleEndian>,
+            (Address::U32(_), Endianness::Big   ) => crate::common::address::write32::<byteorder::BigEndian>,
+
+            (Address::U64(_), Endianness::Little) => crate::common::address::write64::<byteorder::LittleEndian>,
+            (Address::U64(_), Endianness::Big   ) => crate::common::address::write64::<byteorder::BigEndian>,
+        };
+
+        // Get the write 16 bit function.
+        let write16: fn(&mut [u8], u16) = match self.header.endianness {
+            Endianness::Little => crate::common::bytes::write16::<byteorder::LittleEndian>,
+            Endianness::Big    => crate::common::bytes::write16::<byteorder::BigEndian>,
+        };
+
+        // Get the write 32 bit function.
+        let write32: fn(&mut [u8], u32) = match self.header.endianness {
+            Endianness::Little => crate::common::bytes::write32::<byteorder::LittleEndian>,
+            Endianness::Big    => crate::common::bytes::write32::<byteorder::BigEndian>,
+        };
+
+        // Get the adequate File Header write function.
+        let fwrite: fn(&FileHeader, &mut [u8], _) = match self.header.entry {
+            Address::U32(_) => FileHeader::write::<4>,
+            Address::U64(_) => FileHeader::write::<8>,
+        };
+
+        // Get the adequate Program Header write function.
+        let pwrite: fn(&ProgramHeader, &mut [u8], _, _) = match self.header.entry {
+            Address::U32(_) => ProgramHeader::write::<4>,
+            Address::U64(_) => ProgramHeader::write::<8>,
+        };
+
+        // Get the adequate Section Header write function.
+        let swrite: fn(&SectionHeader, &mut [u8], _, _) = match self.header.entry {
+            Address::U32(_) => SectionHeader::write::<4>,
+            Address::U64(_) => SectionHeader::write::<8>,
+        };
+
+        // Get the adequate Symbol write function.
+        let ywrite: fn(&Symbol, &mut [u8], _, _, _) = match self.header.entry {
+            Address::U32(_) => Symbol::write::<4>,
+            Address::U64(_) => Symbol::write::<8>,
+        };
+
+        // Lay out the file again if the sections or symbols were modified.
+        let (header, sections, symbols) = match self.modified {
+            true => self.layout(raw, write32),
+            _ => (
+                (*self.header).clone(),
+                self.sections.iter().map(|section| (**section).clone()).collect::<Vec<_>>(),
+                self.symbols.iter().map(|symbol| (**symbol).clone()).collect::<Vec<_>>(),
+            ),
+        };
+
+        // Get the size of an address.
+        let inc = header.entry.bytes();
+
+        // Get the tables dimensions.
+        let phtoffset = usize::from( header.phtoffset );
+        let phtesize = usize::from( header.phtesize );
+        let shtoffset = usize::from( header.shtoffset );
+        let shtesize = usize::from( header.shtesize );
+
+        // Collect the ranges of raw data of the segments, which are copied in place.
+        let ranges = self.programs.iter()
+            .map(|program| (usize::from(program.offset), usize::from(program.filesize)))
+            .filter_map(|(offset, size)| offset.checked_add(size).filter(|end| *end <= raw.len()).map(|end| offset..end))
+            .collect::<Vec<_>>();
+
+        // Get the contents of the sections, from the original file if they were not replaced.
+        let contents = sections.iter()
+            .enumerate()
+            .map(|(i, section)| match &section.data {
+                _ if section.sectiontype == SectionType::Bss => None,
+                Some(data) => Some( &data[..] ),
+                None => self.sections.get(i).and_then(|original| Self::contents(raw, original, Table::SectionHeaders, 0).ok()),
+            })
+            .collect::<Vec<_>>();
+
+        // Calculate the size of the file image.
+        let size = ranges.iter()
+            .map(|range| range.end)
+            .chain( sections.iter().zip( contents.iter() ).filter_map(|(section, content)| content.map(|content| usize::from(section.offset) + content.len())) )
+            .chain( core::iter::once( 40 + (3 * inc) ) )
+            .chain( Some( phtoffset + (self.programs.len() * phtesize) ).filter(|_| !self.programs.is_empty()) )
+            .chain( Some( shtoffset + (sections.len() * shtesize) ).filter(|_| !sections.is_empty()) )
+            .max()
+            .unwrap_or(0);
+
+        // Create the file image, starting from the original file if it was not laid out again
+        // to keep the bytes outside of the sections and segments.
+        let mut image = match self.modified {
+            true => vec![0u8; size],
+            _ => raw.to_vec(),
+        };
+
+        image.resize( core::cmp::max(image.len(), size), 0 );
+
+        // Copy the contents of the segments.
+        for range in ranges {
+            image[range.clone()].copy_from_slice( &raw[range] );
+        }
+
+        // Copy the contents of the sections.
+        for (section, content) in sections.iter().zip( contents.iter() ) {
+            if let Some(content) = content {
+                let offset = usize::from( section.offset );
+                image[offset..offset+content.len()].copy_from_slice( content );
+            }
+        }
+
+        // Write the file header.
+        fwrite(&header, &mut image, write);
+
+        // Write the Program Header Table.
+        for (i, program) in self.programs.iter().enumerate() {
+            let start = phtoffset + (i * phtesize);
+            pwrite(program, &mut image[start..start+phtesize], write, write32);
+        }
+
+        // Write the Section Header Table.
+        for (i, section) in sections.iter().enumerate() {
+            let start = shtoffset + (i * shtesize);
+            swrite(section, &mut image[start..start+shtesize], write, write32);
+        }
+
+        // Write the symbol table.
+        if let Some(symtab) = sections.iter().find(|section| section.sectiontype == SectionType::SymbolTable) {
+            let offset = usize::from( symtab.offset );
+            let entrysize = usize::from( symtab.entrysize );
+
+            // Check the entries can hold a symbol of this class.
+            if entrysize >= 8 + (2 * inc) {
+                for (i, symbol) in symbols.iter().enumerate() {
+                    let start = offset + (i * entrysize);
+
+                    if let Some(entry) = image.get_mut(start..start+entrysize) {
+                        ywrite(symbol, entry, write, write16, write32);
+                    }
+                }
+            }
+        }
+
+        image
+    }
+
+    /// Internal function to lay out the file again after its sections or symbols were modified.
+    /// Sections mapped by a segment keep their offsets, the rest are placed in order after all the mapped contents.
+    /// Local symbols are placed first and the references to the reordered symbols are remapped.
+    /// The string tables and the extended section indices are regenerated, creating the extended section indices if a symbol needs them.
+    fn layout(&self, raw: &[u8], write32: fn(&mut [u8], u32)) -> (FileHeader, Vec<SectionHeader>, Vec<Symbol>) {
+        use symbol::Bind;
+
+        // Copy the headers, which are modified in place.
+        let mut header = (*self.header).clone();
+        let mut sections = self.sections.iter().map(|section| (**section).clone()).collect::<Vec<_>>();
+
+        // Copy the symbols, placing the local symbols first.
+        let mut symbols = self.symbols.iter()
+            .filter(|symbol| symbol.bind == Bind::Local)
+            .chain( self.symbols.iter().filter(|symbol| symbol.bind != Bind::Local) )
+            .map(|symbol| (**symbol).clone())
+            .collect::<Vec<_>>();
+
+        // Get the size of an address.
+        let inc = header.entry.bytes();
+
+        // Create the extended section indices of the symbol table if a symbol needs them.
+        let symtab = sections.iter().position(|section| section.sectiontype == SectionType::SymbolTable);
+
+        if let Some(index) = symtab {
+            let exists = sections.iter().any(|section| (section.sectiontype == SectionType::SymbolTableX) && (section.link as usize == index));
+
+            if !exists && symbols.iter().any(|symbol| symbol.extended) {
+                let mut shndx = SectionHeader::new(".symtab_shndx", SectionType::SymbolTableX, header.entry.with(0));
+                shndx.link = index as u32;
+                shndx.alignment = shndx.alignment.with(4);
+                shndx.entrysize = shndx.entrysize.with(4);
+
+                sections.push( shndx );
+            }
+        }
+
+        // Update the number of entries of the tables.
+        header.phnum = self.programs.len() as u32;
+        header.shnum = sections.len() as u32;
+
+        // Get the indices of the string tables.
+        let shstrndx = Some( header.shstrndx as usize ).filter(|index| (*index != 0) && (*index < sections.len()));
+        let strndx = symtab.map(|index| sections[index].link as usize).filter(|index| (*index != 0) && (*index < sections.len()));
+
+        // Regenerate the section name table.
+        let mut shnames = (vec![0u8], HashMap::new());
+
+        if shstrndx.is_some() {
+            for section in sections.iter_mut() {
+                section.nameidx = Self::intern(&mut shnames, section.raw_name());
+            }
+        }
+
+        // Regenerate the symbol string table, which may be shared with the section names.
+        let mut synames = (vec![0u8], HashMap::new());
+
+        if strndx.is_some() {
+            let names = match strndx == shstrndx {
+                true => &mut shnames,
+                _ => &mut synames,
+            };
+
+            for symbol in symbols.iter_mut() {
+                symbol.nameidx = Self::intern(names, symbol.raw_name());
+            }
+        }
+
+        for (index, (names, _)) in [(shstrndx, shnames), (strndx, synames)] {
+            if let Some(section) = index.filter(|_| names.len() > 1).map(|index| &mut sections[index]) {
+                section.filesize = section.filesize.with( names.len() as u64 );
+                section.data = Some( names.into() );
+            }
+        }
+
+        if let Some(index) = symtab {
+            // Resize the symbol table and point to the first non local symbol.
+            let symtab = &mut sections[index];
+            let size = symbols.len() * usize::from( symtab.entrysize );
+
+            symtab.filesize = symtab.filesize.with( size as u64 );
+            symtab.info = symbols.iter().filter(|symbol| symbol.bind == Bind::Local).count() as u32;
+            symtab.data = Some( vec![0u8; size].into() );
+
+            // Remap the references to the symbols if they were reordered or removed.
+            if symbols.iter().enumerate().any(|(i, symbol)| symbol.original != Some(i as u32)) {
+                let mut map = HashMap::new();
+
+                for (i, symbol) in symbols.iter().enumerate() {
+                    if let Some(original) = symbol.original {
+                        map.insert(original, i as u32);
+                    }
+                }
+
+                for (section, original) in sections.iter_mut().zip( self.sections.iter() ).filter(|(section, _)| section.link as usize == index) {
+                    match section.sectiontype {
+                        // Remap the symbols of the relocations.
+                        SectionType::Relocation | SectionType::RelocationA => {
+                            let mut content = match &section.data {
+                                Some(data) => data.to_vec(),
+                                _ => Self::contents(raw, original, Table::SectionHeaders, 0).map(|data| data.to_vec()).unwrap_or_default(),
+                            };
+
+                            self.remap_relocations(&mut content, usize::from(section.entrysize), |symbol| map.get(&symbol).cloned().unwrap_or(0));
+                            section.data = Some( content.into() );
+                        },
+
+                        // Remap the signature of the section groups.
+                        SectionType::Group => section.info = map.get(&section.info).cloned().unwrap_or(0),
+
+                        _ => (),
+                    }
+                }
+            }
+
+            // Regenerate the extended section indices of the symbol table.
+            let shndx = sections.iter_mut()
+                .find(|section| (section.sectiontype == SectionType::SymbolTableX) && (section.link as usize == index));
+
+            if let Some(shndx) = shndx {
+                let mut indices = vec![0u8; symbols.len() * 4];
+
+                for (symbol, entry) in symbols.iter().zip( indices.chunks_exact_mut(4) ) {
+                    if symbol.extended {
+                        write32( entry, symbol.shidx );
+                    }
+                }
+
+                shndx.filesize = shndx.filesize.with( indices.len() as u64 );
+                shndx.data = Some( indices.into() );
+            }
+        }
+
+        // The first section holds the overflowed values of the file header.
+        if let Some(first) = sections.first_mut() {
+            first.filesize = first.filesize.with( match header.shnum >= section::LORESERVE { true => header.shnum as u64, _ => 0 } );
+            first.link = match header.shstrndx >= section::LORESERVE { true => header.shstrndx, _ => 0 };
+            first.info = match header.phnum >= program::XNUM { true => header.phnum, _ => 0 };
+        }
+
+        // Get the end of the Program Header Table.
+        let phtend = match self.programs.len() {
+            0 => 0,
+            n => usize::from( header.phtoffset ).saturating_add( n * usize::from(header.phtesize) ),
+        };
+
+        // Start placing the sections after the file header, the Program Header Table and the mapped contents.
+        let mut cursor = self.programs.iter()
+            .filter_map(|program| usize::from(program.offset).checked_add( usize::from(program.filesize) ).filter(|end| *end <= raw.len()))
+            .chain( sections.iter().filter(|section| section.mapped && (section.sectiontype != SectionType::Bss)).map(|section| usize::from(section.offset).saturating_add( usize::from(section.filesize) )) )
+            .chain( core::iter::once( 40 + (3 * inc) ) )
+            .chain( core::iter::once( phtend ) )
+            .max()
+            .unwrap_or(0);
+
+        // Place the sections that are not mapped in order, skipping the null section.
+        for (i, section) in sections.iter_mut().enumerate().skip(1).filter(|(_, section)| !section.mapped) {
+            // Align the offset of the section, ignoring invalid alignments.
+            let alignment = usize::from( section.alignment );
+
+            if alignment.is_power_of_two() {
+                cursor = cursor.checked_next_multiple_of( alignment ).unwrap_or(cursor);
+            }
+
+            section.offset = section.offset.with( cursor as u64 );
+
+            // Only the contents present in the file take space.
+            let size = match (&section.data, section.sectiontype) {
+                (_, SectionType::Bss) => 0,
+                (Some(data), _) => data.len(),
+                (None, _) => self.sections.get(i).and_then(|original| Self::contents(raw, original, Table::SectionHeaders, 0).ok()).map(|data| data.len()).unwrap_or(0),
+            };
+
+            cursor = cursor.saturating_add( size );
+        }
+
+        // Place the Section Header Table after the sections.
+        header.shtoffset = match sections.len() {
+            0 => header.shtoffset.with(0),
+            _ => header.shtoffset.with( cursor.next_multiple_of(inc) as u64 ),
+        };
+
+        (header, sections, symbols)
+    }
+
+    /// Returns a mutable reference to the section at the given index.
+    /// The file is laid out again when it is written.
+    pub(super) fn section_mut(&mut self, index: usize) -> Option<&mut SectionHeader> {
+        let section = self.sections.get_mut(index)?;
+        self.modified = true;
+
+        Some( Arc::make_mut(section) )
+    }
+
+    /// Adds a new section with the given name, type and contents at the end of the section list.
+    /// Creates the null section and the section name table if the object has none.
+    pub(super) fn add_section(&mut self, name: &str, sectiontype: SectionType, data: Vec<u8>) -> &mut SectionHeader {
+        // Get the width of the addresses of the object.
+        let zero = self.header.entry.with(0);
+
+        let header = Arc::make_mut( &mut self.header );
+
+        // The first section is always the null section.
+        if self.sections.is_empty() {
+            header.shtesize = (16 + (6 * zero.bytes())) as u16;
+            self.sections.push( Arc::new( SectionHeader::new("", SectionType::Null, zero) ) );
+        }
+
+        // Create the section name table.
+        if header.shstrndx == 0 {
+            header.shstrndx = self.sections.len() as u32;
+            self.sections.push( Arc::new( SectionHeader::new(".shstrtab", SectionType::StringTable, zero) ) );
+        }
+
+        // Create the new section.
+        let mut section = SectionHeader::new(name, sectiontype, zero);
+        section.filesize = zero.with( data.len() as u64 );
+
+        if sectiontype != SectionType::Bss {
+            section.data = Some( data.into() );
+        }
+
+        self.sections.push( Arc::new( section ) );
+
+        header.shnum = self.sections.len() as u32;
+        self.modified = true;
+
+        let index = self.sections.len() - 1;
+        Arc::make_mut( &mut self.sections[index] )
+    }
+
+    /// Removes the section at the given index and remaps all the section indices after it.
+    /// References to the removed section are reset to the undefined section.
+    /// Removing the symbol table removes all the symbols.
+    pub(super) fn remove_section(&mut self, index: usize) -> Option<Arc<SectionHeader>> {
+        use core::cmp::Ordering;
+
+        // The null section cannot be removed.
+        if (index == 0) || (index >= self.sections.len()) {
+            return None;
+        }
+
+        // Check if the section holds the symbols.
+        let symtab = self.sections.iter().position(|section| section.sectiontype == SectionType::SymbolTable) == Some(index);
+
+        let removed = self.sections.remove(index);
+
+        // Remaps a section index after the removal.
+        let remap = |i: u32| match (i as usize).cmp(&index) {
+            Ordering::Less => i,
+            Ordering::Equal => 0,
+            Ordering::Greater => i - 1,
+        };
+
+        // Remap the associated sections.
+        for section in self.sections.iter_mut() {
+            let link = remap( section.link );
+
+            let info = match section.infolink() {
+                true => remap( section.info ),
+                _ => section.info,
+            };
+
+            if (link != section.link) || (info != section.info) {
+                let section = Arc::make_mut( section );
+                section.link = link;
+                section.info = info;
+            }
+        }
+
+        // Remap the section name table.
+        let header = Arc::make_mut( &mut self.header );
+        header.shnum = self.sections.len() as u32;
+        header.shstrndx = remap( header.shstrndx );
+
+        // Remap the sections of the symbols, leaving the reserved indices untouched.
+        if symtab {
+            self.symbols.clear();
+        } else {
+            for symbol in self.symbols.iter_mut() {
+                if (symbol.shidx != 0) && (symbol.extended || (symbol.shidx < section::LORESERVE)) {
+                    let shidx = remap( symbol.shidx );
+
+                    if shidx != symbol.shidx {
+                        let symbol = Arc::make_mut( symbol );
+                        symbol.shidx = shidx;
+                        symbol.extended = shidx >= section::LORESERVE;
+                    }
+                }
+            }
+        }
+
+        self.modified = true;
+
+        Some( removed )
+    }
+
+    /// Replaces the contents of the section at the given index.
+    /// Returns `false` if the section does not exist or is mapped by a segment and the contents do not fit in its place.
+    pub(super) fn set_content(&mut self, index: usize, data: Vec<u8>) -> bool {
+        let section = match self.sections.get_mut(index) {
+            Some(section) if index != 0 => section,
+            _ => return false,
+        };
+
+        // Sections without contents in the file only change their size.
+        let bss = section.sectiontype == SectionType::Bss;
+
+        // Mapped sections cannot grow past their place in the file.
+        if section.mapped && !bss && (data.len() > usize::from(section.filesize)) {
+            return false;
+        }
+
+        let section = Arc::make_mut( section );
+        section.filesize = section.filesize.with( data.len() as u64 );
+        section.data = match bss {
+            true => None,
+            _ => Some( data.into() ),
+        };
+
+        self.modified = true;
+
+        true
+    }
+
+    /// Resizes the section at the given index, truncating its contents or padding them with zeros.
+    /// Returns `false` if the section does not exist or is mapped by a segment and does not fit in its place.
+    pub(super) fn resize(&mut self, raw: &[u8], index: usize, size: usize) -> bool {
+        let section = match self.sections.get(index) {
+            Some(section) => section,
+            _ => return false,
+        };
+
+        // Get the current contents of the section.
+        let mut data = match (&section.data, section.sectiontype) {
+            (_, SectionType::Bss) => Vec::new(),
+            (Some(data), _) => data.to_vec(),
+            (None, _) => Self::contents(raw, section, Table::SectionHeaders, index).map(|data| data.to_vec()).unwrap_or_default(),
+        };
+
+        data.resize(size, 0);
+
+        self.set_content(index, data)
+    }
+
+    /// Returns a mutable reference to the symbol at the given index.
+    /// The symbol and string tables are regenerated when the object is written.
+    pub(super) fn symbol_mut(&mut self, index: usize) -> Option<&mut Symbol> {
+        let symbol = self.symbols.get_mut(index)?;
+        self.modified = true;
+
+        Some( Arc::make_mut(symbol) )
+    }
+
+    /// Adds a new symbol at the end of the symbol list and returns its index.
+    /// Creates the symbol and string tables if the object has none.
+    pub(super) fn add_symbol(&mut self, mut symbol: Symbol) -> usize {
+        use symbol::{ Bind, SymbolType };
+
+        // Get the width of the addresses of the object.
+        let zero = self.header.entry.with(0);
+
+        // Create the symbol table and its string table.
+        if !self.sections.iter().any(|section| section.sectiontype == SectionType::SymbolTable) {
+            let symtab = self.add_section(".symtab", SectionType::SymbolTable, Vec::new());
+            symtab.entrysize = zero.with( (8 + (2 * zero.bytes())) as u64 );
+            symtab.alignment = zero.with( zero.bytes() as u64 );
+
+            self.add_section(".strtab", SectionType::StringTable, vec![0u8]);
+
+            let strndx = self.sections.len() - 1;
+            Arc::make_mut( &mut self.sections[strndx - 1] ).link = strndx as u32;
+
+            // The first symbol is always the null symbol.
+            self.symbols.clear();
+            self.symbols.push( Arc::new( Symbol::new("", Bind::Local, SymbolType::None) ) );
+        }
+
+        // Match the width of the addresses of the object.
+        symbol.value = zero.with( u64::from(symbol.value) );
+        symbol.size = zero.with( u64::from(symbol.size) );
+        symbol.original = None;
+
+        self.symbols.push( Arc::new( symbol ) );
+        self.modified = true;
+
+        self.symbols.len() - 1
+    }
+
+    /// Removes the symbol at the given index and returns it.
+    /// Symbols referenced by relocations or section groups cannot be removed.
+    pub(super) fn remove_symbol(&mut self, raw: &[u8], index: usize) -> Option<Arc<Symbol>> {
+        // The null symbol cannot be removed.
+        if (index == 0) || (index >= self.symbols.len()) {
+            return None;
+        }
+
+        // Check the symbol is not referenced in the file.
+        let symtab = self.sections.iter().position(|section| section.sectiontype == SectionType::SymbolTable);
+
+        if let (Some(original), Some(symtab)) = (self.symbols[index].original, symtab) {
+            for (i, section) in self.sections.iter().enumerate().filter(|(_, section)| section.link as usize == symtab) {
+                let referenced = match section.sectiontype {
+                    SectionType::Relocation | SectionType::RelocationA => {
+                        let mut content = match &section.data {
+                            Some(data) => data.to_vec(),
+                            _ => Self::contents(raw, section, Table::SectionHeaders, i).map(|data| data.to_vec()).unwrap_or_default(),
+                        };
+
+                        let mut referenced = false;
+                        self.remap_relocations(&mut content, usize::from(section.entrysize), |symbol| { referenced |= symbol == original; symbol });
+
+                        referenced
+                    },
+
+                    SectionType::Group => section.info == original,
+
+                    _ => false,
+                };
+
+                if referenced {
+                    return None;
+                }
+            }
+        }
+
+        self.modified = true;
+
+        Some( self.symbols.remove(index) )
+    }
+
+    /// Parses the relocations of all the relocation sections.
+    /// The symbols are resolved when the relocations reference the symbol table of the object.
+    pub(super) fn relocations(&self, raw: &[u8]) -> Result<Vec<RelocationTable>, Error> {
+        use header::Endianness;
+
+        // Get the read function.
+        let read: fn(&[u8]) -> Address = match (self.header.entry, self.header.endianness) {
+            (Address::U32(_), Endianness::Little) => crate::common::address::read32::<byteorder::LittleEndian>,
+            (Address::U32(_), Endianness::Big   ) => crate::common::address::read32::<byteorder::BigEndian>,
+
+            (Address::U64(_), Endianness::Little) => crate::common::address::read64::<byteorder::LittleEndian>,
+            (Address::U64(_), Endianness::Big   ) => crate::common::address::read64::<byteorder::BigEndian>,
+        };
+
+        // Get the adequate Relocation parse function.
+        let rparse: fn(_, _, _, _) -> Result<Relocation, _> = match self.header.entry {
+            Address::U32(_) => Relocation::parse::<_, 4>,
+            Address::U64(_) => Relocation::parse::<_, 8>,
+        };
+
+        // Get the size of an address.
+        let inc = self.header.entry.bytes();
+
+        // Get the symbol table of the object.
+        let symtab = self.sections.iter().position(|section| section.sectiontype == SectionType::SymbolTable);
+
+        // Edited symbols are found by their index in the parsed symbol table.
+        let originals = match self.modified {
+            true => Some( self.symbols.iter().filter_map(|symbol| symbol.original.map(|original| (original, symbol))).collect::<HashMap<_, _>>() ),
+            _ => None,
+        };
+
+        // Create the list of relocation tables.
+        let mut tables = Vec::new();
+
+        for (index, section) in self.sections.iter().enumerate() {
+            // Check if the section has explicit addends.
+            let rela = match section.sectiontype {
+                SectionType::Relocation => false,
+                SectionType::RelocationA => true,
+                _ => continue,
+            };
+
+            // Check the size of the entries.
+            let expected = match rela {
+                true => 3 * inc,
+                _ => 2 * inc,
+            };
+
+            let entrysize = usize::from( section.entrysize );
+
+            if entrysize < expected {
+                return Err( Error::BadHeaderSize { table: Table::Relocations, expected, found: entrysize } );
+            }
+
+            // Get the contents of the section.
+            let content = self.section_content(raw, index)?;
+
+            // Parse the relocations, resolving the symbols of the symbol table of the object.
+            let mut relocations = Vec::new();
+
+            for chunk in content.chunks_exact(entrysize) {
+                let mut relocation = rparse(chunk, read, rela, self.header.architecture)?;
+
+                if (relocation.symidx != 0) && (Some(section.link as usize) == symtab) {
+                    relocation.symbol = match &originals {
+                        Some(originals) => originals.get(&relocation.symidx).map(|symbol| Arc::clone(symbol)),
+                        _ => self.symbols.get(relocation.symidx as usize).cloned(),
+                    };
+                }
+
+                relocations.push( relocation );
+            }
+
+            // Get the linked sections.
+            let linked = |index: u32| match index {
+                0 => None,
+                _ => self.sections.get(index as usize).cloned(),
+            };
+
+            tables.push( RelocationTable {
+                section: Arc::clone(section),
+                target: linked( section.info ),
+                symtab: linked( section.link ),
+                relocations,
+            });
+        }
+
+        Ok( tables )
+    }
 
-    /// List of section headers.
-    pub(super) sections: Vec<Arc<SectionHeader>>,
+    /// Returns an iterator over the notes of the object.
+    /// The notes are found through the note sections or, if there are none, through the note segments.
+    pub(super) fn notes<'a>(&'a self, raw: &'a [u8]) -> NoteIterator<'a> {
+        use header::Endianness;
 
-    /// List of symbols.
-    pub(super) symbols: Vec<Arc<Symbol>>,
-}
+        // Get the read 32 bit function.
+        let read32: fn(&[u8]) -> u32 = match self.header.endianness {
+            Endianness::Little => crate::common::bytes::read32::<byteorder::LittleEndian>,
+            Endianness::Big    => crate::common::bytes::read32::<byteorder::BigEndian>,
+        };
+
+        // Notes are aligned to 8 bytes only if their container says so.
+        let align = |alignment: Address| match u64::from(alignment) {
+            8 => 8,
+            _ => 4,
+        };
+
+        // Collect the regions with notes.
+        let mut regions = self.sections.iter()
+            .enumerate()
+            .filter(|(_, section)| section.sectiontype == SectionType::Notes)
+            .map(|(index, section)| note::Region { data: self.section_content(raw, index), offset: usize::from(section.offset), align: align(section.alignment) })
+            .collect::<Vec<_>>();
+
+        if regions.is_empty() {
+            regions = self.programs.iter()
+                .enumerate()
+                .filter(|(_, program)| program.programtype == ProgramType::Note)
+                .map(|(index, program)| {
+                    let offset = usize::from( program.offset );
+
+                    let data = match offset.checked_add( usize::from(program.filesize) ).and_then(|end| raw.get(offset..end)) {
+                        Some(data) => Ok( data ),
+                        _ => Err( Error::OutOfBounds { table: Table::ProgramHeaders, index, offset } ),
+                    };
+
+                    note::Region { data, offset, align: align(program.alignment) }
+                })
+                .collect();
+        }
+
+        // The iterator takes the regions from the end.
+        regions.reverse();
+
+        NoteIterator { regions, position: 0, index: 0, architecture: self.header.architecture, read32 }
+    }
+
+    /// Parses the entries of the dynamic section, up to the terminating entry.
+    /// The dynamic section is found through its segment, or through its section if there are no segments.
+    pub(super) fn dynamic(&self, raw: &[u8]) -> Result<Vec<DynamicEntry>, Error> {
+        use header::Endianness;
 
-impl ELFData {
-    pub fn parse(raw: &[u8]) -> Result<Self, ()> {
         // Get the read function.
-        let read: fn(&[u8]) -> crate::common::address::Address = match (raw[0x04], raw[0x05]) {
-            (1, 1) => crate::common::address::read32::<byteorder::LittleEndian>,
-            (1, 2) => crate::common::address::read32::<byteorder::BigEndian>,
+        let read: fn(&[u8]) -> Address = match (self.header.entry, self.header.endianness) {
+            (Address::U32(_), Endianness::Little) => crate::common::address::read32::<byteorder::LittleEndian>,
+            (Address::U32(_), Endianness::Big   ) => crate::common::address::read32::<byteorder::BigEndian>,
 
-            (2, 1) => crate::common::address::read64::<byteorder::LittleEndian>,
-            (2, 2) => crate::common::address::read64::<byteorder::BigEndian>,
+            (Address::U64(_), Endianness::Little) => crate::common::address::read64::<byteorder::LittleEndian>,
+            (Address::U64(_), Endianness::Big   ) => crate::common::address::read64::<byteorder::BigEndian>,
+        };
 
-            _ => return Err( () ),
+        // Get the size of an address.
+        let inc = self.header.entry.bytes();
+
+        // Get the contents of the dynamic section and the index of its linked string table section.
+        let (content, link) = match self.programs.iter().position(|program| program.programtype == ProgramType::Dynamic) {
+            Some(index) => {
+                let program = &self.programs[index];
+                let offset = usize::from( program.offset );
+
+                match offset.checked_add( usize::from(program.filesize) ).and_then(|end| raw.get(offset..end)) {
+                    Some(content) => (content, None),
+                    _ => return Err( Error::OutOfBounds { table: Table::ProgramHeaders, index, offset } ),
+                }
+            },
+
+            _ => match self.sections.iter().position(|section| section.sectiontype == SectionType::Dynamic) {
+                Some(index) => (self.section_content(raw, index)?, Some( self.sections[index].link as usize )),
+                _ => return Ok( Vec::new() ),
+            },
         };
 
-        // Get the read 16 bit function.
-        let read16: fn(&[u8]) -> u16 = match raw[0x05] {
-            1 => crate::common::bytes::read16::<byteorder::LittleEndian>,
-            2 => crate::common::bytes::read16::<byteorder::BigEndian>,
+        // Read the raw tags and values.
+        let entries = content.chunks_exact(2 * inc)
+            .map(|chunk| (u64::from( read(&chunk[..inc]) ), u64::from( read(&chunk[inc..]) )))
+            .take_while(|(tag, _)| *tag != 0)
+            .collect::<Vec<_>>();
 
-            _ => return Err( () ),
+        // Get the value of the first entry with the given tag.
+        let value = |tag: u64| entries.iter().find(|entry| entry.0 == tag).map(|entry| entry.1);
+
+        // Get the dynamic string table through its address, or through the section linked to the dynamic section.
+        let strings = match (value(5).and_then(|address| self.file_offset(address)), value(10)) {
+            (Some(offset), Some(size)) => offset.checked_add(size as usize).and_then(|end| raw.get(offset..end)).map(|strings| (offset, strings)),
+            _ => None,
         };
 
-        // Get the read 32 bit function.
-        let read32: fn(&[u8]) -> u32 = match raw[0x05] {
-            1 => crate::common::bytes::read32::<byteorder::LittleEndian>,
-            2 => crate::common::bytes::read32::<byteorder::BigEndian>,
+        let strings = match (strings, link) {
+            (Some(strings), _) => Some( strings ),
+            (_, Some(link)) if link != 0 => match self.sections.get(link) {
+                Some(section) => Some( (usize::from( section.offset ), self.section_content(raw, link)?) ),
+                _ => None,
+            },
+            _ => None,
+        };
 
-            _ => return Err( () ),
+        let (offset, strings) = match strings {
+            Some(strings) => strings,
+            _ if entries.iter().any(|(tag, _)| DynamicEntry::is_string(*tag)) => return Err( Error::MissingStringTable { table: Table::Dynamic, index: link.unwrap_or(0) } ),
+            _ => (0, &[][..]),
         };
 
-        // Get the adequate File Header parse function.
-        let fparse: fn(_, _) -> Result<FileHeader, _> = match raw[0x04] {
-            1 => FileHeader::parse::<_, 4>,
-            2 => FileHeader::parse::<_, 8>,
+        // Decode the entries.
+        entries.iter()
+            .enumerate()
+            .map(|(index, (tag, value))| match DynamicEntry::new(*tag, *value, strings) {
+                Some(entry) => Ok( entry ),
+                _ => Err( Error::BadNameIndex { table: Table::Dynamic, index, offset: offset.saturating_add(*value as usize) } ),
+            })
+            .collect()
+    }
+
+    /// Translates a virtual address into an offset in the file image.
+    /// The address is mapped through the loadable segments, or through the allocated sections if there are none.
+    pub(super) fn file_offset(&self, address: u64) -> Option<usize> {
+        // Map the address through the given range of addresses.
+        let map = |vaddr: Address, offset: Address, size: Address| {
+            let delta = address.checked_sub( u64::from(vaddr) )?;
 
-            _ => return Err(()),
+            match delta < u64::from(size) {
+                true => usize::from(offset).checked_add(delta as usize),
+                _ => None,
+            }
         };
 
-        // Parse the header.
-        let header = Arc::new( fparse(raw, read)? );
+        match self.programs.iter().any(|program| program.programtype == ProgramType::Load) {
+            true => self.programs.iter()
+                .filter(|program| program.programtype == ProgramType::Load)
+                .find_map(|program| map(program.vaddr, program.offset, program.filesize)),
 
-        // Create the program list.
-        let mut programs = Vec::new();
+            _ => self.sections.iter()
+                .filter(|section| (section.sectiontype != SectionType::Bss) && ((u64::from( section.flags ) & section::ALLOC) != 0))
+                .find_map(|section| map(section.vaddr, section.offset, section.filesize)),
+        }
+    }
 
-        // Get the adequate Program Header parse function.
-        let pparse: fn(_, _, _) -> Result<ProgramHeader, _> = match raw[0x04] {
-            1 => ProgramHeader::parse::<_, 4>,
-            2 => ProgramHeader::parse::<_, 8>,
+    /// Returns the memory region that contains the given address in the given address space.
+    /// The region is found through the loadable segments, or through the allocated sections if there are none,
+    /// and is returned as its range of addresses and its contents in the file. The contents may be shorter
+    /// than the region, in which case the rest of the region is filled with zeros.
+    pub(super) fn region<'a>(&'a self, raw: &'a [u8], address: u64, space: AddressSpace) -> Result<(Range<u64>, &'a [u8]), Error> {
+        // Checks if the address is in the given range of addresses.
+        let contains = |start: Address, size: Address| {
+            let start = u64::from(start);
+            let end = start.checked_add( u64::from(size) )?;
 
-            _ => return Err(()),
+            Some( start..end ).filter(|range| range.contains(&address))
         };
 
-        for chunk in Self::chunks(raw, header.phtoffset, header.phnum, header.phtesize) {
-            programs.push( Arc::new( pparse(chunk, read, read32)? ) );
+        match self.programs.iter().any(|program| program.programtype == ProgramType::Load) {
+            true => {
+                let found = self.programs.iter()
+                    .enumerate()
+                    .filter(|(_, program)| program.programtype == ProgramType::Load)
+                    .find_map(|(index, program)| {
+                        let start = match space {
+                            AddressSpace::Virtual => program.vaddr,
+                            AddressSpace::Physical => program.paddr,
+                        };
+
+                        contains(start, program.memsize).map(|range| (index, program, range))
+                    });
+
+                let (index, program, range) = match found {
+                    Some(found) => found,
+                    _ => return Err( Error::Unmapped(address) ),
+                };
+
+                // Get the contents of the segment in the file.
+                let offset = usize::from( program.offset );
+                let size = core::cmp::min( u64::from(program.filesize), range.end - range.start ) as usize;
+
+                match offset.checked_add(size).and_then(|end| raw.get(offset..end)) {
+                    Some(data) => Ok( (range, data) ),
+                    _ => Err( Error::OutOfBounds { table: Table::ProgramHeaders, index, offset } ),
+                }
+            },
+
+            _ => {
+                let found = self.sections.iter()
+                    .enumerate()
+                    .filter(|(_, section)| (u64::from( section.flags ) & section::ALLOC) != 0)
+                    .find_map(|(index, section)| contains(section.vaddr, section.filesize).map(|range| (index, section, range)));
+
+                match found {
+                    Some((_, section, range)) if section.sectiontype == SectionType::Bss => Ok( (range, &[][..]) ),
+                    Some((index, _, range)) => Ok( (range, self.section_content(raw, index)?) ),
+                    _ => Err( Error::Unmapped(address) ),
+                }
+            },
         }
-    
-        // Create the section list.
-        let mut sections = Vec::new();
+    }
 
-        // Get the adequate Section Header parse function.
-        let sparse: fn(_, _, _) -> Result<SectionHeader, _> = match raw[0x04] {
-            1 => SectionHeader::parse::<_, 4>,
-            2 => SectionHeader::parse::<_, 8>,
+    /// Reads the given number of bytes at the given address of the loaded image.
+    /// The read may span several regions, and the parts of the regions that are not in the file read as zeros.
+    pub(super) fn read<'a>(&'a self, raw: &'a [u8], address: u64, len: usize, space: AddressSpace) -> Result<Cow<'a, [u8]>, Error> {
+        let end = address.checked_add(len as u64).ok_or( Error::Unmapped(address) )?;
 
-            _ => return Err(()),
-        };
+        let mut output: Vec<u8> = Vec::new();
+        let mut current = address;
 
-        for chunk in Self::chunks(raw, header.shtoffset, header.shnum, header.shtesize) {
-            sections.push( sparse(chunk, read, read32)? );
+        while current < end {
+            let (Range { start, end: stop }, data) = self.region(raw, current, space)?;
+
+            // Get the part of the region that is read.
+            let first = (current - start) as usize;
+            let last = (core::cmp::min(stop, end) - start) as usize;
+
+            // Borrow the contents if the whole read is in the file.
+            if (current == address) && (last <= data.len()) && (stop >= end) {
+                return Ok( Cow::Borrowed( &data[first..last] ) );
+            }
+
+            let copied = core::cmp::min(last, data.len());
+
+            if first < copied {
+                output.extend_from_slice( &data[first..copied] );
+            }
+
+            output.resize( output.len() + (last - core::cmp::max(first, copied)), 0 );
+
+            current = start + last as u64;
         }
 
-        // Check if the .shstrtab section is present.
-        if sections.len() < header.shstrndx as usize {
-            return Err(());
+        Ok( Cow::Owned( output ) )
+    }
+
+    /// Reads the null terminated string at the given address of the loaded image.
+    /// The terminating null is not included in the string.
+    pub(super) fn read_cstr(&self, raw: &[u8], address: u64, space: AddressSpace) -> Result<String, Error> {
+        let mut string = Vec::new();
+        let mut current = address;
+
+        loop {
+            let (Range { start, end: stop }, data) = self.region(raw, current, space)?;
+            let first = (current - start) as usize;
+
+            // The zero filled part of a region terminates the string.
+            let bytes = data.get(first..).unwrap_or(&[]);
+
+            match bytes.iter().position(|byte| *byte == 0) {
+                Some(position) => {
+                    string.extend_from_slice( &bytes[..position] );
+                    break;
+                },
+
+                _ if (start + data.len() as u64) < stop => {
+                    string.extend_from_slice( bytes );
+                    break;
+                },
+
+                _ => {
+                    string.extend_from_slice( bytes );
+                    current = stop;
+                },
+            }
         }
 
-        // Load the names of all the sections.
-        {
-            // Get the offset and size of the .shstrtab section.
-            let offset   = usize::from( sections[ usize::from(header.shstrndx) ].offset );
-            let filesize = usize::from( sections[ usize::from(header.shstrndx) ].filesize );
+        Ok( String::from_utf8_lossy(&string).into_owned() )
+    }
 
-            // Get the range of raw data of this section.
-            let names = &raw[offset..offset+filesize];
+    /// Builds the memory image of the loadable segments at their physical (load) addresses, or at
+    /// their virtual addresses if no segment has a physical address.
+    /// The zero filled part of the segments (e.g. `.bss`) is not part of the image, and the entry
+    /// point is the start address of the image.
+    pub(super) fn image(&self, raw: &[u8]) -> Result<MemoryImage, Error> {
+        let mut image = MemoryImage::new();
 
-            // Rename all the sections.
-            for section in &mut sections {
-                section.rename( names );
+        // Segments without physical addresses load at their virtual addresses.
+        let physical = self.programs.iter()
+            .any(|program| (program.programtype == ProgramType::Load) && (u64::from( program.paddr ) != 0));
+
+        for (index, program) in self.programs.iter().enumerate() {
+            if program.programtype != ProgramType::Load {
+                continue;
             }
+
+            let address = match physical {
+                true => u64::from( program.paddr ),
+                _ => u64::from( program.vaddr ),
+            };
+
+            image.insert( address, Self::loaded(raw, index, program)? )?;
         }
 
-        // Put all sections in ARC.
-        let sections: Vec<Arc<SectionHeader>> = sections.into_iter()
-            .map(|x| Arc::new(x))
-            .collect();
+        image.set_start( Some( u64::from( self.header.entry ) ) );
+
+        Ok( image )
+    }
+
+    /// Returns the load address and contents of the sections that are loaded in memory, sorted by
+    /// load address. The load address of a section is found through the segment that contains it,
+    /// as done by the GNU binutils. Without sections, the loadable segments are used instead.
+    pub(super) fn loadable<'a>(&'a self, raw: &'a [u8]) -> Result<Vec<(u64, &'a [u8])>, Error> {
+        let loads = self.programs.iter()
+            .filter(|program| program.programtype == ProgramType::Load)
+            .collect::<Vec<_>>();
 
-        // Get the symbol table section contents.
-        let symtab = match sections.iter().find(|section| &section.name == ".symtab") {
+        let mut chunks = Vec::new();
+
+        // Segments without physical addresses load at their virtual addresses.
+        let physical = loads.iter().any(|program| u64::from( program.paddr ) != 0);
+
+        if self.sections.is_empty() {
+            for (index, program) in self.programs.iter().enumerate() {
+                if program.programtype != ProgramType::Load {
+                    continue;
+                }
+
+                let address = match physical {
+                    true => u64::from( program.paddr ),
+                    _ => u64::from( program.vaddr ),
+                };
+
+                chunks.push( (address, Self::loaded(raw, index, program)?) );
+            }
+        }
+
+        for (index, section) in self.sections.iter().enumerate() {
+            if ((u64::from( section.flags ) & section::ALLOC) == 0) || (section.sectiontype == SectionType::Bss) || (u64::from( section.filesize ) == 0) {
+                continue;
+            }
+
+            let (offset, vaddr, size) = (u64::from( section.offset ), u64::from( section.vaddr ), u64::from( section.filesize ));
+
+            // Find the segment that contains the section in the file and in memory.
+            let segment = loads.iter()
+                .filter(|_| physical)
+                .find(|program| {
+                    let (start, address) = (u64::from( program.offset ), u64::from( program.vaddr ));
+
+                    (offset >= start) && ((offset - start).checked_add(size) <= Some( u64::from(program.filesize) ))
+                        && (vaddr >= address) && ((vaddr - address).checked_add(size) <= Some( u64::from(program.memsize) ))
+                });
+
+            let address = match segment {
+                Some(program) => u64::from( program.paddr ).wrapping_add( offset - u64::from(program.offset) ),
+                _ => vaddr,
+            };
+
+            let start = offset as usize;
+
+            match start.checked_add(size as usize).and_then(|end| raw.get(start..end)) {
+                Some(data) => chunks.push( (address, data) ),
+                _ => return Err( Error::OutOfBounds { table: Table::SectionHeaders, index, offset: start } ),
+            }
+        }
+
+        chunks.sort_by_key(|(address, _)| *address);
+
+        Ok( chunks )
+    }
+
+    /// Internal function to get the contents of a segment in the file, without its zero filled part.
+    fn loaded<'a>(raw: &'a [u8], index: usize, program: &ProgramHeader) -> Result<&'a [u8], Error> {
+        let offset = usize::from( program.offset );
+        let size = core::cmp::min( u64::from(program.filesize), u64::from(program.memsize) ) as usize;
+
+        match offset.checked_add(size).and_then(|end| raw.get(offset..end)) {
+            Some(data) => Ok( data ),
+            _ => Err( Error::OutOfBounds { table: Table::ProgramHeaders, index, offset } ),
+        }
+    }
+
+    /// Returns the contents of the section at the given index.
+    /// Contents added or replaced after parsing take precedence over the file image.
+    pub(super) fn section_content<'a>(&'a self, raw: &'a [u8], index: usize) -> Result<&'a [u8], Error> {
+        let section = match self.sections.get(index) {
             Some(section) => section,
-            _ => return Err( () ),
+            _ => return Err( Error::OutOfBounds { table: Table::SectionHeaders, index, offset: 0 } ),
+        };
+
+        match &section.data {
+            Some(data) => Ok( &data[..] ),
+            _ => Self::contents(raw, section, Table::SectionHeaders, index),
+        }
+    }
+
+    /// Returns the compression header and the compressed data of the section at the given index.
+    /// Sections with the `SHF_COMPRESSED` flag start with an `Elf_Chdr` header, and legacy `.zdebug_*`
+    /// sections with the `ZLIB` magic. Returns `None` if the section is not compressed.
+    pub(super) fn compression<'a>(&'a self, raw: &'a [u8], index: usize) -> Result<Option<(CompressionHeader, &'a [u8])>, Error> {
+        use header::Endianness;
+
+        // Get the contents of the section.
+        let content = self.section_content(raw, index)?;
+        let section = &self.sections[index];
+
+        if section.sectiontype == SectionType::Bss {
+            return Ok( None );
+        }
+
+        let header = if (u64::from( section.flags ) & section::COMPRESSED) != 0 {
+            // Get the read functions.
+            let read: fn(&[u8]) -> Address = match (self.header.entry, self.header.endianness) {
+                (Address::U32(_), Endianness::Little) => crate::common::address::read32::<byteorder::LittleEndian>,
+                (Address::U32(_), Endianness::Big   ) => crate::common::address::read32::<byteorder::BigEndian>,
+
+                (Address::U64(_), Endianness::Little) => crate::common::address::read64::<byteorder::LittleEndian>,
+                (Address::U64(_), Endianness::Big   ) => crate::common::address::read64::<byteorder::BigEndian>,
+            };
+
+            let read32: fn(&[u8]) -> u32 = match self.header.endianness {
+                Endianness::Little => crate::common::bytes::read32::<byteorder::LittleEndian>,
+                Endianness::Big    => crate::common::bytes::read32::<byteorder::BigEndian>,
+            };
+
+            // Get the adequate compression header parse function.
+            let cparse: fn(&[u8], _, _, _, _) -> Result<CompressionHeader, _> = match self.header.entry {
+                Address::U32(_) => CompressionHeader::parse::<4>,
+                Address::U64(_) => CompressionHeader::parse::<8>,
+            };
+
+            cparse(content, index, usize::from( section.offset ), read, read32)?
+        } else if section.name.starts_with(compression::ZDEBUG_PREFIX) {
+            match CompressionHeader::legacy(content, u64::from( section.alignment )) {
+                Some(header) => header,
+                _ => return Ok( None ),
+            }
+        } else {
+            return Ok( None );
+        };
+
+        Ok( Some( (header, &content[header.headersize..]) ) )
+    }
+
+    /// Returns the uncompressed contents of the section at the given index.
+    /// The contents of sections that are not compressed are returned as they are.
+    pub(super) fn decompressed<'a>(&'a self, raw: &'a [u8], index: usize) -> Result<Cow<'a, [u8]>, Error> {
+        match self.compression(raw, index)? {
+            Some((header, data)) => compression::decompress(&header, data, index, usize::from( self.sections[index].offset )).map(Cow::Owned),
+            _ => self.section_content(raw, index).map(Cow::Borrowed),
+        }
+    }
+
+    /// Internal function to remap the symbol indices of the relocation entries in the given contents.
+    fn remap_relocations<F: FnMut(u32) -> u32>(&self, content: &mut [u8], entrysize: usize, mut remap: F) {
+        use byteorder::{ BigEndian, LittleEndian };
+        use crate::common::bytes::{ read32, read64, write32, write64 };
+        use header::Endianness;
+
+        // Get the size of an address.
+        let inc = self.header.entry.bytes();
+
+        // Check the entries hold the offset and the information.
+        if entrysize < (2 * inc) {
+            return;
+        }
+
+        // Get the function to read the information, which has the size of an address.
+        let read: fn(&[u8]) -> u64 = match (inc, self.header.endianness) {
+            (4, Endianness::Little) => |b| u64::from( read32::<LittleEndian>(b) ),
+            (4, Endianness::Big   ) => |b| u64::from( read32::<BigEndian>(b) ),
+
+            (_, Endianness::Little) => read64::<LittleEndian>,
+            (_, Endianness::Big   ) => read64::<BigEndian>,
+        };
+
+        // Get the function to write the information.
+        let write: fn(&mut [u8], u64) = match (inc, self.header.endianness) {
+            (4, Endianness::Little) => |b, n| write32::<LittleEndian>(b, n as u32),
+            (4, Endianness::Big   ) => |b, n| write32::<BigEndian>(b, n as u32),
+
+            (_, Endianness::Little) => write64::<LittleEndian>,
+            (_, Endianness::Big   ) => write64::<BigEndian>,
+        };
+
+        // The symbol index is in the upper bits of the information.
+        let shift = match inc {
+            4 => 8,
+            _ => 32,
         };
 
+        for entry in content.chunks_exact_mut(entrysize) {
+            let field = &mut entry[inc..2*inc];
+            let info = read( field );
+
+            let symbol = remap( (info >> shift) as u32 );
+            write( field, (u64::from(symbol) << shift) | (info & ((1 << shift) - 1)) );
+        }
+    }
+
+    /// Internal function to add a name to a string table being built, reusing the names already in it.
+    fn intern(table: &mut (Vec<u8>, HashMap<Vec<u8>, u32>), name: &[u8]) -> u32 {
+        // The empty name is always the first string.
+        if name.is_empty() {
+            return 0;
+        }
+
+        let (names, indices) = table;
+
+        if let Some(index) = indices.get(name) {
+            return *index;
+        }
+
+        let index = names.len() as u32;
+        names.extend_from_slice( name );
+        names.push(0);
+
+        indices.insert(name.to_vec(), index);
+
+        index
+    }
+
+    /// Internal function to parse the symbols of the given symbol table section.
+    /// The symbols are named with the string table linked to the symbol table.
+    /// Symbols with overflowed section indices are resolved through the extended section indices.
+    fn symbols<'a, F>(raw: &'a [u8], sections: &[Arc<SectionHeader>], index: usize, expected: usize, parse: F, read32: fn(&[u8]) -> u32) -> Result<Vec<Arc<Symbol>>, Error> where F: Fn(&'a [u8]) -> Result<Symbol, Error> {
+        // Get the symbol table section.
+        let symtab = &sections[index];
+
+        // Check the size of the symbols before counting them.
+        if usize::from(symtab.entrysize) == 0 {
+            return Err( Error::BadHeaderSize { table: Table::Symbols, expected, found: 0 } );
+        }
+
         // Create the symbol list.
         let mut symbols = Vec::new();
 
-        // Get the adequate Section Header parse function.
-        let sparse: fn(_, _, _, _) -> Result<Symbol, _> = match raw[0x04] {
-            1 => Symbol::parse::<_, 4>,
-            2 => Symbol::parse::<_, 8>,
+        for (i, chunk) in Self::chunks(raw, Table::Symbols, symtab.offset, usize::from(symtab.filesize) / usize::from(symtab.entrysize), symtab.entrysize, expected)?.enumerate() {
+            let mut symbol = parse(chunk)?;
+            symbol.original = Some( i as u32 );
+
+            symbols.push( symbol );
+        }
 
-            _ => return Err(()),
+        // Resolve the overflowed section indices with the extended section indices of this table.
+        let shndx = sections.iter()
+            .enumerate()
+            .find(|(_, section)| (section.sectiontype == SectionType::SymbolTableX) && (section.link as usize == index));
+
+        if let Some((shndxidx, shndx)) = shndx {
+            // Get the extended section indices.
+            let indices = Self::contents(raw, shndx, Table::SectionHeaders, shndxidx)?;
+
+            for (symbol, entry) in symbols.iter_mut().zip( indices.chunks_exact(4) ) {
+                if symbol.shidx == section::XINDEX {
+                    symbol.shidx = read32(entry);
+                    symbol.extended = true;
+                }
+            }
+        }
+
+        // Get the linked string table section.
+        let link = symtab.link as usize;
+
+        let strtab = match sections.get(link) {
+            Some(section) if link != 0 => section,
+            _ => return Err( Error::MissingStringTable { table: Table::Symbols, index: link } ),
         };
 
-        for chunk in Self::chunks(raw, symtab.offset, usize::from(symtab.filesize) / usize::from(symtab.entrysize), symtab.entrysize) {
-            symbols.push( sparse(chunk, read, read16, read32)? );
+        // Get the offset and range of raw data of the string table.
+        let offset = usize::from( strtab.offset );
+        let names = Self::contents(raw, strtab, Table::SectionHeaders, link)?;
+
+        // Rename all the symbols.
+        for (index, symbol) in symbols.iter_mut().enumerate() {
+            if !symbol.rename( names ) {
+                return Err( Error::BadNameIndex { table: Table::Symbols, index, offset: offset.saturating_add(symbol.nameidx as usize) } );
+            }
         }
 
-        // Load the names of all the symbols.
-        {
-            // Get the string table section contents.
-            let header = match sections.iter().find(|section| &section.name == ".strtab") {
-                Some(section) => section,
-                _ => return Err( () ),
+        // Put all symbols in ARC.
+        Ok( symbols.into_iter().map(Arc::new).collect() )
+    }
+
+    /// Internal function to parse the hash table of the dynamic symbols.
+    /// The table is found through its section or, if there is none, through the dynamic section.
+    /// The GNU hash table is preferred when both tables exist.
+    fn hashtable<G>(&self, raw: &[u8], entries: &[DynamicEntry], gparse: G, read32: fn(&[u8]) -> u32) -> Result<Option<HashTable>, Error> where G: Fn(&[u8], usize) -> Result<hash::GnuHash, Error> {
+        // Find the tables through their sections.
+        let gnu = self.sections.iter().position(|section| section.sectiontype == SectionType::GnuHash);
+        let sysv = self.sections.iter().position(|section| section.sectiontype == SectionType::Hash);
+
+        if let Some(index) = gnu.or(sysv) {
+            let section = &self.sections[index];
+            let content = Self::contents(raw, section, Table::SectionHeaders, index)?;
+
+            return match section.sectiontype {
+                SectionType::GnuHash => Ok( Some( HashTable::Gnu( gparse(content, usize::from(section.offset))? ) ) ),
+                _ => Ok( Some( HashTable::SysV( hash::SysVHash::parse(content, usize::from(section.offset), read32)? ) ) ),
             };
+        }
+
+        // Find the tables through the dynamic section.
+        let (mut gnu, mut sysv) = (None, None);
+
+        for entry in entries {
+            match *entry {
+                DynamicEntry::GnuHash(address) => gnu = self.file_offset(address),
+                DynamicEntry::Hash(address)    => sysv = self.file_offset(address),
+                _ => (),
+            }
+        }
 
-            // Get the offset and size of the .strtab section.
-            let offset   = usize::from( header.offset );
-            let filesize = usize::from( header.filesize );
+        // The segments may claim more data than the file has.
+        let table = |offset: usize| raw.get(offset..).ok_or( Error::OutOfBounds { table: Table::Hash, index: 0, offset } );
+
+        match (gnu, sysv) {
+            (Some(offset), _) => Ok( Some( HashTable::Gnu( gparse(table(offset)?, offset)? ) ) ),
+            (_, Some(offset)) => Ok( Some( HashTable::SysV( hash::SysVHash::parse(table(offset)?, offset, read32)? ) ) ),
+            _ => Ok( None ),
+        }
+    }
 
-            // Get the range of raw data of this section.
-            let names = &raw[offset..offset+filesize];
+    /// Internal function to parse the dynamic symbol table found through the dynamic section (`DT_SYMTAB`).
+    /// The number of symbols is taken from the hash table of the dynamic symbols.
+    fn tagged_symbols<'a, F>(&self, raw: &'a [u8], entries: &[DynamicEntry], inc: usize, parse: F) -> Result<Vec<Arc<Symbol>>, Error> where F: Fn(&'a [u8]) -> Result<Symbol, Error> {
+        // Get the addresses of the tables.
+        let (mut symtab, mut strtab, mut strsize) = (None, None, None);
+        let expected = 8 + (2 * inc);
+        let mut entrysize = expected;
 
-            // Rename all the sections.
-            for symbol in &mut symbols {
-                symbol.rename( names );
+        for entry in entries {
+            match *entry {
+                DynamicEntry::SymTab(address) => symtab = Some( address ),
+                DynamicEntry::StrTab(address) => strtab = Some( address ),
+                DynamicEntry::StrSize(size)   => strsize = Some( size as usize ),
+                DynamicEntry::SymEntry(size)  => entrysize = size as usize,
+                _ => (),
             }
         }
 
-        // Put all symbols in ARC.
-        let symbols = symbols.into_iter()
-            .map(|x| Arc::new(x))
-            .collect();
+        // Get the offset of the symbol table in the file and the number of symbols.
+        let (offset, count) = match (symtab.and_then(|address| self.file_offset(address)), &self.hash) {
+            (Some(offset), Some(hash)) => (offset, hash.count()),
+            _ => return Ok( Vec::new() ),
+        };
 
-        Ok(Self {
-            header,
-            programs,
-            sections,
-            symbols,
-        })
+        // Get the dynamic string table.
+        let (start, strings) = match self.dynstr(raw, strtab, strsize) {
+            Some(strings) => strings,
+            _ => return Err( Error::MissingStringTable { table: Table::Symbols, index: 0 } ),
+        };
+
+        // Parse and name the symbols.
+        let mut symbols = Vec::new();
+
+        for (index, chunk) in Self::chunks(raw, Table::Symbols, offset, count, entrysize, expected)?.enumerate() {
+            let mut symbol = parse(chunk)?;
+
+            if !symbol.rename( strings ) {
+                return Err( Error::BadNameIndex { table: Table::Symbols, index, offset: start.saturating_add(symbol.nameidx as usize) } );
+            }
+
+            symbol.original = Some( index as u32 );
+            symbols.push( Arc::new( symbol ) );
+        }
+
+        Ok( symbols )
+    }
+
+    /// Internal function to get the dynamic string table and its offset given its address and size.
+    fn dynstr<'a>(&self, raw: &'a [u8], address: Option<u64>, size: Option<usize>) -> Option<(usize, &'a [u8])> {
+        let start = self.file_offset( address? )?;
+        raw.get( start..start.checked_add( size? )? ).map(|strings| (start, strings))
+    }
+
+    /// Internal function to parse the GNU versions of the dynamic symbols and the versions required from each library.
+    /// The tables are found through their sections or, if there are none, through the dynamic section.
+    /// Returns the version of each dynamic symbol and the requirements.
+    fn versions(&self, raw: &[u8], entries: &[DynamicEntry], read16: fn(&[u8]) -> u16, read32: fn(&[u8]) -> u32) -> Result<(Vec<Option<version::SymbolVersion>>, Vec<VersionRequirement>), Error> {
+        // Find the tables through their sections.
+        let find = |sectiontype| self.sections.iter().position(|section| section.sectiontype == sectiontype);
+        let (versym, verdef, verneed) = (find(SectionType::VerSym), find(SectionType::VerDef), find(SectionType::VerNeed));
+
+        // Get the offset of the version symbols, the offset and number of definitions and requirements and the string table.
+        let (versym, verdef, verneed, strings) = match (versym, verdef, verneed) {
+            (None, None, None) => {
+                let (mut versym, mut verdef, mut verneed) = (None, (None, 0), (None, 0));
+                let (mut strtab, mut strsize) = (None, None);
+
+                for entry in entries {
+                    match *entry {
+                        DynamicEntry::VerSym(address)  => versym = self.file_offset(address),
+                        DynamicEntry::VerDef(address)  => verdef.0 = self.file_offset(address),
+                        DynamicEntry::VerDefNum(n)     => verdef.1 = n as usize,
+                        DynamicEntry::VerNeed(address) => verneed.0 = self.file_offset(address),
+                        DynamicEntry::VerNeedNum(n)    => verneed.1 = n as usize,
+                        DynamicEntry::StrTab(address)  => strtab = Some( address ),
+                        DynamicEntry::StrSize(size)    => strsize = Some( size as usize ),
+                        _ => (),
+                    }
+                }
+
+                let verdef = verdef.0.map(|offset| (offset, verdef.1));
+                let verneed = verneed.0.map(|offset| (offset, verneed.1));
+
+                (versym, verdef, verneed, self.dynstr(raw, strtab, strsize).ok_or( 0 ))
+            },
+
+            (versym, verdef, verneed) => {
+                // Check that the tables are in the file.
+                for index in [versym, verdef, verneed].iter().flatten() {
+                    Self::contents(raw, &self.sections[*index], Table::SectionHeaders, *index)?;
+                }
+
+                let table = |index: usize| (usize::from( self.sections[index].offset ), self.sections[index].info as usize);
+
+                // The definitions and requirements are linked to the dynamic string table.
+                let link = verdef.or(verneed).map(|index| self.sections[index].link as usize).unwrap_or(0);
+
+                let strings = match self.sections.get(link) {
+                    Some(section) if link != 0 => Self::contents(raw, section, Table::SectionHeaders, link).map(|strings| (usize::from( section.offset ), strings)).map_err(|_| link),
+                    _ => Err( link ),
+                };
+
+                (versym.map(|index| table(index).0), verdef.map(table), verneed.map(table), strings)
+            },
+        };
+
+        // Parse the definitions and requirements.
+        let (mut definitions, mut requirements) = (Vec::new(), Vec::new());
+
+        if verdef.is_some() || verneed.is_some() {
+            let strings = strings.map_err(|index| Error::MissingStringTable { table: Table::Versions, index })?;
+
+            if let Some((offset, count)) = verdef {
+                definitions = version::definitions(raw, offset, count, strings, read16, read32)?;
+            }
+
+            if let Some((offset, count)) = verneed {
+                requirements = version::requirements(raw, offset, count, strings, read16, read32)?;
+            }
+        }
+
+        // Get the version of each dynamic symbol.
+        let mut versions = Vec::new();
+
+        if let Some(offset) = versym {
+            for index in 0..self.dynsyms.len() {
+                let entry = offset.checked_add(2 * index).and_then(|start| raw.get(start..start.checked_add(2)?));
+
+                let versym = match entry {
+                    Some(entry) => read16(entry),
+                    _ => return Err( Error::OutOfBounds { table: Table::Versions, index, offset: offset.saturating_add(2 * index) } ),
+                };
+
+                versions.push( version::version(versym, &definitions, &requirements) );
+            }
+        }
+
+        Ok( (versions, requirements) )
     }
 
     /// Internal function to create chunk iterators over the tables of the file.
-    fn chunks<O: Copy, N: Copy, S: Copy>(raw: &[u8], offset: O, num: N, size: S) -> core::slice::ChunksExact<u8> where usize: From<O> + From<N> + From<S> {
-        // Calculate the start and end.
-        let start = usize::from(offset);
-        let end = usize::from(offset) + (usize::from(num) * usize::from(size));
+    /// Checks that the entries have at least the expected size and that the whole table is in the file.
+    fn chunks<O: Copy, N: Copy, S: Copy>(raw: &[u8], table: Table, offset: O, num: N, size: S, expected: usize) -> Result<core::slice::ChunksExact<'_, u8>, Error> where usize: From<O> + From<N> + From<S> {
+        // Get the table dimensions.
+        let (offset, num, size) = (usize::from(offset), usize::from(num), usize::from(size));
+
+        // An empty table is always valid.
+        if num == 0 {
+            return Ok( raw[0..0].chunks_exact(1) );
+        }
+
+        // Check the size of the entries.
+        if size < expected {
+            return Err( Error::BadHeaderSize { table, expected, found: size } );
+        }
+
+        // Calculate the start and end, checking they do not overflow.
+        let start = offset;
+        let end = num.checked_mul(size).and_then(|len| start.checked_add(len));
+
+        match end.and_then(|end| raw.get(start..end)) {
+            Some(data) => Ok( data.chunks_exact(size) ),
 
-        raw[start..end].chunks_exact(usize::from(size))
+            _ => {
+                // Locate the first entry that is not completely in the file.
+                let index = raw.len().saturating_sub(start) / size;
+
+                Err( Error::OutOfBounds { table, index, offset: start.saturating_add(index * size) } )
+            },
+        }
+    }
+
+    /// Internal function to get the contents of a section, checking they are in the file.
+    fn contents<'a>(raw: &'a [u8], section: &SectionHeader, table: Table, index: usize) -> Result<&'a [u8], Error> {
+        // Get the offset and size of the section.
+        let offset = usize::from( section.offset );
+        let size = usize::from( section.filesize );
+
+        match offset.checked_add(size).and_then(|end| raw.get(offset..end)) {
+            Some(data) => Ok( data ),
+            _ => Err( Error::OutOfBounds { table, index, offset } ),
+        }
     }
 
     /// Returns the endianness of the ELF object.
@@ -207,7 +1776,7 @@ impl core::fmt::Display for ELFData {
         args += "ELF Object 32-bit\n";
 
         // Add the header.
-        args += &format!("{}", self.header.prettyprint());
+        args += &self.header.prettyprint();
 
         // Add the sections.
         for program in &self.programs {
@@ -244,4 +1813,9 @@ pub trait HasContent {
 
     /// Returns the offset into the file image.
     fn offset(&self) -> usize;
+
+    /// Returns the contents of the item if they are not in the file image.
+    fn data(&self) -> Option<Arc<[u8]>> {
+        None
+    }
 }
@@ -0,0 +1,381 @@
+//! Notes of an ELF file.
+//! Iteration over the notes of the `PT_NOTE` segments and `SHT_NOTE` sections and decoding of the
+//! well-known GNU notes.
+
+
+
+use crate::error::{ Error, Table };
+
+use super::header::Architecture;
+
+
+
+/// Type of the build ID note (`NT_GNU_BUILD_ID`).
+pub const NT_GNU_BUILD_ID: u32 = 3;
+
+/// Type of the ABI tag note (`NT_GNU_ABI_TAG`).
+pub const NT_GNU_ABI_TAG: u32 = 1;
+
+/// Type of the gold linker version note (`NT_GNU_GOLD_VERSION`).
+pub const NT_GNU_GOLD_VERSION: u32 = 4;
+
+/// Type of the program property note (`NT_GNU_PROPERTY_TYPE_0`).
+pub const NT_GNU_PROPERTY_TYPE_0: u32 = 5;
+
+/// Feature property of AArch64 (`GNU_PROPERTY_AARCH64_FEATURE_1_AND`).
+pub const GNU_PROPERTY_AARCH64_FEATURE_1_AND: u32 = 0xC000_0000;
+
+/// Feature property of x86 (`GNU_PROPERTY_X86_FEATURE_1_AND`).
+pub const GNU_PROPERTY_X86_FEATURE_1_AND: u32 = 0xC000_0002;
+
+
+
+/// A note of the object.
+#[derive(Debug, Clone)]
+pub struct Note<'a> {
+    /// Name of the owner of the note, without the terminating null.
+    pub(super) name: &'a str,
+
+    /// Type of the note, interpreted according to its owner.
+    pub(super) kind: u32,
+
+    /// Descriptor of the note.
+    pub(super) descriptor: &'a [u8],
+
+    /// Alignment of the note (4 or 8 bytes).
+    pub(super) align: usize,
+
+    /// Architecture of the object, which defines the meaning of some properties.
+    pub(super) architecture: Architecture,
+
+    /// Function to read a 32 bit value in the endianness of the object.
+    pub(super) read32: fn(&[u8]) -> u32,
+}
+
+impl<'a> Note<'a> {
+    /// Returns the name of the owner of the note.
+    pub fn name(&self) -> &'a str {
+        self.name
+    }
+
+    /// Returns the type of the note.
+    pub fn kind(&self) -> u32 {
+        self.kind
+    }
+
+    /// Returns the descriptor of the note.
+    pub fn descriptor(&self) -> &'a [u8] {
+        self.descriptor
+    }
+
+    /// Decodes the descriptor of a well-known GNU note.
+    /// Returns `None` if the note is not a well-known note or its descriptor is malformed.
+    pub fn decode(&self) -> Option<NoteData<'a>> {
+        if self.name != "GNU" {
+            return None;
+        }
+
+        let desc = self.descriptor;
+
+        match self.kind {
+            NT_GNU_BUILD_ID => Some( NoteData::BuildId(desc) ),
+
+            NT_GNU_ABI_TAG if desc.len() >= 16 => {
+                let word = |i: usize| (self.read32)( &desc[4*i..4*(i+1)] );
+                Some( NoteData::AbiTag { os: AbiOS::from( word(0) ), major: word(1), minor: word(2), patch: word(3) } )
+            },
+
+            NT_GNU_GOLD_VERSION => {
+                let end = desc.iter().position(|b| *b == 0).unwrap_or(desc.len());
+                core::str::from_utf8( &desc[..end] ).ok().map(NoteData::GoldVersion)
+            },
+
+            NT_GNU_PROPERTY_TYPE_0 => self.properties().map(NoteData::Properties),
+
+            _ => None,
+        }
+    }
+
+    /// Internal function to decode the array of program properties.
+    /// Each property is padded to the alignment of the note.
+    fn properties(&self) -> Option<Vec<Property<'a>>> {
+        let mut properties = Vec::new();
+        let mut desc = self.descriptor;
+
+        while !desc.is_empty() {
+            // Read the property header.
+            let header = desc.get(0..8)?;
+            let (kind, size) = ((self.read32)( &header[0..4] ), (self.read32)( &header[4..8] ) as usize);
+            let data = desc.get( 8..size.checked_add(8)? )?;
+
+            // Decode the known properties.
+            let features = data.get(0..4).filter(|_| size == 4).map(self.read32);
+
+            let property = match (self.architecture, kind, features) {
+                (Architecture::X86, GNU_PROPERTY_X86_FEATURE_1_AND, Some(raw)) | (Architecture::AMD64, GNU_PROPERTY_X86_FEATURE_1_AND, Some(raw)) =>
+                    Property::X86Features { ibt: (raw & 0x1) != 0, shstk: (raw & 0x2) != 0, raw },
+
+                (Architecture::Aarch64, GNU_PROPERTY_AARCH64_FEATURE_1_AND, Some(raw)) =>
+                    Property::AArch64Features { bti: (raw & 0x1) != 0, pac: (raw & 0x2) != 0, raw },
+
+                _ => Property::Other { kind, data },
+            };
+
+            properties.push( property );
+
+            // Skip the padding, which may be missing at the end of the descriptor.
+            let next = align(8 + size, self.align)?;
+            desc = desc.get(next..).unwrap_or(&[]);
+        }
+
+        Some( properties )
+    }
+}
+
+
+
+/// The decoded descriptor of a well-known GNU note.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NoteData<'a> {
+    /// Unique build ID of the object (`NT_GNU_BUILD_ID`).
+    BuildId(&'a [u8]),
+
+    /// Target OS and earliest compatible kernel version (`NT_GNU_ABI_TAG`).
+    AbiTag {
+        /// Target OS.
+        os: AbiOS,
+
+        /// Major version of the kernel.
+        major: u32,
+
+        /// Minor version of the kernel.
+        minor: u32,
+
+        /// Patch version of the kernel.
+        patch: u32,
+    },
+
+    /// Program properties (`NT_GNU_PROPERTY_TYPE_0`).
+    Properties(Vec<Property<'a>>),
+
+    /// Version of the gold linker that linked the object (`NT_GNU_GOLD_VERSION`).
+    GoldVersion(&'a str),
+}
+
+impl<'a> core::fmt::Display for NoteData<'a> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self {
+            NoteData::BuildId(id) => write!(f, "Build ID: {}", id.iter().map(|b| format!("{:02x}", b)).collect::<String>()),
+
+            NoteData::AbiTag { os, major, minor, patch } => write!(f, "ABI tag: {} {}.{}.{}", os, major, minor, patch),
+
+            NoteData::Properties(properties) => {
+                write!(f, "Properties:")?;
+
+                for property in properties {
+                    write!(f, " [{}]", property)?;
+                }
+
+                Ok(())
+            },
+
+            NoteData::GoldVersion(version) => write!(f, "Gold version: {}", version),
+        }
+    }
+}
+
+
+
+/// Target OS of the ABI tag note.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AbiOS {
+    Linux,
+    Hurd,
+    Solaris,
+    FreeBSD,
+
+    /// Unknown OS.
+    Other(u32),
+}
+
+impl core::convert::From<u32> for AbiOS {
+    fn from(os: u32) -> AbiOS {
+        match os {
+            0 => AbiOS::Linux,
+            1 => AbiOS::Hurd,
+            2 => AbiOS::Solaris,
+            3 => AbiOS::FreeBSD,
+            x => AbiOS::Other(x),
+        }
+    }
+}
+
+impl core::fmt::Display for AbiOS {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self {
+            AbiOS::Linux    => write!(f, "Linux"),
+            AbiOS::Hurd     => write!(f, "Hurd"),
+            AbiOS::Solaris  => write!(f, "Solaris"),
+            AbiOS::FreeBSD  => write!(f, "FreeBSD"),
+            AbiOS::Other(x) => write!(f, "OS {}", x),
+        }
+    }
+}
+
+
+
+/// A program property of the `NT_GNU_PROPERTY_TYPE_0` note.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Property<'a> {
+    /// x86 control-flow protection features (`GNU_PROPERTY_X86_FEATURE_1_AND`).
+    X86Features {
+        /// Indirect branch tracking.
+        ibt: bool,
+
+        /// Shadow stack.
+        shstk: bool,
+
+        /// Raw feature bits.
+        raw: u32,
+    },
+
+    /// AArch64 control-flow protection features (`GNU_PROPERTY_AARCH64_FEATURE_1_AND`).
+    AArch64Features {
+        /// Branch target identification.
+        bti: bool,
+
+        /// Pointer authentication.
+        pac: bool,
+
+        /// Raw feature bits.
+        raw: u32,
+    },
+
+    /// Property without typed decoding.
+    Other {
+        /// Type of the property.
+        kind: u32,
+
+        /// Data of the property.
+        data: &'a [u8],
+    },
+}
+
+impl<'a> core::fmt::Display for Property<'a> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        // Lists the names of the enabled features.
+        let features = |list: &[(bool, &str)]| list.iter().filter(|(set, _)| *set).map(|(_, name)| *name).collect::<Vec<_>>().join(", ");
+
+        match self {
+            Property::X86Features { ibt, shstk, .. } => write!(f, "x86 features: {}", features(&[(*ibt, "IBT"), (*shstk, "SHSTK")])),
+            Property::AArch64Features { bti, pac, .. } => write!(f, "AArch64 features: {}", features(&[(*bti, "BTI"), (*pac, "PAC")])),
+            Property::Other { kind, data } => write!(f, "Property 0x{:08X} ({} bytes)", kind, data.len()),
+        }
+    }
+}
+
+
+
+/// A region of the file that contains notes.
+#[derive(Debug, Clone)]
+pub(super) struct Region<'a> {
+    /// Contents of the region, or the error found when locating it.
+    pub(super) data: Result<&'a [u8], Error>,
+
+    /// Offset of the region in the file image.
+    pub(super) offset: usize,
+
+    /// Alignment of the notes in the region.
+    pub(super) align: usize,
+}
+
+/// Iterator over the notes of the object.
+/// Yields an error and skips the rest of the region when a note is malformed.
+#[derive(Debug, Clone)]
+pub struct NoteIterator<'a> {
+    /// Regions left to iterate, in reverse order.
+    pub(super) regions: Vec<Region<'a>>,
+
+    /// Position in the current region.
+    pub(super) position: usize,
+
+    /// Number of notes iterated, used to report errors.
+    pub(super) index: usize,
+
+    /// Architecture of the object.
+    pub(super) architecture: Architecture,
+
+    /// Function to read a 32 bit value in the endianness of the object.
+    pub(super) read32: fn(&[u8]) -> u32,
+}
+
+impl<'a> Iterator for NoteIterator<'a> {
+    type Item = Result<Note<'a>, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // Get the current region.
+        let region = self.regions.last()?;
+
+        let data = match region.data {
+            Ok(data) => data,
+            Err(error) => {
+                self.regions.pop();
+                return Some( Err(error) );
+            },
+        };
+
+        // Go to the next region at the end of this one.
+        if self.position >= data.len() {
+            self.regions.pop();
+            self.position = 0;
+            return self.next();
+        }
+
+        // Parse the note.
+        let note = parse(&data[self.position..], region.align, self.read32);
+        let offset = region.offset.saturating_add(self.position);
+        let index = self.index;
+
+        self.index += 1;
+
+        match note {
+            Some((name, kind, descriptor, size)) => {
+                self.position += size;
+                Some( Ok( Note { name, kind, descriptor, align: region.align, architecture: self.architecture, read32: self.read32 } ) )
+            },
+
+            _ => {
+                self.regions.pop();
+                self.position = 0;
+                Some( Err( Error::OutOfBounds { table: Table::Notes, index, offset } ) )
+            },
+        }
+    }
+}
+
+/// Parses the note at the start of the data.
+/// Returns the name, type and descriptor of the note and its padded size.
+fn parse(data: &[u8], alignment: usize, read32: fn(&[u8]) -> u32) -> Option<(&str, u32, &[u8], usize)> {
+    // Read the header.
+    let header = data.get(0..12)?;
+    let (namesz, descsz, kind) = (read32( &header[0..4] ) as usize, read32( &header[4..8] ) as usize, read32( &header[8..12] ));
+
+    // Read the name, without the terminating null.
+    let name = data.get( 12..namesz.checked_add(12)? )?;
+    let name = core::str::from_utf8( name.strip_suffix(&[0]).unwrap_or(name) ).ok()?;
+
+    // Read the descriptor, which is aligned after the name.
+    let start = align(12 + namesz, alignment)?;
+    let end = start.checked_add(descsz)?;
+    let descriptor = data.get(start..end)?;
+
+    // The padding of the last note may be missing.
+    let size = core::cmp::min(align(end, alignment)?, data.len());
+
+    Some( (name, kind, descriptor, size) )
+}
+
+/// Rounds the value up to the given alignment.
+fn align(value: usize, alignment: usize) -> Option<usize> {
+    value.checked_next_multiple_of(alignment)
+}
@@ -13,6 +13,13 @@ impl core::convert::From<u32> for Flags {
     }
 }
 
+impl core::convert::From<Flags> for u32 {
+    #[inline(always)]
+    fn from(f: Flags) -> u32 {
+        f.0
+    }
+}
+
 impl core::fmt::Display for Flags {
     fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
         write!(f, "0x{:08X}", self.0)
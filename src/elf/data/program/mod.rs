@@ -11,12 +11,33 @@ mod programtype;
 pub use flags::Flags;
 pub use programtype::ProgramType;
 
-use crate::common::address::Address;
+use crate::{
+    common::address::Address,
+    error::{ Error, Table },
+};
+
+
+
+/// Escape value of the number of program headers when it does not fit in
+/// the file header (`PN_XNUM`).
+pub const XNUM: u32 = 0xFFFF;
+
+
+
+/// The address space used to locate the contents of a segment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AddressSpace {
+    /// Virtual addresses (VMA), where the contents are at execution time.
+    Virtual,
+
+    /// Physical addresses (LMA), where the contents are loaded (e.g. the flash of a microcontroller).
+    Physical,
+}
 
 
 
 /// A common program header structure. Will be instantiated by each implementator.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct ProgramHeader {
     /// Type of program header.
     pub(super) programtype: ProgramType,
@@ -45,7 +66,7 @@ pub struct ProgramHeader {
 
 impl ProgramHeader {
     /// Parses the given slice of data into an ELF file header.
-    pub fn parse<R: AsRef<[u8]>, const INC: usize>(raw: R, read: fn(&[u8]) -> Address, read32: fn(&[u8]) -> u32) -> Result<Self, ()> {
+    pub fn parse<R: AsRef<[u8]>, const INC: usize>(raw: R, read: fn(&[u8]) -> Address, read32: fn(&[u8]) -> u32) -> Result<Self, Error> {
         // Header size constant.
         let hsize: usize = 8 + (6 * INC);
 
@@ -54,7 +75,7 @@ impl ProgramHeader {
 
         // Check there is minimum length.
         if raw.len() < hsize {
-            return Err(());
+            return Err( Error::BadHeaderSize { table: Table::ProgramHeaders, expected: hsize, found: raw.len() } );
         }
 
         // Get the program type.
@@ -100,7 +121,7 @@ impl ProgramHeader {
         let alignment = read( &raw[i..i+INC] );
         i += INC;
 
-        assert_eq!(i, hsize);
+        debug_assert_eq!(i, hsize);
 
         Ok(Self {
             programtype,
@@ -116,13 +137,59 @@ impl ProgramHeader {
 }
 
 impl ProgramHeader {
+    /// Writes the program header into the given slice of data.
+    /// The slice must be at least as long as a program header of the class.
+    pub fn write<const INC: usize>(&self, raw: &mut [u8], write: fn(&mut [u8], Address), write32: fn(&mut [u8], u32)) {
+        // Write the program type.
+        write32( &mut raw[0x00..0x04], u32::from(self.programtype) );
+
+        // Create the dynamic index.
+        let mut i = 0x04;
+
+        if INC == 8 {
+            write32( &mut raw[i..i+4], u32::from(self.flags) );
+            i += 4;
+        }
+
+        // Write the offset.
+        write( &mut raw[i..i+INC], self.offset );
+        i += INC;
+
+        // Write the virtual address.
+        write( &mut raw[i..i+INC], self.vaddr );
+        i += INC;
+
+        // Write the physical address.
+        write( &mut raw[i..i+INC], self.paddr );
+        i += INC;
+
+        // Write the file size.
+        write( &mut raw[i..i+INC], self.filesize );
+        i += INC;
+
+        // Write the memory size.
+        write( &mut raw[i..i+INC], self.memsize );
+        i += INC;
+
+        if INC == 4 {
+            write32( &mut raw[i..i+4], u32::from(self.flags) );
+            i += 4;
+        }
+
+        // Write the alignment.
+        write( &mut raw[i..i+INC], self.alignment );
+        i += INC;
+
+        debug_assert_eq!(i, 8 + (6 * INC));
+    }
+
     /// Creates a pretty print of the segment's information.
     pub fn prettyprint(&self) -> String {
         // Create output string.
         let mut args = String::new();
 
         // Section name.
-        args += &format!("Program:\n");
+        args += "Program:\n";
 
         // Section type.
         args += &format!("  Program type: {}\n", self.programtype);
@@ -2,7 +2,7 @@
 
 
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ProgramType {
     Null        ,
     Load        ,
@@ -14,6 +14,7 @@ pub enum ProgramType {
     TLS         ,
     OS(u32)     ,
     Process(u32),
+    Other(u32)  ,
 }
 
 impl core::convert::From<u32> for ProgramType {
@@ -32,7 +33,28 @@ impl core::convert::From<u32> for ProgramType {
             0x60000000..=0x6FFFFFFF => OS(t),
             0x70000000..=0x7FFFFFFF => Process(t),
 
-            _ => Null,
+            0x00000000 => Null,
+
+            _ => Other(t),
+        }
+    }
+}
+
+impl core::convert::From<ProgramType> for u32 {
+    fn from(t: ProgramType) -> u32 {
+        use ProgramType::*;
+
+        match t {
+            Null        => 0x00000000,
+            Load        => 0x00000001,
+            Dynamic     => 0x00000002,
+            Interpreter => 0x00000003,
+            Note        => 0x00000004,
+            SharedLib   => 0x00000005,
+            ProgHeader  => 0x00000006,
+            TLS         => 0x00000007,
+
+            OS(t) | Process(t) | Other(t) => t,
         }
     }
 }
@@ -52,6 +74,7 @@ impl core::fmt::Display for ProgramType {
             TLS          => String::from("Thread Local Storage"),
             OS(t)        => format!("OS Specific ({})", t),
             Process(t)   => format!("Process Specific ({})", t),
+            Other(t)     => format!("Unknown ({})", t),
         };
 
         write!(f, "{}", arg)
@@ -0,0 +1,776 @@
+//! AArch64 relocation types.
+
+
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AArch64 {
+    /// `R_AARCH64_NONE`.
+    None,
+
+    /// `R_AARCH64_ABS64`.
+    Abs64,
+
+    /// `R_AARCH64_ABS32`.
+    Abs32,
+
+    /// `R_AARCH64_ABS16`.
+    Abs16,
+
+    /// `R_AARCH64_PREL64`.
+    Prel64,
+
+    /// `R_AARCH64_PREL32`.
+    Prel32,
+
+    /// `R_AARCH64_PREL16`.
+    Prel16,
+
+    /// `R_AARCH64_MOVW_UABS_G0`.
+    MovwUabsG0,
+
+    /// `R_AARCH64_MOVW_UABS_G0_NC`.
+    MovwUabsG0Nc,
+
+    /// `R_AARCH64_MOVW_UABS_G1`.
+    MovwUabsG1,
+
+    /// `R_AARCH64_MOVW_UABS_G1_NC`.
+    MovwUabsG1Nc,
+
+    /// `R_AARCH64_MOVW_UABS_G2`.
+    MovwUabsG2,
+
+    /// `R_AARCH64_MOVW_UABS_G2_NC`.
+    MovwUabsG2Nc,
+
+    /// `R_AARCH64_MOVW_UABS_G3`.
+    MovwUabsG3,
+
+    /// `R_AARCH64_MOVW_SABS_G0`.
+    MovwSabsG0,
+
+    /// `R_AARCH64_MOVW_SABS_G1`.
+    MovwSabsG1,
+
+    /// `R_AARCH64_MOVW_SABS_G2`.
+    MovwSabsG2,
+
+    /// `R_AARCH64_LD_PREL_LO19`.
+    LdPrelLo19,
+
+    /// `R_AARCH64_ADR_PREL_LO21`.
+    AdrPrelLo21,
+
+    /// `R_AARCH64_ADR_PREL_PG_HI21`.
+    AdrPrelPgHi21,
+
+    /// `R_AARCH64_ADR_PREL_PG_HI21_NC`.
+    AdrPrelPgHi21Nc,
+
+    /// `R_AARCH64_ADD_ABS_LO12_NC`.
+    AddAbsLo12Nc,
+
+    /// `R_AARCH64_LDST8_ABS_LO12_NC`.
+    Ldst8AbsLo12Nc,
+
+    /// `R_AARCH64_TSTBR14`.
+    Tstbr14,
+
+    /// `R_AARCH64_CONDBR19`.
+    Condbr19,
+
+    /// `R_AARCH64_JUMP26`.
+    Jump26,
+
+    /// `R_AARCH64_CALL26`.
+    Call26,
+
+    /// `R_AARCH64_LDST16_ABS_LO12_NC`.
+    Ldst16AbsLo12Nc,
+
+    /// `R_AARCH64_LDST32_ABS_LO12_NC`.
+    Ldst32AbsLo12Nc,
+
+    /// `R_AARCH64_LDST64_ABS_LO12_NC`.
+    Ldst64AbsLo12Nc,
+
+    /// `R_AARCH64_MOVW_PREL_G0`.
+    MovwPrelG0,
+
+    /// `R_AARCH64_MOVW_PREL_G0_NC`.
+    MovwPrelG0Nc,
+
+    /// `R_AARCH64_MOVW_PREL_G1`.
+    MovwPrelG1,
+
+    /// `R_AARCH64_MOVW_PREL_G1_NC`.
+    MovwPrelG1Nc,
+
+    /// `R_AARCH64_MOVW_PREL_G2`.
+    MovwPrelG2,
+
+    /// `R_AARCH64_MOVW_PREL_G2_NC`.
+    MovwPrelG2Nc,
+
+    /// `R_AARCH64_MOVW_PREL_G3`.
+    MovwPrelG3,
+
+    /// `R_AARCH64_LDST128_ABS_LO12_NC`.
+    Ldst128AbsLo12Nc,
+
+    /// `R_AARCH64_MOVW_GOTOFF_G0`.
+    MovwGotoffG0,
+
+    /// `R_AARCH64_MOVW_GOTOFF_G0_NC`.
+    MovwGotoffG0Nc,
+
+    /// `R_AARCH64_MOVW_GOTOFF_G1`.
+    MovwGotoffG1,
+
+    /// `R_AARCH64_MOVW_GOTOFF_G1_NC`.
+    MovwGotoffG1Nc,
+
+    /// `R_AARCH64_MOVW_GOTOFF_G2`.
+    MovwGotoffG2,
+
+    /// `R_AARCH64_MOVW_GOTOFF_G2_NC`.
+    MovwGotoffG2Nc,
+
+    /// `R_AARCH64_MOVW_GOTOFF_G3`.
+    MovwGotoffG3,
+
+    /// `R_AARCH64_GOTREL64`.
+    Gotrel64,
+
+    /// `R_AARCH64_GOTREL32`.
+    Gotrel32,
+
+    /// `R_AARCH64_GOT_LD_PREL19`.
+    GotLdPrel19,
+
+    /// `R_AARCH64_LD64_GOTOFF_LO15`.
+    Ld64GotoffLo15,
+
+    /// `R_AARCH64_ADR_GOT_PAGE`.
+    AdrGotPage,
+
+    /// `R_AARCH64_LD64_GOT_LO12_NC`.
+    Ld64GotLo12Nc,
+
+    /// `R_AARCH64_LD64_GOTPAGE_LO15`.
+    Ld64GotpageLo15,
+
+    /// `R_AARCH64_TLSGD_ADR_PREL21`.
+    TlsgdAdrPrel21,
+
+    /// `R_AARCH64_TLSGD_ADR_PAGE21`.
+    TlsgdAdrPage21,
+
+    /// `R_AARCH64_TLSGD_ADD_LO12_NC`.
+    TlsgdAddLo12Nc,
+
+    /// `R_AARCH64_TLSGD_MOVW_G1`.
+    TlsgdMovwG1,
+
+    /// `R_AARCH64_TLSGD_MOVW_G0_NC`.
+    TlsgdMovwG0Nc,
+
+    /// `R_AARCH64_TLSLD_ADR_PREL21`.
+    TlsldAdrPrel21,
+
+    /// `R_AARCH64_TLSLD_ADR_PAGE21`.
+    TlsldAdrPage21,
+
+    /// `R_AARCH64_TLSLD_ADD_LO12_NC`.
+    TlsldAddLo12Nc,
+
+    /// `R_AARCH64_TLSLD_MOVW_G1`.
+    TlsldMovwG1,
+
+    /// `R_AARCH64_TLSLD_MOVW_G0_NC`.
+    TlsldMovwG0Nc,
+
+    /// `R_AARCH64_TLSLD_LD_PREL19`.
+    TlsldLdPrel19,
+
+    /// `R_AARCH64_TLSLD_MOVW_DTPREL_G2`.
+    TlsldMovwDtprelG2,
+
+    /// `R_AARCH64_TLSLD_MOVW_DTPREL_G1`.
+    TlsldMovwDtprelG1,
+
+    /// `R_AARCH64_TLSLD_MOVW_DTPREL_G1_NC`.
+    TlsldMovwDtprelG1Nc,
+
+    /// `R_AARCH64_TLSLD_MOVW_DTPREL_G0`.
+    TlsldMovwDtprelG0,
+
+    /// `R_AARCH64_TLSLD_MOVW_DTPREL_G0_NC`.
+    TlsldMovwDtprelG0Nc,
+
+    /// `R_AARCH64_TLSLD_ADD_DTPREL_HI12`.
+    TlsldAddDtprelHi12,
+
+    /// `R_AARCH64_TLSLD_ADD_DTPREL_LO12`.
+    TlsldAddDtprelLo12,
+
+    /// `R_AARCH64_TLSLD_ADD_DTPREL_LO12_NC`.
+    TlsldAddDtprelLo12Nc,
+
+    /// `R_AARCH64_TLSLD_LDST8_DTPREL_LO12`.
+    TlsldLdst8DtprelLo12,
+
+    /// `R_AARCH64_TLSLD_LDST8_DTPREL_LO12_NC`.
+    TlsldLdst8DtprelLo12Nc,
+
+    /// `R_AARCH64_TLSLD_LDST16_DTPREL_LO12`.
+    TlsldLdst16DtprelLo12,
+
+    /// `R_AARCH64_TLSLD_LDST16_DTPREL_LO12_NC`.
+    TlsldLdst16DtprelLo12Nc,
+
+    /// `R_AARCH64_TLSLD_LDST32_DTPREL_LO12`.
+    TlsldLdst32DtprelLo12,
+
+    /// `R_AARCH64_TLSLD_LDST32_DTPREL_LO12_NC`.
+    TlsldLdst32DtprelLo12Nc,
+
+    /// `R_AARCH64_TLSLD_LDST64_DTPREL_LO12`.
+    TlsldLdst64DtprelLo12,
+
+    /// `R_AARCH64_TLSLD_LDST64_DTPREL_LO12_NC`.
+    TlsldLdst64DtprelLo12Nc,
+
+    /// `R_AARCH64_TLSIE_MOVW_GOTTPREL_G1`.
+    TlsieMovwGottprelG1,
+
+    /// `R_AARCH64_TLSIE_MOVW_GOTTPREL_G0_NC`.
+    TlsieMovwGottprelG0Nc,
+
+    /// `R_AARCH64_TLSIE_ADR_GOTTPREL_PAGE21`.
+    TlsieAdrGottprelPage21,
+
+    /// `R_AARCH64_TLSIE_LD64_GOTTPREL_LO12_NC`.
+    TlsieLd64GottprelLo12Nc,
+
+    /// `R_AARCH64_TLSIE_LD_GOTTPREL_PREL19`.
+    TlsieLdGottprelPrel19,
+
+    /// `R_AARCH64_TLSLE_MOVW_TPREL_G2`.
+    TlsleMovwTprelG2,
+
+    /// `R_AARCH64_TLSLE_MOVW_TPREL_G1`.
+    TlsleMovwTprelG1,
+
+    /// `R_AARCH64_TLSLE_MOVW_TPREL_G1_NC`.
+    TlsleMovwTprelG1Nc,
+
+    /// `R_AARCH64_TLSLE_MOVW_TPREL_G0`.
+    TlsleMovwTprelG0,
+
+    /// `R_AARCH64_TLSLE_MOVW_TPREL_G0_NC`.
+    TlsleMovwTprelG0Nc,
+
+    /// `R_AARCH64_TLSLE_ADD_TPREL_HI12`.
+    TlsleAddTprelHi12,
+
+    /// `R_AARCH64_TLSLE_ADD_TPREL_LO12`.
+    TlsleAddTprelLo12,
+
+    /// `R_AARCH64_TLSLE_ADD_TPREL_LO12_NC`.
+    TlsleAddTprelLo12Nc,
+
+    /// `R_AARCH64_TLSLE_LDST8_TPREL_LO12`.
+    TlsleLdst8TprelLo12,
+
+    /// `R_AARCH64_TLSLE_LDST8_TPREL_LO12_NC`.
+    TlsleLdst8TprelLo12Nc,
+
+    /// `R_AARCH64_TLSLE_LDST16_TPREL_LO12`.
+    TlsleLdst16TprelLo12,
+
+    /// `R_AARCH64_TLSLE_LDST16_TPREL_LO12_NC`.
+    TlsleLdst16TprelLo12Nc,
+
+    /// `R_AARCH64_TLSLE_LDST32_TPREL_LO12`.
+    TlsleLdst32TprelLo12,
+
+    /// `R_AARCH64_TLSLE_LDST32_TPREL_LO12_NC`.
+    TlsleLdst32TprelLo12Nc,
+
+    /// `R_AARCH64_TLSLE_LDST64_TPREL_LO12`.
+    TlsleLdst64TprelLo12,
+
+    /// `R_AARCH64_TLSLE_LDST64_TPREL_LO12_NC`.
+    TlsleLdst64TprelLo12Nc,
+
+    /// `R_AARCH64_TLSDESC_LD_PREL19`.
+    TlsdescLdPrel19,
+
+    /// `R_AARCH64_TLSDESC_ADR_PREL21`.
+    TlsdescAdrPrel21,
+
+    /// `R_AARCH64_TLSDESC_ADR_PAGE21`.
+    TlsdescAdrPage21,
+
+    /// `R_AARCH64_TLSDESC_LD64_LO12`.
+    TlsdescLd64Lo12,
+
+    /// `R_AARCH64_TLSDESC_ADD_LO12`.
+    TlsdescAddLo12,
+
+    /// `R_AARCH64_TLSDESC_OFF_G1`.
+    TlsdescOffG1,
+
+    /// `R_AARCH64_TLSDESC_OFF_G0_NC`.
+    TlsdescOffG0Nc,
+
+    /// `R_AARCH64_TLSDESC_LDR`.
+    TlsdescLdr,
+
+    /// `R_AARCH64_TLSDESC_ADD`.
+    TlsdescAdd,
+
+    /// `R_AARCH64_TLSDESC_CALL`.
+    TlsdescCall,
+
+    /// `R_AARCH64_TLSLE_LDST128_TPREL_LO12`.
+    TlsleLdst128TprelLo12,
+
+    /// `R_AARCH64_TLSLE_LDST128_TPREL_LO12_NC`.
+    TlsleLdst128TprelLo12Nc,
+
+    /// `R_AARCH64_TLSLD_LDST128_DTPREL_LO12`.
+    TlsldLdst128DtprelLo12,
+
+    /// `R_AARCH64_TLSLD_LDST128_DTPREL_LO12_NC`.
+    TlsldLdst128DtprelLo12Nc,
+
+    /// `R_AARCH64_COPY`.
+    Copy,
+
+    /// `R_AARCH64_GLOB_DAT`.
+    GlobDat,
+
+    /// `R_AARCH64_JUMP_SLOT`.
+    JumpSlot,
+
+    /// `R_AARCH64_RELATIVE`.
+    Relative,
+
+    /// `R_AARCH64_TLS_DTPMOD`.
+    TlsDtpmod,
+
+    /// `R_AARCH64_TLS_DTPREL`.
+    TlsDtprel,
+
+    /// `R_AARCH64_TLS_TPREL`.
+    TlsTprel,
+
+    /// `R_AARCH64_TLSDESC`.
+    Tlsdesc,
+
+    /// `R_AARCH64_IRELATIVE`.
+    Irelative,
+
+    /// Unknown relocation type.
+    Unknown(u32),
+}
+
+impl core::convert::From<u32> for AArch64 {
+    fn from(t: u32) -> Self {
+        match t {
+            0    => AArch64::None,
+            257  => AArch64::Abs64,
+            258  => AArch64::Abs32,
+            259  => AArch64::Abs16,
+            260  => AArch64::Prel64,
+            261  => AArch64::Prel32,
+            262  => AArch64::Prel16,
+            263  => AArch64::MovwUabsG0,
+            264  => AArch64::MovwUabsG0Nc,
+            265  => AArch64::MovwUabsG1,
+            266  => AArch64::MovwUabsG1Nc,
+            267  => AArch64::MovwUabsG2,
+            268  => AArch64::MovwUabsG2Nc,
+            269  => AArch64::MovwUabsG3,
+            270  => AArch64::MovwSabsG0,
+            271  => AArch64::MovwSabsG1,
+            272  => AArch64::MovwSabsG2,
+            273  => AArch64::LdPrelLo19,
+            274  => AArch64::AdrPrelLo21,
+            275  => AArch64::AdrPrelPgHi21,
+            276  => AArch64::AdrPrelPgHi21Nc,
+            277  => AArch64::AddAbsLo12Nc,
+            278  => AArch64::Ldst8AbsLo12Nc,
+            279  => AArch64::Tstbr14,
+            280  => AArch64::Condbr19,
+            282  => AArch64::Jump26,
+            283  => AArch64::Call26,
+            284  => AArch64::Ldst16AbsLo12Nc,
+            285  => AArch64::Ldst32AbsLo12Nc,
+            286  => AArch64::Ldst64AbsLo12Nc,
+            287  => AArch64::MovwPrelG0,
+            288  => AArch64::MovwPrelG0Nc,
+            289  => AArch64::MovwPrelG1,
+            290  => AArch64::MovwPrelG1Nc,
+            291  => AArch64::MovwPrelG2,
+            292  => AArch64::MovwPrelG2Nc,
+            293  => AArch64::MovwPrelG3,
+            299  => AArch64::Ldst128AbsLo12Nc,
+            300  => AArch64::MovwGotoffG0,
+            301  => AArch64::MovwGotoffG0Nc,
+            302  => AArch64::MovwGotoffG1,
+            303  => AArch64::MovwGotoffG1Nc,
+            304  => AArch64::MovwGotoffG2,
+            305  => AArch64::MovwGotoffG2Nc,
+            306  => AArch64::MovwGotoffG3,
+            307  => AArch64::Gotrel64,
+            308  => AArch64::Gotrel32,
+            309  => AArch64::GotLdPrel19,
+            310  => AArch64::Ld64GotoffLo15,
+            311  => AArch64::AdrGotPage,
+            312  => AArch64::Ld64GotLo12Nc,
+            313  => AArch64::Ld64GotpageLo15,
+            512  => AArch64::TlsgdAdrPrel21,
+            513  => AArch64::TlsgdAdrPage21,
+            514  => AArch64::TlsgdAddLo12Nc,
+            515  => AArch64::TlsgdMovwG1,
+            516  => AArch64::TlsgdMovwG0Nc,
+            517  => AArch64::TlsldAdrPrel21,
+            518  => AArch64::TlsldAdrPage21,
+            519  => AArch64::TlsldAddLo12Nc,
+            520  => AArch64::TlsldMovwG1,
+            521  => AArch64::TlsldMovwG0Nc,
+            522  => AArch64::TlsldLdPrel19,
+            523  => AArch64::TlsldMovwDtprelG2,
+            524  => AArch64::TlsldMovwDtprelG1,
+            525  => AArch64::TlsldMovwDtprelG1Nc,
+            526  => AArch64::TlsldMovwDtprelG0,
+            527  => AArch64::TlsldMovwDtprelG0Nc,
+            528  => AArch64::TlsldAddDtprelHi12,
+            529  => AArch64::TlsldAddDtprelLo12,
+            530  => AArch64::TlsldAddDtprelLo12Nc,
+            531  => AArch64::TlsldLdst8DtprelLo12,
+            532  => AArch64::TlsldLdst8DtprelLo12Nc,
+            533  => AArch64::TlsldLdst16DtprelLo12,
+            534  => AArch64::TlsldLdst16DtprelLo12Nc,
+            535  => AArch64::TlsldLdst32DtprelLo12,
+            536  => AArch64::TlsldLdst32DtprelLo12Nc,
+            537  => AArch64::TlsldLdst64DtprelLo12,
+            538  => AArch64::TlsldLdst64DtprelLo12Nc,
+            539  => AArch64::TlsieMovwGottprelG1,
+            540  => AArch64::TlsieMovwGottprelG0Nc,
+            541  => AArch64::TlsieAdrGottprelPage21,
+            542  => AArch64::TlsieLd64GottprelLo12Nc,
+            543  => AArch64::TlsieLdGottprelPrel19,
+            544  => AArch64::TlsleMovwTprelG2,
+            545  => AArch64::TlsleMovwTprelG1,
+            546  => AArch64::TlsleMovwTprelG1Nc,
+            547  => AArch64::TlsleMovwTprelG0,
+            548  => AArch64::TlsleMovwTprelG0Nc,
+            549  => AArch64::TlsleAddTprelHi12,
+            550  => AArch64::TlsleAddTprelLo12,
+            551  => AArch64::TlsleAddTprelLo12Nc,
+            552  => AArch64::TlsleLdst8TprelLo12,
+            553  => AArch64::TlsleLdst8TprelLo12Nc,
+            554  => AArch64::TlsleLdst16TprelLo12,
+            555  => AArch64::TlsleLdst16TprelLo12Nc,
+            556  => AArch64::TlsleLdst32TprelLo12,
+            557  => AArch64::TlsleLdst32TprelLo12Nc,
+            558  => AArch64::TlsleLdst64TprelLo12,
+            559  => AArch64::TlsleLdst64TprelLo12Nc,
+            560  => AArch64::TlsdescLdPrel19,
+            561  => AArch64::TlsdescAdrPrel21,
+            562  => AArch64::TlsdescAdrPage21,
+            563  => AArch64::TlsdescLd64Lo12,
+            564  => AArch64::TlsdescAddLo12,
+            565  => AArch64::TlsdescOffG1,
+            566  => AArch64::TlsdescOffG0Nc,
+            567  => AArch64::TlsdescLdr,
+            568  => AArch64::TlsdescAdd,
+            569  => AArch64::TlsdescCall,
+            570  => AArch64::TlsleLdst128TprelLo12,
+            571  => AArch64::TlsleLdst128TprelLo12Nc,
+            572  => AArch64::TlsldLdst128DtprelLo12,
+            573  => AArch64::TlsldLdst128DtprelLo12Nc,
+            1024 => AArch64::Copy,
+            1025 => AArch64::GlobDat,
+            1026 => AArch64::JumpSlot,
+            1027 => AArch64::Relative,
+            1028 => AArch64::TlsDtpmod,
+            1029 => AArch64::TlsDtprel,
+            1030 => AArch64::TlsTprel,
+            1031 => AArch64::Tlsdesc,
+            1032 => AArch64::Irelative,
+
+            _ => AArch64::Unknown(t),
+        }
+    }
+}
+
+impl core::convert::From<AArch64> for u32 {
+    fn from(t: AArch64) -> u32 {
+        match t {
+            AArch64::None                     => 0,
+            AArch64::Abs64                    => 257,
+            AArch64::Abs32                    => 258,
+            AArch64::Abs16                    => 259,
+            AArch64::Prel64                   => 260,
+            AArch64::Prel32                   => 261,
+            AArch64::Prel16                   => 262,
+            AArch64::MovwUabsG0               => 263,
+            AArch64::MovwUabsG0Nc             => 264,
+            AArch64::MovwUabsG1               => 265,
+            AArch64::MovwUabsG1Nc             => 266,
+            AArch64::MovwUabsG2               => 267,
+            AArch64::MovwUabsG2Nc             => 268,
+            AArch64::MovwUabsG3               => 269,
+            AArch64::MovwSabsG0               => 270,
+            AArch64::MovwSabsG1               => 271,
+            AArch64::MovwSabsG2               => 272,
+            AArch64::LdPrelLo19               => 273,
+            AArch64::AdrPrelLo21              => 274,
+            AArch64::AdrPrelPgHi21            => 275,
+            AArch64::AdrPrelPgHi21Nc          => 276,
+            AArch64::AddAbsLo12Nc             => 277,
+            AArch64::Ldst8AbsLo12Nc           => 278,
+            AArch64::Tstbr14                  => 279,
+            AArch64::Condbr19                 => 280,
+            AArch64::Jump26                   => 282,
+            AArch64::Call26                   => 283,
+            AArch64::Ldst16AbsLo12Nc          => 284,
+            AArch64::Ldst32AbsLo12Nc          => 285,
+            AArch64::Ldst64AbsLo12Nc          => 286,
+            AArch64::MovwPrelG0               => 287,
+            AArch64::MovwPrelG0Nc             => 288,
+            AArch64::MovwPrelG1               => 289,
+            AArch64::MovwPrelG1Nc             => 290,
+            AArch64::MovwPrelG2               => 291,
+            AArch64::MovwPrelG2Nc             => 292,
+            AArch64::MovwPrelG3               => 293,
+            AArch64::Ldst128AbsLo12Nc         => 299,
+            AArch64::MovwGotoffG0             => 300,
+            AArch64::MovwGotoffG0Nc           => 301,
+            AArch64::MovwGotoffG1             => 302,
+            AArch64::MovwGotoffG1Nc           => 303,
+            AArch64::MovwGotoffG2             => 304,
+            AArch64::MovwGotoffG2Nc           => 305,
+            AArch64::MovwGotoffG3             => 306,
+            AArch64::Gotrel64                 => 307,
+            AArch64::Gotrel32                 => 308,
+            AArch64::GotLdPrel19              => 309,
+            AArch64::Ld64GotoffLo15           => 310,
+            AArch64::AdrGotPage               => 311,
+            AArch64::Ld64GotLo12Nc            => 312,
+            AArch64::Ld64GotpageLo15          => 313,
+            AArch64::TlsgdAdrPrel21           => 512,
+            AArch64::TlsgdAdrPage21           => 513,
+            AArch64::TlsgdAddLo12Nc           => 514,
+            AArch64::TlsgdMovwG1              => 515,
+            AArch64::TlsgdMovwG0Nc            => 516,
+            AArch64::TlsldAdrPrel21           => 517,
+            AArch64::TlsldAdrPage21           => 518,
+            AArch64::TlsldAddLo12Nc           => 519,
+            AArch64::TlsldMovwG1              => 520,
+            AArch64::TlsldMovwG0Nc            => 521,
+            AArch64::TlsldLdPrel19            => 522,
+            AArch64::TlsldMovwDtprelG2        => 523,
+            AArch64::TlsldMovwDtprelG1        => 524,
+            AArch64::TlsldMovwDtprelG1Nc      => 525,
+            AArch64::TlsldMovwDtprelG0        => 526,
+            AArch64::TlsldMovwDtprelG0Nc      => 527,
+            AArch64::TlsldAddDtprelHi12       => 528,
+            AArch64::TlsldAddDtprelLo12       => 529,
+            AArch64::TlsldAddDtprelLo12Nc     => 530,
+            AArch64::TlsldLdst8DtprelLo12     => 531,
+            AArch64::TlsldLdst8DtprelLo12Nc   => 532,
+            AArch64::TlsldLdst16DtprelLo12    => 533,
+            AArch64::TlsldLdst16DtprelLo12Nc  => 534,
+            AArch64::TlsldLdst32DtprelLo12    => 535,
+            AArch64::TlsldLdst32DtprelLo12Nc  => 536,
+            AArch64::TlsldLdst64DtprelLo12    => 537,
+            AArch64::TlsldLdst64DtprelLo12Nc  => 538,
+            AArch64::TlsieMovwGottprelG1      => 539,
+            AArch64::TlsieMovwGottprelG0Nc    => 540,
+            AArch64::TlsieAdrGottprelPage21   => 541,
+            AArch64::TlsieLd64GottprelLo12Nc  => 542,
+            AArch64::TlsieLdGottprelPrel19    => 543,
+            AArch64::TlsleMovwTprelG2         => 544,
+            AArch64::TlsleMovwTprelG1         => 545,
+            AArch64::TlsleMovwTprelG1Nc       => 546,
+            AArch64::TlsleMovwTprelG0         => 547,
+            AArch64::TlsleMovwTprelG0Nc       => 548,
+            AArch64::TlsleAddTprelHi12        => 549,
+            AArch64::TlsleAddTprelLo12        => 550,
+            AArch64::TlsleAddTprelLo12Nc      => 551,
+            AArch64::TlsleLdst8TprelLo12      => 552,
+            AArch64::TlsleLdst8TprelLo12Nc    => 553,
+            AArch64::TlsleLdst16TprelLo12     => 554,
+            AArch64::TlsleLdst16TprelLo12Nc   => 555,
+            AArch64::TlsleLdst32TprelLo12     => 556,
+            AArch64::TlsleLdst32TprelLo12Nc   => 557,
+            AArch64::TlsleLdst64TprelLo12     => 558,
+            AArch64::TlsleLdst64TprelLo12Nc   => 559,
+            AArch64::TlsdescLdPrel19          => 560,
+            AArch64::TlsdescAdrPrel21         => 561,
+            AArch64::TlsdescAdrPage21         => 562,
+            AArch64::TlsdescLd64Lo12          => 563,
+            AArch64::TlsdescAddLo12           => 564,
+            AArch64::TlsdescOffG1             => 565,
+            AArch64::TlsdescOffG0Nc           => 566,
+            AArch64::TlsdescLdr               => 567,
+            AArch64::TlsdescAdd               => 568,
+            AArch64::TlsdescCall              => 569,
+            AArch64::TlsleLdst128TprelLo12    => 570,
+            AArch64::TlsleLdst128TprelLo12Nc  => 571,
+            AArch64::TlsldLdst128DtprelLo12   => 572,
+            AArch64::TlsldLdst128DtprelLo12Nc => 573,
+            AArch64::Copy                     => 1024,
+            AArch64::GlobDat                  => 1025,
+            AArch64::JumpSlot                 => 1026,
+            AArch64::Relative                 => 1027,
+            AArch64::TlsDtpmod                => 1028,
+            AArch64::TlsDtprel                => 1029,
+            AArch64::TlsTprel                 => 1030,
+            AArch64::Tlsdesc                  => 1031,
+            AArch64::Irelative                => 1032,
+
+            AArch64::Unknown(t) => t,
+        }
+    }
+}
+
+impl core::fmt::Display for AArch64 {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        let s = match *self {
+            AArch64::None                     => "R_AARCH64_NONE",
+            AArch64::Abs64                    => "R_AARCH64_ABS64",
+            AArch64::Abs32                    => "R_AARCH64_ABS32",
+            AArch64::Abs16                    => "R_AARCH64_ABS16",
+            AArch64::Prel64                   => "R_AARCH64_PREL64",
+            AArch64::Prel32                   => "R_AARCH64_PREL32",
+            AArch64::Prel16                   => "R_AARCH64_PREL16",
+            AArch64::MovwUabsG0               => "R_AARCH64_MOVW_UABS_G0",
+            AArch64::MovwUabsG0Nc             => "R_AARCH64_MOVW_UABS_G0_NC",
+            AArch64::MovwUabsG1               => "R_AARCH64_MOVW_UABS_G1",
+            AArch64::MovwUabsG1Nc             => "R_AARCH64_MOVW_UABS_G1_NC",
+            AArch64::MovwUabsG2               => "R_AARCH64_MOVW_UABS_G2",
+            AArch64::MovwUabsG2Nc             => "R_AARCH64_MOVW_UABS_G2_NC",
+            AArch64::MovwUabsG3               => "R_AARCH64_MOVW_UABS_G3",
+            AArch64::MovwSabsG0               => "R_AARCH64_MOVW_SABS_G0",
+            AArch64::MovwSabsG1               => "R_AARCH64_MOVW_SABS_G1",
+            AArch64::MovwSabsG2               => "R_AARCH64_MOVW_SABS_G2",
+            AArch64::LdPrelLo19               => "R_AARCH64_LD_PREL_LO19",
+            AArch64::AdrPrelLo21              => "R_AARCH64_ADR_PREL_LO21",
+            AArch64::AdrPrelPgHi21            => "R_AARCH64_ADR_PREL_PG_HI21",
+            AArch64::AdrPrelPgHi21Nc          => "R_AARCH64_ADR_PREL_PG_HI21_NC",
+            AArch64::AddAbsLo12Nc             => "R_AARCH64_ADD_ABS_LO12_NC",
+            AArch64::Ldst8AbsLo12Nc           => "R_AARCH64_LDST8_ABS_LO12_NC",
+            AArch64::Tstbr14                  => "R_AARCH64_TSTBR14",
+            AArch64::Condbr19                 => "R_AARCH64_CONDBR19",
+            AArch64::Jump26                   => "R_AARCH64_JUMP26",
+            AArch64::Call26                   => "R_AARCH64_CALL26",
+            AArch64::Ldst16AbsLo12Nc          => "R_AARCH64_LDST16_ABS_LO12_NC",
+            AArch64::Ldst32AbsLo12Nc          => "R_AARCH64_LDST32_ABS_LO12_NC",
+            AArch64::Ldst64AbsLo12Nc          => "R_AARCH64_LDST64_ABS_LO12_NC",
+            AArch64::MovwPrelG0               => "R_AARCH64_MOVW_PREL_G0",
+            AArch64::MovwPrelG0Nc             => "R_AARCH64_MOVW_PREL_G0_NC",
+            AArch64::MovwPrelG1               => "R_AARCH64_MOVW_PREL_G1",
+            AArch64::MovwPrelG1Nc             => "R_AARCH64_MOVW_PREL_G1_NC",
+            AArch64::MovwPrelG2               => "R_AARCH64_MOVW_PREL_G2",
+            AArch64::MovwPrelG2Nc             => "R_AARCH64_MOVW_PREL_G2_NC",
+            AArch64::MovwPrelG3               => "R_AARCH64_MOVW_PREL_G3",
+            AArch64::Ldst128AbsLo12Nc         => "R_AARCH64_LDST128_ABS_LO12_NC",
+            AArch64::MovwGotoffG0             => "R_AARCH64_MOVW_GOTOFF_G0",
+            AArch64::MovwGotoffG0Nc           => "R_AARCH64_MOVW_GOTOFF_G0_NC",
+            AArch64::MovwGotoffG1             => "R_AARCH64_MOVW_GOTOFF_G1",
+            AArch64::MovwGotoffG1Nc           => "R_AARCH64_MOVW_GOTOFF_G1_NC",
+            AArch64::MovwGotoffG2             => "R_AARCH64_MOVW_GOTOFF_G2",
+            AArch64::MovwGotoffG2Nc           => "R_AARCH64_MOVW_GOTOFF_G2_NC",
+            AArch64::MovwGotoffG3             => "R_AARCH64_MOVW_GOTOFF_G3",
+            AArch64::Gotrel64                 => "R_AARCH64_GOTREL64",
+            AArch64::Gotrel32                 => "R_AARCH64_GOTREL32",
+            AArch64::GotLdPrel19              => "R_AARCH64_GOT_LD_PREL19",
+            AArch64::Ld64GotoffLo15           => "R_AARCH64_LD64_GOTOFF_LO15",
+            AArch64::AdrGotPage               => "R_AARCH64_ADR_GOT_PAGE",
+            AArch64::Ld64GotLo12Nc            => "R_AARCH64_LD64_GOT_LO12_NC",
+            AArch64::Ld64GotpageLo15          => "R_AARCH64_LD64_GOTPAGE_LO15",
+            AArch64::TlsgdAdrPrel21           => "R_AARCH64_TLSGD_ADR_PREL21",
+            AArch64::TlsgdAdrPage21           => "R_AARCH64_TLSGD_ADR_PAGE21",
+            AArch64::TlsgdAddLo12Nc           => "R_AARCH64_TLSGD_ADD_LO12_NC",
+            AArch64::TlsgdMovwG1              => "R_AARCH64_TLSGD_MOVW_G1",
+            AArch64::TlsgdMovwG0Nc            => "R_AARCH64_TLSGD_MOVW_G0_NC",
+            AArch64::TlsldAdrPrel21           => "R_AARCH64_TLSLD_ADR_PREL21",
+            AArch64::TlsldAdrPage21           => "R_AARCH64_TLSLD_ADR_PAGE21",
+            AArch64::TlsldAddLo12Nc           => "R_AARCH64_TLSLD_ADD_LO12_NC",
+            AArch64::TlsldMovwG1              => "R_AARCH64_TLSLD_MOVW_G1",
+            AArch64::TlsldMovwG0Nc            => "R_AARCH64_TLSLD_MOVW_G0_NC",
+            AArch64::TlsldLdPrel19            => "R_AARCH64_TLSLD_LD_PREL19",
+            AArch64::TlsldMovwDtprelG2        => "R_AARCH64_TLSLD_MOVW_DTPREL_G2",
+            AArch64::TlsldMovwDtprelG1        => "R_AARCH64_TLSLD_MOVW_DTPREL_G1",
+            AArch64::TlsldMovwDtprelG1Nc      => "R_AARCH64_TLSLD_MOVW_DTPREL_G1_NC",
+            AArch64::TlsldMovwDtprelG0        => "R_AARCH64_TLSLD_MOVW_DTPREL_G0",
+            AArch64::TlsldMovwDtprelG0Nc      => "R_AARCH64_TLSLD_MOVW_DTPREL_G0_NC",
+            AArch64::TlsldAddDtprelHi12       => "R_AARCH64_TLSLD_ADD_DTPREL_HI12",
+            AArch64::TlsldAddDtprelLo12       => "R_AARCH64_TLSLD_ADD_DTPREL_LO12",
+            AArch64::TlsldAddDtprelLo12Nc     => "R_AARCH64_TLSLD_ADD_DTPREL_LO12_NC",
+            AArch64::TlsldLdst8DtprelLo12     => "R_AARCH64_TLSLD_LDST8_DTPREL_LO12",
+            AArch64::TlsldLdst8DtprelLo12Nc   => "R_AARCH64_TLSLD_LDST8_DTPREL_LO12_NC",
+            AArch64::TlsldLdst16DtprelLo12    => "R_AARCH64_TLSLD_LDST16_DTPREL_LO12",
+            AArch64::TlsldLdst16DtprelLo12Nc  => "R_AARCH64_TLSLD_LDST16_DTPREL_LO12_NC",
+            AArch64::TlsldLdst32DtprelLo12    => "R_AARCH64_TLSLD_LDST32_DTPREL_LO12",
+            AArch64::TlsldLdst32DtprelLo12Nc  => "R_AARCH64_TLSLD_LDST32_DTPREL_LO12_NC",
+            AArch64::TlsldLdst64DtprelLo12    => "R_AARCH64_TLSLD_LDST64_DTPREL_LO12",
+            AArch64::TlsldLdst64DtprelLo12Nc  => "R_AARCH64_TLSLD_LDST64_DTPREL_LO12_NC",
+            AArch64::TlsieMovwGottprelG1      => "R_AARCH64_TLSIE_MOVW_GOTTPREL_G1",
+            AArch64::TlsieMovwGottprelG0Nc    => "R_AARCH64_TLSIE_MOVW_GOTTPREL_G0_NC",
+            AArch64::TlsieAdrGottprelPage21   => "R_AARCH64_TLSIE_ADR_GOTTPREL_PAGE21",
+            AArch64::TlsieLd64GottprelLo12Nc  => "R_AARCH64_TLSIE_LD64_GOTTPREL_LO12_NC",
+            AArch64::TlsieLdGottprelPrel19    => "R_AARCH64_TLSIE_LD_GOTTPREL_PREL19",
+            AArch64::TlsleMovwTprelG2         => "R_AARCH64_TLSLE_MOVW_TPREL_G2",
+            AArch64::TlsleMovwTprelG1         => "R_AARCH64_TLSLE_MOVW_TPREL_G1",
+            AArch64::TlsleMovwTprelG1Nc       => "R_AARCH64_TLSLE_MOVW_TPREL_G1_NC",
+            AArch64::TlsleMovwTprelG0         => "R_AARCH64_TLSLE_MOVW_TPREL_G0",
+            AArch64::TlsleMovwTprelG0Nc       => "R_AARCH64_TLSLE_MOVW_TPREL_G0_NC",
+            AArch64::TlsleAddTprelHi12        => "R_AARCH64_TLSLE_ADD_TPREL_HI12",
+            AArch64::TlsleAddTprelLo12        => "R_AARCH64_TLSLE_ADD_TPREL_LO12",
+            AArch64::TlsleAddTprelLo12Nc      => "R_AARCH64_TLSLE_ADD_TPREL_LO12_NC",
+            AArch64::TlsleLdst8TprelLo12      => "R_AARCH64_TLSLE_LDST8_TPREL_LO12",
+            AArch64::TlsleLdst8TprelLo12Nc    => "R_AARCH64_TLSLE_LDST8_TPREL_LO12_NC",
+            AArch64::TlsleLdst16TprelLo12     => "R_AARCH64_TLSLE_LDST16_TPREL_LO12",
+            AArch64::TlsleLdst16TprelLo12Nc   => "R_AARCH64_TLSLE_LDST16_TPREL_LO12_NC",
+            AArch64::TlsleLdst32TprelLo12     => "R_AARCH64_TLSLE_LDST32_TPREL_LO12",
+            AArch64::TlsleLdst32TprelLo12Nc   => "R_AARCH64_TLSLE_LDST32_TPREL_LO12_NC",
+            AArch64::TlsleLdst64TprelLo12     => "R_AARCH64_TLSLE_LDST64_TPREL_LO12",
+            AArch64::TlsleLdst64TprelLo12Nc   => "R_AARCH64_TLSLE_LDST64_TPREL_LO12_NC",
+            AArch64::TlsdescLdPrel19          => "R_AARCH64_TLSDESC_LD_PREL19",
+            AArch64::TlsdescAdrPrel21         => "R_AARCH64_TLSDESC_ADR_PREL21",
+            AArch64::TlsdescAdrPage21         => "R_AARCH64_TLSDESC_ADR_PAGE21",
+            AArch64::TlsdescLd64Lo12          => "R_AARCH64_TLSDESC_LD64_LO12",
+            AArch64::TlsdescAddLo12           => "R_AARCH64_TLSDESC_ADD_LO12",
+            AArch64::TlsdescOffG1             => "R_AARCH64_TLSDESC_OFF_G1",
+            AArch64::TlsdescOffG0Nc           => "R_AARCH64_TLSDESC_OFF_G0_NC",
+            AArch64::TlsdescLdr               => "R_AARCH64_TLSDESC_LDR",
+            AArch64::TlsdescAdd               => "R_AARCH64_TLSDESC_ADD",
+            AArch64::TlsdescCall              => "R_AARCH64_TLSDESC_CALL",
+            AArch64::TlsleLdst128TprelLo12    => "R_AARCH64_TLSLE_LDST128_TPREL_LO12",
+            AArch64::TlsleLdst128TprelLo12Nc  => "R_AARCH64_TLSLE_LDST128_TPREL_LO12_NC",
+            AArch64::TlsldLdst128DtprelLo12   => "R_AARCH64_TLSLD_LDST128_DTPREL_LO12",
+            AArch64::TlsldLdst128DtprelLo12Nc => "R_AARCH64_TLSLD_LDST128_DTPREL_LO12_NC",
+            AArch64::Copy                     => "R_AARCH64_COPY",
+            AArch64::GlobDat                  => "R_AARCH64_GLOB_DAT",
+            AArch64::JumpSlot                 => "R_AARCH64_JUMP_SLOT",
+            AArch64::Relative                 => "R_AARCH64_RELATIVE",
+            AArch64::TlsDtpmod                => "R_AARCH64_TLS_DTPMOD",
+            AArch64::TlsDtprel                => "R_AARCH64_TLS_DTPREL",
+            AArch64::TlsTprel                 => "R_AARCH64_TLS_TPREL",
+            AArch64::Tlsdesc                  => "R_AARCH64_TLSDESC",
+            AArch64::Irelative                => "R_AARCH64_IRELATIVE",
+
+            AArch64::Unknown(t) => return write!(f, "Unknown ({})", t),
+        };
+
+        write!(f, "{}", s)
+    }
+}
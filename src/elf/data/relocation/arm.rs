@@ -0,0 +1,734 @@
+//! Arm (Aarch32) relocation types.
+
+
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ARM {
+    /// `R_ARM_NONE`.
+    None,
+
+    /// `R_ARM_PC24`.
+    Pc24,
+
+    /// `R_ARM_ABS32`.
+    Abs32,
+
+    /// `R_ARM_REL32`.
+    Rel32,
+
+    /// `R_ARM_LDR_PC_G0`.
+    LdrPcG0,
+
+    /// `R_ARM_ABS16`.
+    Abs16,
+
+    /// `R_ARM_ABS12`.
+    Abs12,
+
+    /// `R_ARM_THM_ABS5`.
+    ThmAbs5,
+
+    /// `R_ARM_ABS8`.
+    Abs8,
+
+    /// `R_ARM_SBREL32`.
+    Sbrel32,
+
+    /// `R_ARM_THM_CALL`.
+    ThmCall,
+
+    /// `R_ARM_THM_PC8`.
+    ThmPc8,
+
+    /// `R_ARM_BREL_ADJ`.
+    BrelAdj,
+
+    /// `R_ARM_TLS_DESC`.
+    TlsDesc,
+
+    /// `R_ARM_THM_SWI8`.
+    ThmSwi8,
+
+    /// `R_ARM_XPC25`.
+    Xpc25,
+
+    /// `R_ARM_THM_XPC22`.
+    ThmXpc22,
+
+    /// `R_ARM_TLS_DTPMOD32`.
+    TlsDtpmod32,
+
+    /// `R_ARM_TLS_DTPOFF32`.
+    TlsDtpoff32,
+
+    /// `R_ARM_TLS_TPOFF32`.
+    TlsTpoff32,
+
+    /// `R_ARM_COPY`.
+    Copy,
+
+    /// `R_ARM_GLOB_DAT`.
+    GlobDat,
+
+    /// `R_ARM_JUMP_SLOT`.
+    JumpSlot,
+
+    /// `R_ARM_RELATIVE`.
+    Relative,
+
+    /// `R_ARM_GOTOFF32`.
+    Gotoff32,
+
+    /// `R_ARM_BASE_PREL`.
+    BasePrel,
+
+    /// `R_ARM_GOT_BREL`.
+    GotBrel,
+
+    /// `R_ARM_PLT32`.
+    Plt32,
+
+    /// `R_ARM_CALL`.
+    Call,
+
+    /// `R_ARM_JUMP24`.
+    Jump24,
+
+    /// `R_ARM_THM_JUMP24`.
+    ThmJump24,
+
+    /// `R_ARM_BASE_ABS`.
+    BaseAbs,
+
+    /// `R_ARM_ALU_PCREL_7_0`.
+    AluPcrel70,
+
+    /// `R_ARM_ALU_PCREL_15_8`.
+    AluPcrel158,
+
+    /// `R_ARM_ALU_PCREL_23_15`.
+    AluPcrel2315,
+
+    /// `R_ARM_LDR_SBREL_11_0`.
+    LdrSbrel110,
+
+    /// `R_ARM_ALU_SBREL_19_12`.
+    AluSbrel1912,
+
+    /// `R_ARM_ALU_SBREL_27_20`.
+    AluSbrel2720,
+
+    /// `R_ARM_TARGET1`.
+    Target1,
+
+    /// `R_ARM_SBREL31`.
+    Sbrel31,
+
+    /// `R_ARM_V4BX`.
+    V4bx,
+
+    /// `R_ARM_TARGET2`.
+    Target2,
+
+    /// `R_ARM_PREL31`.
+    Prel31,
+
+    /// `R_ARM_MOVW_ABS_NC`.
+    MovwAbsNc,
+
+    /// `R_ARM_MOVT_ABS`.
+    MovtAbs,
+
+    /// `R_ARM_MOVW_PREL_NC`.
+    MovwPrelNc,
+
+    /// `R_ARM_MOVT_PREL`.
+    MovtPrel,
+
+    /// `R_ARM_THM_MOVW_ABS_NC`.
+    ThmMovwAbsNc,
+
+    /// `R_ARM_THM_MOVT_ABS`.
+    ThmMovtAbs,
+
+    /// `R_ARM_THM_MOVW_PREL_NC`.
+    ThmMovwPrelNc,
+
+    /// `R_ARM_THM_MOVT_PREL`.
+    ThmMovtPrel,
+
+    /// `R_ARM_THM_JUMP19`.
+    ThmJump19,
+
+    /// `R_ARM_THM_JUMP6`.
+    ThmJump6,
+
+    /// `R_ARM_THM_ALU_PREL_11_0`.
+    ThmAluPrel110,
+
+    /// `R_ARM_THM_PC12`.
+    ThmPc12,
+
+    /// `R_ARM_ABS32_NOI`.
+    Abs32Noi,
+
+    /// `R_ARM_REL32_NOI`.
+    Rel32Noi,
+
+    /// `R_ARM_ALU_PC_G0_NC`.
+    AluPcG0Nc,
+
+    /// `R_ARM_ALU_PC_G0`.
+    AluPcG0,
+
+    /// `R_ARM_ALU_PC_G1_NC`.
+    AluPcG1Nc,
+
+    /// `R_ARM_ALU_PC_G1`.
+    AluPcG1,
+
+    /// `R_ARM_ALU_PC_G2`.
+    AluPcG2,
+
+    /// `R_ARM_LDR_PC_G1`.
+    LdrPcG1,
+
+    /// `R_ARM_LDR_PC_G2`.
+    LdrPcG2,
+
+    /// `R_ARM_LDRS_PC_G0`.
+    LdrsPcG0,
+
+    /// `R_ARM_LDRS_PC_G1`.
+    LdrsPcG1,
+
+    /// `R_ARM_LDRS_PC_G2`.
+    LdrsPcG2,
+
+    /// `R_ARM_LDC_PC_G0`.
+    LdcPcG0,
+
+    /// `R_ARM_LDC_PC_G1`.
+    LdcPcG1,
+
+    /// `R_ARM_LDC_PC_G2`.
+    LdcPcG2,
+
+    /// `R_ARM_ALU_SB_G0_NC`.
+    AluSbG0Nc,
+
+    /// `R_ARM_ALU_SB_G0`.
+    AluSbG0,
+
+    /// `R_ARM_ALU_SB_G1_NC`.
+    AluSbG1Nc,
+
+    /// `R_ARM_ALU_SB_G1`.
+    AluSbG1,
+
+    /// `R_ARM_ALU_SB_G2`.
+    AluSbG2,
+
+    /// `R_ARM_LDR_SB_G0`.
+    LdrSbG0,
+
+    /// `R_ARM_LDR_SB_G1`.
+    LdrSbG1,
+
+    /// `R_ARM_LDR_SB_G2`.
+    LdrSbG2,
+
+    /// `R_ARM_LDRS_SB_G0`.
+    LdrsSbG0,
+
+    /// `R_ARM_LDRS_SB_G1`.
+    LdrsSbG1,
+
+    /// `R_ARM_LDRS_SB_G2`.
+    LdrsSbG2,
+
+    /// `R_ARM_LDC_SB_G0`.
+    LdcSbG0,
+
+    /// `R_ARM_LDC_SB_G1`.
+    LdcSbG1,
+
+    /// `R_ARM_LDC_SB_G2`.
+    LdcSbG2,
+
+    /// `R_ARM_MOVW_BREL_NC`.
+    MovwBrelNc,
+
+    /// `R_ARM_MOVT_BREL`.
+    MovtBrel,
+
+    /// `R_ARM_MOVW_BREL`.
+    MovwBrel,
+
+    /// `R_ARM_THM_MOVW_BREL_NC`.
+    ThmMovwBrelNc,
+
+    /// `R_ARM_THM_MOVT_BREL`.
+    ThmMovtBrel,
+
+    /// `R_ARM_THM_MOVW_BREL`.
+    ThmMovwBrel,
+
+    /// `R_ARM_TLS_GOTDESC`.
+    TlsGotdesc,
+
+    /// `R_ARM_TLS_CALL`.
+    TlsCall,
+
+    /// `R_ARM_TLS_DESCSEQ`.
+    TlsDescseq,
+
+    /// `R_ARM_THM_TLS_CALL`.
+    ThmTlsCall,
+
+    /// `R_ARM_PLT32_ABS`.
+    Plt32Abs,
+
+    /// `R_ARM_GOT_ABS`.
+    GotAbs,
+
+    /// `R_ARM_GOT_PREL`.
+    GotPrel,
+
+    /// `R_ARM_GOT_BREL12`.
+    GotBrel12,
+
+    /// `R_ARM_GOTOFF12`.
+    Gotoff12,
+
+    /// `R_ARM_GOTRELAX`.
+    Gotrelax,
+
+    /// `R_ARM_GNU_VTENTRY`.
+    GnuVtentry,
+
+    /// `R_ARM_GNU_VTINHERIT`.
+    GnuVtinherit,
+
+    /// `R_ARM_THM_JUMP11`.
+    ThmJump11,
+
+    /// `R_ARM_THM_JUMP8`.
+    ThmJump8,
+
+    /// `R_ARM_TLS_GD32`.
+    TlsGd32,
+
+    /// `R_ARM_TLS_LDM32`.
+    TlsLdm32,
+
+    /// `R_ARM_TLS_LDO32`.
+    TlsLdo32,
+
+    /// `R_ARM_TLS_IE32`.
+    TlsIe32,
+
+    /// `R_ARM_TLS_LE32`.
+    TlsLe32,
+
+    /// `R_ARM_TLS_LDO12`.
+    TlsLdo12,
+
+    /// `R_ARM_TLS_LE12`.
+    TlsLe12,
+
+    /// `R_ARM_TLS_IE12GP`.
+    TlsIe12gp,
+
+    /// `R_ARM_THM_TLS_DESCSEQ16`.
+    ThmTlsDescseq16,
+
+    /// `R_ARM_THM_TLS_DESCSEQ32`.
+    ThmTlsDescseq32,
+
+    /// `R_ARM_THM_GOT_BREL12`.
+    ThmGotBrel12,
+
+    /// `R_ARM_IRELATIVE`.
+    Irelative,
+
+    /// Unknown relocation type.
+    Unknown(u32),
+}
+
+impl core::convert::From<u32> for ARM {
+    fn from(t: u32) -> Self {
+        match t {
+            0    => ARM::None,
+            1    => ARM::Pc24,
+            2    => ARM::Abs32,
+            3    => ARM::Rel32,
+            4    => ARM::LdrPcG0,
+            5    => ARM::Abs16,
+            6    => ARM::Abs12,
+            7    => ARM::ThmAbs5,
+            8    => ARM::Abs8,
+            9    => ARM::Sbrel32,
+            10   => ARM::ThmCall,
+            11   => ARM::ThmPc8,
+            12   => ARM::BrelAdj,
+            13   => ARM::TlsDesc,
+            14   => ARM::ThmSwi8,
+            15   => ARM::Xpc25,
+            16   => ARM::ThmXpc22,
+            17   => ARM::TlsDtpmod32,
+            18   => ARM::TlsDtpoff32,
+            19   => ARM::TlsTpoff32,
+            20   => ARM::Copy,
+            21   => ARM::GlobDat,
+            22   => ARM::JumpSlot,
+            23   => ARM::Relative,
+            24   => ARM::Gotoff32,
+            25   => ARM::BasePrel,
+            26   => ARM::GotBrel,
+            27   => ARM::Plt32,
+            28   => ARM::Call,
+            29   => ARM::Jump24,
+            30   => ARM::ThmJump24,
+            31   => ARM::BaseAbs,
+            32   => ARM::AluPcrel70,
+            33   => ARM::AluPcrel158,
+            34   => ARM::AluPcrel2315,
+            35   => ARM::LdrSbrel110,
+            36   => ARM::AluSbrel1912,
+            37   => ARM::AluSbrel2720,
+            38   => ARM::Target1,
+            39   => ARM::Sbrel31,
+            40   => ARM::V4bx,
+            41   => ARM::Target2,
+            42   => ARM::Prel31,
+            43   => ARM::MovwAbsNc,
+            44   => ARM::MovtAbs,
+            45   => ARM::MovwPrelNc,
+            46   => ARM::MovtPrel,
+            47   => ARM::ThmMovwAbsNc,
+            48   => ARM::ThmMovtAbs,
+            49   => ARM::ThmMovwPrelNc,
+            50   => ARM::ThmMovtPrel,
+            51   => ARM::ThmJump19,
+            52   => ARM::ThmJump6,
+            53   => ARM::ThmAluPrel110,
+            54   => ARM::ThmPc12,
+            55   => ARM::Abs32Noi,
+            56   => ARM::Rel32Noi,
+            57   => ARM::AluPcG0Nc,
+            58   => ARM::AluPcG0,
+            59   => ARM::AluPcG1Nc,
+            60   => ARM::AluPcG1,
+            61   => ARM::AluPcG2,
+            62   => ARM::LdrPcG1,
+            63   => ARM::LdrPcG2,
+            64   => ARM::LdrsPcG0,
+            65   => ARM::LdrsPcG1,
+            66   => ARM::LdrsPcG2,
+            67   => ARM::LdcPcG0,
+            68   => ARM::LdcPcG1,
+            69   => ARM::LdcPcG2,
+            70   => ARM::AluSbG0Nc,
+            71   => ARM::AluSbG0,
+            72   => ARM::AluSbG1Nc,
+            73   => ARM::AluSbG1,
+            74   => ARM::AluSbG2,
+            75   => ARM::LdrSbG0,
+            76   => ARM::LdrSbG1,
+            77   => ARM::LdrSbG2,
+            78   => ARM::LdrsSbG0,
+            79   => ARM::LdrsSbG1,
+            80   => ARM::LdrsSbG2,
+            81   => ARM::LdcSbG0,
+            82   => ARM::LdcSbG1,
+            83   => ARM::LdcSbG2,
+            84   => ARM::MovwBrelNc,
+            85   => ARM::MovtBrel,
+            86   => ARM::MovwBrel,
+            87   => ARM::ThmMovwBrelNc,
+            88   => ARM::ThmMovtBrel,
+            89   => ARM::ThmMovwBrel,
+            90   => ARM::TlsGotdesc,
+            91   => ARM::TlsCall,
+            92   => ARM::TlsDescseq,
+            93   => ARM::ThmTlsCall,
+            94   => ARM::Plt32Abs,
+            95   => ARM::GotAbs,
+            96   => ARM::GotPrel,
+            97   => ARM::GotBrel12,
+            98   => ARM::Gotoff12,
+            99   => ARM::Gotrelax,
+            100  => ARM::GnuVtentry,
+            101  => ARM::GnuVtinherit,
+            102  => ARM::ThmJump11,
+            103  => ARM::ThmJump8,
+            104  => ARM::TlsGd32,
+            105  => ARM::TlsLdm32,
+            106  => ARM::TlsLdo32,
+            107  => ARM::TlsIe32,
+            108  => ARM::TlsLe32,
+            109  => ARM::TlsLdo12,
+            110  => ARM::TlsLe12,
+            111  => ARM::TlsIe12gp,
+            129  => ARM::ThmTlsDescseq16,
+            130  => ARM::ThmTlsDescseq32,
+            131  => ARM::ThmGotBrel12,
+            160  => ARM::Irelative,
+
+            _ => ARM::Unknown(t),
+        }
+    }
+}
+
+impl core::convert::From<ARM> for u32 {
+    fn from(t: ARM) -> u32 {
+        match t {
+            ARM::None            => 0,
+            ARM::Pc24            => 1,
+            ARM::Abs32           => 2,
+            ARM::Rel32           => 3,
+            ARM::LdrPcG0         => 4,
+            ARM::Abs16           => 5,
+            ARM::Abs12           => 6,
+            ARM::ThmAbs5         => 7,
+            ARM::Abs8            => 8,
+            ARM::Sbrel32         => 9,
+            ARM::ThmCall         => 10,
+            ARM::ThmPc8          => 11,
+            ARM::BrelAdj         => 12,
+            ARM::TlsDesc         => 13,
+            ARM::ThmSwi8         => 14,
+            ARM::Xpc25           => 15,
+            ARM::ThmXpc22        => 16,
+            ARM::TlsDtpmod32     => 17,
+            ARM::TlsDtpoff32     => 18,
+            ARM::TlsTpoff32      => 19,
+            ARM::Copy            => 20,
+            ARM::GlobDat         => 21,
+            ARM::JumpSlot        => 22,
+            ARM::Relative        => 23,
+            ARM::Gotoff32        => 24,
+            ARM::BasePrel        => 25,
+            ARM::GotBrel         => 26,
+            ARM::Plt32           => 27,
+            ARM::Call            => 28,
+            ARM::Jump24          => 29,
+            ARM::ThmJump24       => 30,
+            ARM::BaseAbs         => 31,
+            ARM::AluPcrel70      => 32,
+            ARM::AluPcrel158     => 33,
+            ARM::AluPcrel2315    => 34,
+            ARM::LdrSbrel110     => 35,
+            ARM::AluSbrel1912    => 36,
+            ARM::AluSbrel2720    => 37,
+            ARM::Target1         => 38,
+            ARM::Sbrel31         => 39,
+            ARM::V4bx            => 40,
+            ARM::Target2         => 41,
+            ARM::Prel31          => 42,
+            ARM::MovwAbsNc       => 43,
+            ARM::MovtAbs         => 44,
+            ARM::MovwPrelNc      => 45,
+            ARM::MovtPrel        => 46,
+            ARM::ThmMovwAbsNc    => 47,
+            ARM::ThmMovtAbs      => 48,
+            ARM::ThmMovwPrelNc   => 49,
+            ARM::ThmMovtPrel     => 50,
+            ARM::ThmJump19       => 51,
+            ARM::ThmJump6        => 52,
+            ARM::ThmAluPrel110   => 53,
+            ARM::ThmPc12         => 54,
+            ARM::Abs32Noi        => 55,
+            ARM::Rel32Noi        => 56,
+            ARM::AluPcG0Nc       => 57,
+            ARM::AluPcG0         => 58,
+            ARM::AluPcG1Nc       => 59,
+            ARM::AluPcG1         => 60,
+            ARM::AluPcG2         => 61,
+            ARM::LdrPcG1         => 62,
+            ARM::LdrPcG2         => 63,
+            ARM::LdrsPcG0        => 64,
+            ARM::LdrsPcG1        => 65,
+            ARM::LdrsPcG2        => 66,
+            ARM::LdcPcG0         => 67,
+            ARM::LdcPcG1         => 68,
+            ARM::LdcPcG2         => 69,
+            ARM::AluSbG0Nc       => 70,
+            ARM::AluSbG0         => 71,
+            ARM::AluSbG1Nc       => 72,
+            ARM::AluSbG1         => 73,
+            ARM::AluSbG2         => 74,
+            ARM::LdrSbG0         => 75,
+            ARM::LdrSbG1         => 76,
+            ARM::LdrSbG2         => 77,
+            ARM::LdrsSbG0        => 78,
+            ARM::LdrsSbG1        => 79,
+            ARM::LdrsSbG2        => 80,
+            ARM::LdcSbG0         => 81,
+            ARM::LdcSbG1         => 82,
+            ARM::LdcSbG2         => 83,
+            ARM::MovwBrelNc      => 84,
+            ARM::MovtBrel        => 85,
+            ARM::MovwBrel        => 86,
+            ARM::ThmMovwBrelNc   => 87,
+            ARM::ThmMovtBrel     => 88,
+            ARM::ThmMovwBrel     => 89,
+            ARM::TlsGotdesc      => 90,
+            ARM::TlsCall         => 91,
+            ARM::TlsDescseq      => 92,
+            ARM::ThmTlsCall      => 93,
+            ARM::Plt32Abs        => 94,
+            ARM::GotAbs          => 95,
+            ARM::GotPrel         => 96,
+            ARM::GotBrel12       => 97,
+            ARM::Gotoff12        => 98,
+            ARM::Gotrelax        => 99,
+            ARM::GnuVtentry      => 100,
+            ARM::GnuVtinherit    => 101,
+            ARM::ThmJump11       => 102,
+            ARM::ThmJump8        => 103,
+            ARM::TlsGd32         => 104,
+            ARM::TlsLdm32        => 105,
+            ARM::TlsLdo32        => 106,
+            ARM::TlsIe32         => 107,
+            ARM::TlsLe32         => 108,
+            ARM::TlsLdo12        => 109,
+            ARM::TlsLe12         => 110,
+            ARM::TlsIe12gp       => 111,
+            ARM::ThmTlsDescseq16 => 129,
+            ARM::ThmTlsDescseq32 => 130,
+            ARM::ThmGotBrel12    => 131,
+            ARM::Irelative       => 160,
+
+            ARM::Unknown(t) => t,
+        }
+    }
+}
+
+impl core::fmt::Display for ARM {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        let s = match *self {
+            ARM::None            => "R_ARM_NONE",
+            ARM::Pc24            => "R_ARM_PC24",
+            ARM::Abs32           => "R_ARM_ABS32",
+            ARM::Rel32           => "R_ARM_REL32",
+            ARM::LdrPcG0         => "R_ARM_LDR_PC_G0",
+            ARM::Abs16           => "R_ARM_ABS16",
+            ARM::Abs12           => "R_ARM_ABS12",
+            ARM::ThmAbs5         => "R_ARM_THM_ABS5",
+            ARM::Abs8            => "R_ARM_ABS8",
+            ARM::Sbrel32         => "R_ARM_SBREL32",
+            ARM::ThmCall         => "R_ARM_THM_CALL",
+            ARM::ThmPc8          => "R_ARM_THM_PC8",
+            ARM::BrelAdj         => "R_ARM_BREL_ADJ",
+            ARM::TlsDesc         => "R_ARM_TLS_DESC",
+            ARM::ThmSwi8         => "R_ARM_THM_SWI8",
+            ARM::Xpc25           => "R_ARM_XPC25",
+            ARM::ThmXpc22        => "R_ARM_THM_XPC22",
+            ARM::TlsDtpmod32     => "R_ARM_TLS_DTPMOD32",
+            ARM::TlsDtpoff32     => "R_ARM_TLS_DTPOFF32",
+            ARM::TlsTpoff32      => "R_ARM_TLS_TPOFF32",
+            ARM::Copy            => "R_ARM_COPY",
+            ARM::GlobDat         => "R_ARM_GLOB_DAT",
+            ARM::JumpSlot        => "R_ARM_JUMP_SLOT",
+            ARM::Relative        => "R_ARM_RELATIVE",
+            ARM::Gotoff32        => "R_ARM_GOTOFF32",
+            ARM::BasePrel        => "R_ARM_BASE_PREL",
+            ARM::GotBrel         => "R_ARM_GOT_BREL",
+            ARM::Plt32           => "R_ARM_PLT32",
+            ARM::Call            => "R_ARM_CALL",
+            ARM::Jump24          => "R_ARM_JUMP24",
+            ARM::ThmJump24       => "R_ARM_THM_JUMP24",
+            ARM::BaseAbs         => "R_ARM_BASE_ABS",
+            ARM::AluPcrel70      => "R_ARM_ALU_PCREL_7_0",
+            ARM::AluPcrel158     => "R_ARM_ALU_PCREL_15_8",
+            ARM::AluPcrel2315    => "R_ARM_ALU_PCREL_23_15",
+            ARM::LdrSbrel110     => "R_ARM_LDR_SBREL_11_0",
+            ARM::AluSbrel1912    => "R_ARM_ALU_SBREL_19_12",
+            ARM::AluSbrel2720    => "R_ARM_ALU_SBREL_27_20",
+            ARM::Target1         => "R_ARM_TARGET1",
+            ARM::Sbrel31         => "R_ARM_SBREL31",
+            ARM::V4bx            => "R_ARM_V4BX",
+            ARM::Target2         => "R_ARM_TARGET2",
+            ARM::Prel31          => "R_ARM_PREL31",
+            ARM::MovwAbsNc       => "R_ARM_MOVW_ABS_NC",
+            ARM::MovtAbs         => "R_ARM_MOVT_ABS",
+            ARM::MovwPrelNc      => "R_ARM_MOVW_PREL_NC",
+            ARM::MovtPrel        => "R_ARM_MOVT_PREL",
+            ARM::ThmMovwAbsNc    => "R_ARM_THM_MOVW_ABS_NC",
+            ARM::ThmMovtAbs      => "R_ARM_THM_MOVT_ABS",
+            ARM::ThmMovwPrelNc   => "R_ARM_THM_MOVW_PREL_NC",
+            ARM::ThmMovtPrel     => "R_ARM_THM_MOVT_PREL",
+            ARM::ThmJump19       => "R_ARM_THM_JUMP19",
+            ARM::ThmJump6        => "R_ARM_THM_JUMP6",
+            ARM::ThmAluPrel110   => "R_ARM_THM_ALU_PREL_11_0",
+            ARM::ThmPc12         => "R_ARM_THM_PC12",
+            ARM::Abs32Noi        => "R_ARM_ABS32_NOI",
+            ARM::Rel32Noi        => "R_ARM_REL32_NOI",
+            ARM::AluPcG0Nc       => "R_ARM_ALU_PC_G0_NC",
+            ARM::AluPcG0         => "R_ARM_ALU_PC_G0",
+            ARM::AluPcG1Nc       => "R_ARM_ALU_PC_G1_NC",
+            ARM::AluPcG1         => "R_ARM_ALU_PC_G1",
+            ARM::AluPcG2         => "R_ARM_ALU_PC_G2",
+            ARM::LdrPcG1         => "R_ARM_LDR_PC_G1",
+            ARM::LdrPcG2         => "R_ARM_LDR_PC_G2",
+            ARM::LdrsPcG0        => "R_ARM_LDRS_PC_G0",
+            ARM::LdrsPcG1        => "R_ARM_LDRS_PC_G1",
+            ARM::LdrsPcG2        => "R_ARM_LDRS_PC_G2",
+            ARM::LdcPcG0         => "R_ARM_LDC_PC_G0",
+            ARM::LdcPcG1         => "R_ARM_LDC_PC_G1",
+            ARM::LdcPcG2         => "R_ARM_LDC_PC_G2",
+            ARM::AluSbG0Nc       => "R_ARM_ALU_SB_G0_NC",
+            ARM::AluSbG0         => "R_ARM_ALU_SB_G0",
+            ARM::AluSbG1Nc       => "R_ARM_ALU_SB_G1_NC",
+            ARM::AluSbG1         => "R_ARM_ALU_SB_G1",
+            ARM::AluSbG2         => "R_ARM_ALU_SB_G2",
+            ARM::LdrSbG0         => "R_ARM_LDR_SB_G0",
+            ARM::LdrSbG1         => "R_ARM_LDR_SB_G1",
+            ARM::LdrSbG2         => "R_ARM_LDR_SB_G2",
+            ARM::LdrsSbG0        => "R_ARM_LDRS_SB_G0",
+            ARM::LdrsSbG1        => "R_ARM_LDRS_SB_G1",
+            ARM::LdrsSbG2        => "R_ARM_LDRS_SB_G2",
+            ARM::LdcSbG0         => "R_ARM_LDC_SB_G0",
+            ARM::LdcSbG1         => "R_ARM_LDC_SB_G1",
+            ARM::LdcSbG2         => "R_ARM_LDC_SB_G2",
+            ARM::MovwBrelNc      => "R_ARM_MOVW_BREL_NC",
+            ARM::MovtBrel        => "R_ARM_MOVT_BREL",
+            ARM::MovwBrel        => "R_ARM_MOVW_BREL",
+            ARM::ThmMovwBrelNc   => "R_ARM_THM_MOVW_BREL_NC",
+            ARM::ThmMovtBrel     => "R_ARM_THM_MOVT_BREL",
+            ARM::ThmMovwBrel     => "R_ARM_THM_MOVW_BREL",
+            ARM::TlsGotdesc      => "R_ARM_TLS_GOTDESC",
+            ARM::TlsCall         => "R_ARM_TLS_CALL",
+            ARM::TlsDescseq      => "R_ARM_TLS_DESCSEQ",
+            ARM::ThmTlsCall      => "R_ARM_THM_TLS_CALL",
+            ARM::Plt32Abs        => "R_ARM_PLT32_ABS",
+            ARM::GotAbs          => "R_ARM_GOT_ABS",
+            ARM::GotPrel         => "R_ARM_GOT_PREL",
+            ARM::GotBrel12       => "R_ARM_GOT_BREL12",
+            ARM::Gotoff12        => "R_ARM_GOTOFF12",
+            ARM::Gotrelax        => "R_ARM_GOTRELAX",
+            ARM::GnuVtentry      => "R_ARM_GNU_VTENTRY",
+            ARM::GnuVtinherit    => "R_ARM_GNU_VTINHERIT",
+            ARM::ThmJump11       => "R_ARM_THM_JUMP11",
+            ARM::ThmJump8        => "R_ARM_THM_JUMP8",
+            ARM::TlsGd32         => "R_ARM_TLS_GD32",
+            ARM::TlsLdm32        => "R_ARM_TLS_LDM32",
+            ARM::TlsLdo32        => "R_ARM_TLS_LDO32",
+            ARM::TlsIe32         => "R_ARM_TLS_IE32",
+            ARM::TlsLe32         => "R_ARM_TLS_LE32",
+            ARM::TlsLdo12        => "R_ARM_TLS_LDO12",
+            ARM::TlsLe12         => "R_ARM_TLS_LE12",
+            ARM::TlsIe12gp       => "R_ARM_TLS_IE12GP",
+            ARM::ThmTlsDescseq16 => "R_ARM_THM_TLS_DESCSEQ16",
+            ARM::ThmTlsDescseq32 => "R_ARM_THM_TLS_DESCSEQ32",
+            ARM::ThmGotBrel12    => "R_ARM_THM_GOT_BREL12",
+            ARM::Irelative       => "R_ARM_IRELATIVE",
+
+            ARM::Unknown(t) => return write!(f, "Unknown ({})", t),
+        };
+
+        write!(f, "{}", s)
+    }
+}
@@ -0,0 +1,259 @@
+//! Relocations of an ELF file.
+//! Parsing of the `Rel` and `Rela` entries of the relocation sections.
+
+
+
+mod aarch64;
+mod arm;
+mod riscv;
+mod x86;
+mod x86_64;
+
+
+
+pub use aarch64::AArch64;
+pub use arm::ARM;
+pub use riscv::RISCV;
+pub use x86::X86;
+pub use x86_64::X86_64;
+
+use crate::{
+    common::address::Address,
+    error::{ Error, Table },
+};
+
+use super::{
+    SectionHeader, Symbol,
+    header::Architecture,
+};
+
+use std::sync::Arc;
+
+
+
+/// Relocation type of an entry, decoded according to the target architecture.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RelocationType {
+    /// Arm (Aarch32) relocation.
+    ARM(ARM),
+
+    /// AArch64 relocation.
+    AArch64(AArch64),
+
+    /// RISC-V relocation.
+    RISCV(RISCV),
+
+    /// x86 relocation.
+    X86(X86),
+
+    /// x86-64 relocation.
+    X86_64(X86_64),
+
+    /// Relocation of an architecture without named relocation types.
+    Other(u32),
+}
+
+impl RelocationType {
+    /// Decodes the raw relocation type for the given architecture.
+    pub fn new(architecture: Architecture, raw: u32) -> Self {
+        match architecture {
+            Architecture::Aarch32 => RelocationType::ARM( ARM::from(raw) ),
+            Architecture::Aarch64 => RelocationType::AArch64( AArch64::from(raw) ),
+            Architecture::RISCV   => RelocationType::RISCV( RISCV::from(raw) ),
+            Architecture::X86     => RelocationType::X86( X86::from(raw) ),
+            Architecture::AMD64   => RelocationType::X86_64( X86_64::from(raw) ),
+
+            _ => RelocationType::Other(raw),
+        }
+    }
+}
+
+impl core::convert::From<RelocationType> for u32 {
+    fn from(t: RelocationType) -> u32 {
+        match t {
+            RelocationType::ARM(t)     => u32::from(t),
+            RelocationType::AArch64(t) => u32::from(t),
+            RelocationType::RISCV(t)   => u32::from(t),
+            RelocationType::X86(t)     => u32::from(t),
+            RelocationType::X86_64(t)  => u32::from(t),
+            RelocationType::Other(t)   => t,
+        }
+    }
+}
+
+impl core::fmt::Display for RelocationType {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self {
+            RelocationType::ARM(t)     => write!(f, "{}", t),
+            RelocationType::AArch64(t) => write!(f, "{}", t),
+            RelocationType::RISCV(t)   => write!(f, "{}", t),
+            RelocationType::X86(t)     => write!(f, "{}", t),
+            RelocationType::X86_64(t)  => write!(f, "{}", t),
+            RelocationType::Other(t)   => write!(f, "{}", t),
+        }
+    }
+}
+
+
+
+/// A relocation entry of a `Rel` or `Rela` relocation section.
+#[derive(Debug, Clone)]
+pub struct Relocation {
+    /// Offset of the relocated location.
+    /// Section offset in relocatable files, virtual address in other files.
+    pub(super) offset: Address,
+
+    /// Index of the referenced symbol in the linked symbol table.
+    pub(super) symidx: u32,
+
+    /// The referenced symbol, if it is in the symbol table of the object.
+    pub(super) symbol: Option<Arc<Symbol>>,
+
+    /// Type of the relocation.
+    pub(super) kind: RelocationType,
+
+    /// Explicit addend of the relocation (only `Rela` entries).
+    pub(super) addend: Option<i64>,
+}
+
+impl Relocation {
+    /// Parses the given slice of data into a relocation entry.
+    /// The addend is read only if `rela` is `true`.
+    pub fn parse<R: AsRef<[u8]>, const INC: usize>(raw: R, read: fn(&[u8]) -> Address, rela: bool, architecture: Architecture) -> Result<Self, Error> {
+        // Entry size constant.
+        let hsize: usize = match rela {
+            true => 3 * INC,
+            _ => 2 * INC,
+        };
+
+        // Get the slice.
+        let raw = raw.as_ref();
+
+        // Check there is minimum length.
+        if raw.len() < hsize {
+            return Err( Error::BadHeaderSize { table: Table::Relocations, expected: hsize, found: raw.len() } );
+        }
+
+        // Read the offset.
+        let offset = read( &raw[0..INC] );
+
+        // Read the information.
+        let info = u64::from( read( &raw[INC..2*INC] ) );
+
+        // Split the information into the symbol index and the type.
+        let (symidx, kind) = match INC {
+            4 => ((info >> 8) as u32, (info & 0xFF) as u32),
+            _ => ((info >> 32) as u32, (info & 0xFFFF_FFFF) as u32),
+        };
+
+        // Read the addend, sign extending it to 64 bits.
+        let addend = match rela {
+            true => match read( &raw[2*INC..3*INC] ) {
+                Address::U32(a) => Some( a as i32 as i64 ),
+                Address::U64(a) => Some( a as i64 ),
+            },
+            _ => None,
+        };
+
+        Ok(Self {
+            offset,
+            symidx,
+            symbol: None,
+            kind: RelocationType::new(architecture, kind),
+            addend,
+        })
+    }
+
+    /// Returns the offset of the relocated location.
+    pub fn offset(&self) -> Address {
+        self.offset
+    }
+
+    /// Returns the index of the referenced symbol.
+    pub fn symidx(&self) -> u32 {
+        self.symidx
+    }
+
+    /// Returns the referenced symbol, if it is in the symbol table of the object.
+    pub fn symbol(&self) -> Option<&Arc<Symbol>> {
+        self.symbol.as_ref()
+    }
+
+    /// Returns the type of the relocation.
+    pub fn kind(&self) -> RelocationType {
+        self.kind
+    }
+
+    /// Returns the explicit addend of the relocation (only `Rela` entries).
+    pub fn addend(&self) -> Option<i64> {
+        self.addend
+    }
+
+    /// Creates a pretty print of the relocation's information.
+    pub fn prettyprint(&self) -> String {
+        // Create output string.
+        let mut args = String::new();
+
+        // Relocation type and offset.
+        args += &format!("Relocation {} at {:X}\n", self.kind, self.offset);
+
+        // Referenced symbol.
+        match &self.symbol {
+            Some(symbol) => args += &format!("  - Symbol: \"{}\" ({})\n", symbol.name(), self.symidx),
+            _ => args += &format!("  - Symbol: {}\n", self.symidx),
+        }
+
+        // Explicit addend.
+        if let Some(addend) = self.addend {
+            args += &format!("  - Addend: {}\n", addend);
+        }
+
+        args
+    }
+}
+
+
+
+/// The relocations of a relocation section, linked to their target section and symbol table.
+#[derive(Debug, Clone)]
+pub struct RelocationTable {
+    /// The relocation section.
+    pub(super) section: Arc<SectionHeader>,
+
+    /// The section to which the relocations apply (`info`).
+    pub(super) target: Option<Arc<SectionHeader>>,
+
+    /// The symbol table referenced by the relocations (`link`).
+    pub(super) symtab: Option<Arc<SectionHeader>>,
+
+    /// The relocation entries.
+    pub(super) relocations: Vec<Relocation>,
+}
+
+impl RelocationTable {
+    /// Returns the relocation section.
+    pub fn section(&self) -> &Arc<SectionHeader> {
+        &self.section
+    }
+
+    /// Returns the section to which the relocations apply.
+    /// Dynamic relocation tables do not apply to a single section.
+    pub fn target(&self) -> Option<&Arc<SectionHeader>> {
+        self.target.as_ref()
+    }
+
+    /// Returns the symbol table referenced by the relocations.
+    pub fn symtab(&self) -> Option<&Arc<SectionHeader>> {
+        self.symtab.as_ref()
+    }
+
+    /// Returns the relocation entries.
+    pub fn relocations(&self) -> &Vec<Relocation> {
+        &self.relocations
+    }
+
+    /// Returns `true` if the entries have explicit addends.
+    pub fn rela(&self) -> bool {
+        self.section.sectiontype() == super::section::SectionType::RelocationA
+    }
+}
@@ -0,0 +1,368 @@
+//! RISC-V relocation types.
+
+
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RISCV {
+    /// `R_RISCV_NONE`.
+    None,
+
+    /// `R_RISCV_32`.
+    Abs32,
+
+    /// `R_RISCV_64`.
+    Abs64,
+
+    /// `R_RISCV_RELATIVE`.
+    Relative,
+
+    /// `R_RISCV_COPY`.
+    Copy,
+
+    /// `R_RISCV_JUMP_SLOT`.
+    JumpSlot,
+
+    /// `R_RISCV_TLS_DTPMOD32`.
+    TlsDtpmod32,
+
+    /// `R_RISCV_TLS_DTPMOD64`.
+    TlsDtpmod64,
+
+    /// `R_RISCV_TLS_DTPREL32`.
+    TlsDtprel32,
+
+    /// `R_RISCV_TLS_DTPREL64`.
+    TlsDtprel64,
+
+    /// `R_RISCV_TLS_TPREL32`.
+    TlsTprel32,
+
+    /// `R_RISCV_TLS_TPREL64`.
+    TlsTprel64,
+
+    /// `R_RISCV_BRANCH`.
+    Branch,
+
+    /// `R_RISCV_JAL`.
+    Jal,
+
+    /// `R_RISCV_CALL`.
+    Call,
+
+    /// `R_RISCV_CALL_PLT`.
+    CallPlt,
+
+    /// `R_RISCV_GOT_HI20`.
+    GotHi20,
+
+    /// `R_RISCV_TLS_GOT_HI20`.
+    TlsGotHi20,
+
+    /// `R_RISCV_TLS_GD_HI20`.
+    TlsGdHi20,
+
+    /// `R_RISCV_PCREL_HI20`.
+    PcrelHi20,
+
+    /// `R_RISCV_PCREL_LO12_I`.
+    PcrelLo12I,
+
+    /// `R_RISCV_PCREL_LO12_S`.
+    PcrelLo12S,
+
+    /// `R_RISCV_HI20`.
+    Hi20,
+
+    /// `R_RISCV_LO12_I`.
+    Lo12I,
+
+    /// `R_RISCV_LO12_S`.
+    Lo12S,
+
+    /// `R_RISCV_TPREL_HI20`.
+    TprelHi20,
+
+    /// `R_RISCV_TPREL_LO12_I`.
+    TprelLo12I,
+
+    /// `R_RISCV_TPREL_LO12_S`.
+    TprelLo12S,
+
+    /// `R_RISCV_TPREL_ADD`.
+    TprelAdd,
+
+    /// `R_RISCV_ADD8`.
+    Add8,
+
+    /// `R_RISCV_ADD16`.
+    Add16,
+
+    /// `R_RISCV_ADD32`.
+    Add32,
+
+    /// `R_RISCV_ADD64`.
+    Add64,
+
+    /// `R_RISCV_SUB8`.
+    Sub8,
+
+    /// `R_RISCV_SUB16`.
+    Sub16,
+
+    /// `R_RISCV_SUB32`.
+    Sub32,
+
+    /// `R_RISCV_SUB64`.
+    Sub64,
+
+    /// `R_RISCV_GNU_VTINHERIT`.
+    GnuVtinherit,
+
+    /// `R_RISCV_GNU_VTENTRY`.
+    GnuVtentry,
+
+    /// `R_RISCV_ALIGN`.
+    Align,
+
+    /// `R_RISCV_RVC_BRANCH`.
+    RvcBranch,
+
+    /// `R_RISCV_RVC_JUMP`.
+    RvcJump,
+
+    /// `R_RISCV_RVC_LUI`.
+    RvcLui,
+
+    /// `R_RISCV_GPREL_I`.
+    GprelI,
+
+    /// `R_RISCV_GPREL_S`.
+    GprelS,
+
+    /// `R_RISCV_TPREL_I`.
+    TprelI,
+
+    /// `R_RISCV_TPREL_S`.
+    TprelS,
+
+    /// `R_RISCV_RELAX`.
+    Relax,
+
+    /// `R_RISCV_SUB6`.
+    Sub6,
+
+    /// `R_RISCV_SET6`.
+    Set6,
+
+    /// `R_RISCV_SET8`.
+    Set8,
+
+    /// `R_RISCV_SET16`.
+    Set16,
+
+    /// `R_RISCV_SET32`.
+    Set32,
+
+    /// `R_RISCV_32_PCREL`.
+    Pcrel32,
+
+    /// `R_RISCV_IRELATIVE`.
+    Irelative,
+
+    /// Unknown relocation type.
+    Unknown(u32),
+}
+
+impl core::convert::From<u32> for RISCV {
+    fn from(t: u32) -> Self {
+        match t {
+            0    => RISCV::None,
+            1    => RISCV::Abs32,
+            2    => RISCV::Abs64,
+            3    => RISCV::Relative,
+            4    => RISCV::Copy,
+            5    => RISCV::JumpSlot,
+            6    => RISCV::TlsDtpmod32,
+            7    => RISCV::TlsDtpmod64,
+            8    => RISCV::TlsDtprel32,
+            9    => RISCV::TlsDtprel64,
+            10   => RISCV::TlsTprel32,
+            11   => RISCV::TlsTprel64,
+            16   => RISCV::Branch,
+            17   => RISCV::Jal,
+            18   => RISCV::Call,
+            19   => RISCV::CallPlt,
+            20   => RISCV::GotHi20,
+            21   => RISCV::TlsGotHi20,
+            22   => RISCV::TlsGdHi20,
+            23   => RISCV::PcrelHi20,
+            24   => RISCV::PcrelLo12I,
+            25   => RISCV::PcrelLo12S,
+            26   => RISCV::Hi20,
+            27   => RISCV::Lo12I,
+            28   => RISCV::Lo12S,
+            29   => RISCV::TprelHi20,
+            30   => RISCV::TprelLo12I,
+            31   => RISCV::TprelLo12S,
+            32   => RISCV::TprelAdd,
+            33   => RISCV::Add8,
+            34   => RISCV::Add16,
+            35   => RISCV::Add32,
+            36   => RISCV::Add64,
+            37   => RISCV::Sub8,
+            38   => RISCV::Sub16,
+            39   => RISCV::Sub32,
+            40   => RISCV::Sub64,
+            41   => RISCV::GnuVtinherit,
+            42   => RISCV::GnuVtentry,
+            43   => RISCV::Align,
+            44   => RISCV::RvcBranch,
+            45   => RISCV::RvcJump,
+            46   => RISCV::RvcLui,
+            47   => RISCV::GprelI,
+            48   => RISCV::GprelS,
+            49   => RISCV::TprelI,
+            50   => RISCV::TprelS,
+            51   => RISCV::Relax,
+            52   => RISCV::Sub6,
+            53   => RISCV::Set6,
+            54   => RISCV::Set8,
+            55   => RISCV::Set16,
+            56   => RISCV::Set32,
+            57   => RISCV::Pcrel32,
+            58   => RISCV::Irelative,
+
+            _ => RISCV::Unknown(t),
+        }
+    }
+}
+
+impl core::convert::From<RISCV> for u32 {
+    fn from(t: RISCV) -> u32 {
+        match t {
+            RISCV::None         => 0,
+            RISCV::Abs32        => 1,
+            RISCV::Abs64        => 2,
+            RISCV::Relative     => 3,
+            RISCV::Copy         => 4,
+            RISCV::JumpSlot     => 5,
+            RISCV::TlsDtpmod32  => 6,
+            RISCV::TlsDtpmod64  => 7,
+            RISCV::TlsDtprel32  => 8,
+            RISCV::TlsDtprel64  => 9,
+            RISCV::TlsTprel32   => 10,
+            RISCV::TlsTprel64   => 11,
+            RISCV::Branch       => 16,
+            RISCV::Jal          => 17,
+            RISCV::Call         => 18,
+            RISCV::CallPlt      => 19,
+            RISCV::GotHi20      => 20,
+            RISCV::TlsGotHi20   => 21,
+            RISCV::TlsGdHi20    => 22,
+            RISCV::PcrelHi20    => 23,
+            RISCV::PcrelLo12I   => 24,
+            RISCV::PcrelLo12S   => 25,
+            RISCV::Hi20         => 26,
+            RISCV::Lo12I        => 27,
+            RISCV::Lo12S        => 28,
+            RISCV::TprelHi20    => 29,
+            RISCV::TprelLo12I   => 30,
+            RISCV::TprelLo12S   => 31,
+            RISCV::TprelAdd     => 32,
+            RISCV::Add8         => 33,
+            RISCV::Add16        => 34,
+            RISCV::Add32        => 35,
+            RISCV::Add64        => 36,
+            RISCV::Sub8         => 37,
+            RISCV::Sub16        => 38,
+            RISCV::Sub32        => 39,
+            RISCV::Sub64        => 40,
+            RISCV::GnuVtinherit => 41,
+            RISCV::GnuVtentry   => 42,
+            RISCV::Align        => 43,
+            RISCV::RvcBranch    => 44,
+            RISCV::RvcJump      => 45,
+            RISCV::RvcLui       => 46,
+            RISCV::GprelI       => 47,
+            RISCV::GprelS       => 48,
+            RISCV::TprelI       => 49,
+            RISCV::TprelS       => 50,
+            RISCV::Relax        => 51,
+            RISCV::Sub6         => 52,
+            RISCV::Set6         => 53,
+            RISCV::Set8         => 54,
+            RISCV::Set16        => 55,
+            RISCV::Set32        => 56,
+            RISCV::Pcrel32      => 57,
+            RISCV::Irelative    => 58,
+
+            RISCV::Unknown(t) => t,
+        }
+    }
+}
+
+impl core::fmt::Display for RISCV {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        let s = match *self {
+            RISCV::None         => "R_RISCV_NONE",
+            RISCV::Abs32        => "R_RISCV_32",
+            RISCV::Abs64        => "R_RISCV_64",
+            RISCV::Relative     => "R_RISCV_RELATIVE",
+            RISCV::Copy         => "R_RISCV_COPY",
+            RISCV::JumpSlot     => "R_RISCV_JUMP_SLOT",
+            RISCV::TlsDtpmod32  => "R_RISCV_TLS_DTPMOD32",
+            RISCV::TlsDtpmod64  => "R_RISCV_TLS_DTPMOD64",
+            RISCV::TlsDtprel32  => "R_RISCV_TLS_DTPREL32",
+            RISCV::TlsDtprel64  => "R_RISCV_TLS_DTPREL64",
+            RISCV::TlsTprel32   => "R_RISCV_TLS_TPREL32",
+            RISCV::TlsTprel64   => "R_RISCV_TLS_TPREL64",
+            RISCV::Branch       => "R_RISCV_BRANCH",
+            RISCV::Jal          => "R_RISCV_JAL",
+            RISCV::Call         => "R_RISCV_CALL",
+            RISCV::CallPlt      => "R_RISCV_CALL_PLT",
+            RISCV::GotHi20      => "R_RISCV_GOT_HI20",
+            RISCV::TlsGotHi20   => "R_RISCV_TLS_GOT_HI20",
+            RISCV::TlsGdHi20    => "R_RISCV_TLS_GD_HI20",
+            RISCV::PcrelHi20    => "R_RISCV_PCREL_HI20",
+            RISCV::PcrelLo12I   => "R_RISCV_PCREL_LO12_I",
+            RISCV::PcrelLo12S   => "R_RISCV_PCREL_LO12_S",
+            RISCV::Hi20         => "R_RISCV_HI20",
+            RISCV::Lo12I        => "R_RISCV_LO12_I",
+            RISCV::Lo12S        => "R_RISCV_LO12_S",
+            RISCV::TprelHi20    => "R_RISCV_TPREL_HI20",
+            RISCV::TprelLo12I   => "R_RISCV_TPREL_LO12_I",
+            RISCV::TprelLo12S   => "R_RISCV_TPREL_LO12_S",
+            RISCV::TprelAdd     => "R_RISCV_TPREL_ADD",
+            RISCV::Add8         => "R_RISCV_ADD8",
+            RISCV::Add16        => "R_RISCV_ADD16",
+            RISCV::Add32        => "R_RISCV_ADD32",
+            RISCV::Add64        => "R_RISCV_ADD64",
+            RISCV::Sub8         => "R_RISCV_SUB8",
+            RISCV::Sub16        => "R_RISCV_SUB16",
+            RISCV::Sub32        => "R_RISCV_SUB32",
+            RISCV::Sub64        => "R_RISCV_SUB64",
+            RISCV::GnuVtinherit => "R_RISCV_GNU_VTINHERIT",
+            RISCV::GnuVtentry   => "R_RISCV_GNU_VTENTRY",
+            RISCV::Align        => "R_RISCV_ALIGN",
+            RISCV::RvcBranch    => "R_RISCV_RVC_BRANCH",
+            RISCV::RvcJump      => "R_RISCV_RVC_JUMP",
+            RISCV::RvcLui       => "R_RISCV_RVC_LUI",
+            RISCV::GprelI       => "R_RISCV_GPREL_I",
+            RISCV::GprelS       => "R_RISCV_GPREL_S",
+            RISCV::TprelI       => "R_RISCV_TPREL_I",
+            RISCV::TprelS       => "R_RISCV_TPREL_S",
+            RISCV::Relax        => "R_RISCV_RELAX",
+            RISCV::Sub6         => "R_RISCV_SUB6",
+            RISCV::Set6         => "R_RISCV_SET6",
+            RISCV::Set8         => "R_RISCV_SET8",
+            RISCV::Set16        => "R_RISCV_SET16",
+            RISCV::Set32        => "R_RISCV_SET32",
+            RISCV::Pcrel32      => "R_RISCV_32_PCREL",
+            RISCV::Irelative    => "R_RISCV_IRELATIVE",
+
+            RISCV::Unknown(t) => return write!(f, "Unknown ({})", t),
+        };
+
+        write!(f, "{}", s)
+    }
+}
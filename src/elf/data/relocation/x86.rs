@@ -0,0 +1,290 @@
+//! x86 relocation types.
+
+
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum X86 {
+    /// `R_386_NONE`.
+    None,
+
+    /// `R_386_32`.
+    Abs32,
+
+    /// `R_386_PC32`.
+    Pc32,
+
+    /// `R_386_GOT32`.
+    Got32,
+
+    /// `R_386_PLT32`.
+    Plt32,
+
+    /// `R_386_COPY`.
+    Copy,
+
+    /// `R_386_GLOB_DAT`.
+    GlobDat,
+
+    /// `R_386_JMP_SLOT`.
+    JmpSlot,
+
+    /// `R_386_RELATIVE`.
+    Relative,
+
+    /// `R_386_GOTOFF`.
+    Gotoff,
+
+    /// `R_386_GOTPC`.
+    Gotpc,
+
+    /// `R_386_32PLT`.
+    Abs32Plt,
+
+    /// `R_386_TLS_TPOFF`.
+    TlsTpoff,
+
+    /// `R_386_TLS_IE`.
+    TlsIe,
+
+    /// `R_386_TLS_GOTIE`.
+    TlsGotie,
+
+    /// `R_386_TLS_LE`.
+    TlsLe,
+
+    /// `R_386_TLS_GD`.
+    TlsGd,
+
+    /// `R_386_TLS_LDM`.
+    TlsLdm,
+
+    /// `R_386_16`.
+    Abs16,
+
+    /// `R_386_PC16`.
+    Pc16,
+
+    /// `R_386_8`.
+    Abs8,
+
+    /// `R_386_PC8`.
+    Pc8,
+
+    /// `R_386_TLS_GD_32`.
+    TlsGd32,
+
+    /// `R_386_TLS_GD_PUSH`.
+    TlsGdPush,
+
+    /// `R_386_TLS_GD_CALL`.
+    TlsGdCall,
+
+    /// `R_386_TLS_GD_POP`.
+    TlsGdPop,
+
+    /// `R_386_TLS_LDM_32`.
+    TlsLdm32,
+
+    /// `R_386_TLS_LDM_PUSH`.
+    TlsLdmPush,
+
+    /// `R_386_TLS_LDM_CALL`.
+    TlsLdmCall,
+
+    /// `R_386_TLS_LDM_POP`.
+    TlsLdmPop,
+
+    /// `R_386_TLS_LDO_32`.
+    TlsLdo32,
+
+    /// `R_386_TLS_IE_32`.
+    TlsIe32,
+
+    /// `R_386_TLS_LE_32`.
+    TlsLe32,
+
+    /// `R_386_TLS_DTPMOD32`.
+    TlsDtpmod32,
+
+    /// `R_386_TLS_DTPOFF32`.
+    TlsDtpoff32,
+
+    /// `R_386_TLS_TPOFF32`.
+    TlsTpoff32,
+
+    /// `R_386_SIZE32`.
+    Size32,
+
+    /// `R_386_TLS_GOTDESC`.
+    TlsGotdesc,
+
+    /// `R_386_TLS_DESC_CALL`.
+    TlsDescCall,
+
+    /// `R_386_TLS_DESC`.
+    TlsDesc,
+
+    /// `R_386_IRELATIVE`.
+    Irelative,
+
+    /// `R_386_GOT32X`.
+    Got32x,
+
+    /// Unknown relocation type.
+    Unknown(u32),
+}
+
+impl core::convert::From<u32> for X86 {
+    fn from(t: u32) -> Self {
+        match t {
+            0    => X86::None,
+            1    => X86::Abs32,
+            2    => X86::Pc32,
+            3    => X86::Got32,
+            4    => X86::Plt32,
+            5    => X86::Copy,
+            6    => X86::GlobDat,
+            7    => X86::JmpSlot,
+            8    => X86::Relative,
+            9    => X86::Gotoff,
+            10   => X86::Gotpc,
+            11   => X86::Abs32Plt,
+            14   => X86::TlsTpoff,
+            15   => X86::TlsIe,
+            16   => X86::TlsGotie,
+            17   => X86::TlsLe,
+            18   => X86::TlsGd,
+            19   => X86::TlsLdm,
+            20   => X86::Abs16,
+            21   => X86::Pc16,
+            22   => X86::Abs8,
+            23   => X86::Pc8,
+            24   => X86::TlsGd32,
+            25   => X86::TlsGdPush,
+            26   => X86::TlsGdCall,
+            27   => X86::TlsGdPop,
+            28   => X86::TlsLdm32,
+            29   => X86::TlsLdmPush,
+            30   => X86::TlsLdmCall,
+            31   => X86::TlsLdmPop,
+            32   => X86::TlsLdo32,
+            33   => X86::TlsIe32,
+            34   => X86::TlsLe32,
+            35   => X86::TlsDtpmod32,
+            36   => X86::TlsDtpoff32,
+            37   => X86::TlsTpoff32,
+            38   => X86::Size32,
+            39   => X86::TlsGotdesc,
+            40   => X86::TlsDescCall,
+            41   => X86::TlsDesc,
+            42   => X86::Irelative,
+            43   => X86::Got32x,
+
+            _ => X86::Unknown(t),
+        }
+    }
+}
+
+impl core::convert::From<X86> for u32 {
+    fn from(t: X86) -> u32 {
+        match t {
+            X86::None        => 0,
+            X86::Abs32       => 1,
+            X86::Pc32        => 2,
+            X86::Got32       => 3,
+            X86::Plt32       => 4,
+            X86::Copy        => 5,
+            X86::GlobDat     => 6,
+            X86::JmpSlot     => 7,
+            X86::Relative    => 8,
+            X86::Gotoff      => 9,
+            X86::Gotpc       => 10,
+            X86::Abs32Plt    => 11,
+            X86::TlsTpoff    => 14,
+            X86::TlsIe       => 15,
+            X86::TlsGotie    => 16,
+            X86::TlsLe       => 17,
+            X86::TlsGd       => 18,
+            X86::TlsLdm      => 19,
+            X86::Abs16       => 20,
+            X86::Pc16        => 21,
+            X86::Abs8        => 22,
+            X86::Pc8         => 23,
+            X86::TlsGd32     => 24,
+            X86::TlsGdPush   => 25,
+            X86::TlsGdCall   => 26,
+            X86::TlsGdPop    => 27,
+            X86::TlsLdm32    => 28,
+            X86::TlsLdmPush  => 29,
+            X86::TlsLdmCall  => 30,
+            X86::TlsLdmPop   => 31,
+            X86::TlsLdo32    => 32,
+            X86::TlsIe32     => 33,
+            X86::TlsLe32     => 34,
+            X86::TlsDtpmod32 => 35,
+            X86::TlsDtpoff32 => 36,
+            X86::TlsTpoff32  => 37,
+            X86::Size32      => 38,
+            X86::TlsGotdesc  => 39,
+            X86::TlsDescCall => 40,
+            X86::TlsDesc     => 41,
+            X86::Irelative   => 42,
+            X86::Got32x      => 43,
+
+            X86::Unknown(t) => t,
+        }
+    }
+}
+
+impl core::fmt::Display for X86 {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        let s = match *self {
+            X86::None        => "R_386_NONE",
+            X86::Abs32       => "R_386_32",
+            X86::Pc32        => "R_386_PC32",
+            X86::Got32       => "R_386_GOT32",
+            X86::Plt32       => "R_386_PLT32",
+            X86::Copy        => "R_386_COPY",
+            X86::GlobDat     => "R_386_GLOB_DAT",
+            X86::JmpSlot     => "R_386_JMP_SLOT",
+            X86::Relative    => "R_386_RELATIVE",
+            X86::Gotoff      => "R_386_GOTOFF",
+            X86::Gotpc       => "R_386_GOTPC",
+            X86::Abs32Plt    => "R_386_32PLT",
+            X86::TlsTpoff    => "R_386_TLS_TPOFF",
+            X86::TlsIe       => "R_386_TLS_IE",
+            X86::TlsGotie    => "R_386_TLS_GOTIE",
+            X86::TlsLe       => "R_386_TLS_LE",
+            X86::TlsGd       => "R_386_TLS_GD",
+            X86::TlsLdm      => "R_386_TLS_LDM",
+            X86::Abs16       => "R_386_16",
+            X86::Pc16        => "R_386_PC16",
+            X86::Abs8        => "R_386_8",
+            X86::Pc8         => "R_386_PC8",
+            X86::TlsGd32     => "R_386_TLS_GD_32",
+            X86::TlsGdPush   => "R_386_TLS_GD_PUSH",
+            X86::TlsGdCall   => "R_386_TLS_GD_CALL",
+            X86::TlsGdPop    => "R_386_TLS_GD_POP",
+            X86::TlsLdm32    => "R_386_TLS_LDM_32",
+            X86::TlsLdmPush  => "R_386_TLS_LDM_PUSH",
+            X86::TlsLdmCall  => "R_386_TLS_LDM_CALL",
+            X86::TlsLdmPop   => "R_386_TLS_LDM_POP",
+            X86::TlsLdo32    => "R_386_TLS_LDO_32",
+            X86::TlsIe32     => "R_386_TLS_IE_32",
+            X86::TlsLe32     => "R_386_TLS_LE_32",
+            X86::TlsDtpmod32 => "R_386_TLS_DTPMOD32",
+            X86::TlsDtpoff32 => "R_386_TLS_DTPOFF32",
+            X86::TlsTpoff32  => "R_386_TLS_TPOFF32",
+            X86::Size32      => "R_386_SIZE32",
+            X86::TlsGotdesc  => "R_386_TLS_GOTDESC",
+            X86::TlsDescCall => "R_386_TLS_DESC_CALL",
+            X86::TlsDesc     => "R_386_TLS_DESC",
+            X86::Irelative   => "R_386_IRELATIVE",
+            X86::Got32x      => "R_386_GOT32X",
+
+            X86::Unknown(t) => return write!(f, "Unknown ({})", t),
+        };
+
+        write!(f, "{}", s)
+    }
+}
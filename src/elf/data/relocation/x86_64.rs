@@ -0,0 +1,284 @@
+//! x86-64 relocation types.
+
+
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum X86_64 {
+    /// `R_X86_64_NONE`.
+    None,
+
+    /// `R_X86_64_64`.
+    Abs64,
+
+    /// `R_X86_64_PC32`.
+    Pc32,
+
+    /// `R_X86_64_GOT32`.
+    Got32,
+
+    /// `R_X86_64_PLT32`.
+    Plt32,
+
+    /// `R_X86_64_COPY`.
+    Copy,
+
+    /// `R_X86_64_GLOB_DAT`.
+    GlobDat,
+
+    /// `R_X86_64_JUMP_SLOT`.
+    JumpSlot,
+
+    /// `R_X86_64_RELATIVE`.
+    Relative,
+
+    /// `R_X86_64_GOTPCREL`.
+    Gotpcrel,
+
+    /// `R_X86_64_32`.
+    Abs32,
+
+    /// `R_X86_64_32S`.
+    Abs32s,
+
+    /// `R_X86_64_16`.
+    Abs16,
+
+    /// `R_X86_64_PC16`.
+    Pc16,
+
+    /// `R_X86_64_8`.
+    Abs8,
+
+    /// `R_X86_64_PC8`.
+    Pc8,
+
+    /// `R_X86_64_DTPMOD64`.
+    Dtpmod64,
+
+    /// `R_X86_64_DTPOFF64`.
+    Dtpoff64,
+
+    /// `R_X86_64_TPOFF64`.
+    Tpoff64,
+
+    /// `R_X86_64_TLSGD`.
+    Tlsgd,
+
+    /// `R_X86_64_TLSLD`.
+    Tlsld,
+
+    /// `R_X86_64_DTPOFF32`.
+    Dtpoff32,
+
+    /// `R_X86_64_GOTTPOFF`.
+    Gottpoff,
+
+    /// `R_X86_64_TPOFF32`.
+    Tpoff32,
+
+    /// `R_X86_64_PC64`.
+    Pc64,
+
+    /// `R_X86_64_GOTOFF64`.
+    Gotoff64,
+
+    /// `R_X86_64_GOTPC32`.
+    Gotpc32,
+
+    /// `R_X86_64_GOT64`.
+    Got64,
+
+    /// `R_X86_64_GOTPCREL64`.
+    Gotpcrel64,
+
+    /// `R_X86_64_GOTPC64`.
+    Gotpc64,
+
+    /// `R_X86_64_GOTPLT64`.
+    Gotplt64,
+
+    /// `R_X86_64_PLTOFF64`.
+    Pltoff64,
+
+    /// `R_X86_64_SIZE32`.
+    Size32,
+
+    /// `R_X86_64_SIZE64`.
+    Size64,
+
+    /// `R_X86_64_GOTPC32_TLSDESC`.
+    Gotpc32Tlsdesc,
+
+    /// `R_X86_64_TLSDESC_CALL`.
+    TlsdescCall,
+
+    /// `R_X86_64_TLSDESC`.
+    Tlsdesc,
+
+    /// `R_X86_64_IRELATIVE`.
+    Irelative,
+
+    /// `R_X86_64_RELATIVE64`.
+    Relative64,
+
+    /// `R_X86_64_GOTPCRELX`.
+    Gotpcrelx,
+
+    /// `R_X86_64_REX_GOTPCRELX`.
+    RexGotpcrelx,
+
+    /// Unknown relocation type.
+    Unknown(u32),
+}
+
+impl core::convert::From<u32> for X86_64 {
+    fn from(t: u32) -> Self {
+        match t {
+            0    => X86_64::None,
+            1    => X86_64::Abs64,
+            2    => X86_64::Pc32,
+            3    => X86_64::Got32,
+            4    => X86_64::Plt32,
+            5    => X86_64::Copy,
+            6    => X86_64::GlobDat,
+            7    => X86_64::JumpSlot,
+            8    => X86_64::Relative,
+            9    => X86_64::Gotpcrel,
+            10   => X86_64::Abs32,
+            11   => X86_64::Abs32s,
+            12   => X86_64::Abs16,
+            13   => X86_64::Pc16,
+            14   => X86_64::Abs8,
+            15   => X86_64::Pc8,
+            16   => X86_64::Dtpmod64,
+            17   => X86_64::Dtpoff64,
+            18   => X86_64::Tpoff64,
+            19   => X86_64::Tlsgd,
+            20   => X86_64::Tlsld,
+            21   => X86_64::Dtpoff32,
+            22   => X86_64::Gottpoff,
+            23   => X86_64::Tpoff32,
+            24   => X86_64::Pc64,
+            25   => X86_64::Gotoff64,
+            26   => X86_64::Gotpc32,
+            27   => X86_64::Got64,
+            28   => X86_64::Gotpcrel64,
+            29   => X86_64::Gotpc64,
+            30   => X86_64::Gotplt64,
+            31   => X86_64::Pltoff64,
+            32   => X86_64::Size32,
+            33   => X86_64::Size64,
+            34   => X86_64::Gotpc32Tlsdesc,
+            35   => X86_64::TlsdescCall,
+            36   => X86_64::Tlsdesc,
+            37   => X86_64::Irelative,
+            38   => X86_64::Relative64,
+            41   => X86_64::Gotpcrelx,
+            42   => X86_64::RexGotpcrelx,
+
+            _ => X86_64::Unknown(t),
+        }
+    }
+}
+
+impl core::convert::From<X86_64> for u32 {
+    fn from(t: X86_64) -> u32 {
+        match t {
+            X86_64::None           => 0,
+            X86_64::Abs64          => 1,
+            X86_64::Pc32           => 2,
+            X86_64::Got32          => 3,
+            X86_64::Plt32          => 4,
+            X86_64::Copy           => 5,
+            X86_64::GlobDat        => 6,
+            X86_64::JumpSlot       => 7,
+            X86_64::Relative       => 8,
+            X86_64::Gotpcrel       => 9,
+            X86_64::Abs32          => 10,
+            X86_64::Abs32s         => 11,
+            X86_64::Abs16          => 12,
+            X86_64::Pc16           => 13,
+            X86_64::Abs8           => 14,
+            X86_64::Pc8            => 15,
+            X86_64::Dtpmod64       => 16,
+            X86_64::Dtpoff64       => 17,
+            X86_64::Tpoff64        => 18,
+            X86_64::Tlsgd          => 19,
+            X86_64::Tlsld          => 20,
+            X86_64::Dtpoff32       => 21,
+            X86_64::Gottpoff       => 22,
+            X86_64::Tpoff32        => 23,
+            X86_64::Pc64           => 24,
+            X86_64::Gotoff64       => 25,
+            X86_64::Gotpc32        => 26,
+            X86_64::Got64          => 27,
+            X86_64::Gotpcrel64     => 28,
+            X86_64::Gotpc64        => 29,
+            X86_64::Gotplt64       => 30,
+            X86_64::Pltoff64       => 31,
+            X86_64::Size32         => 32,
+            X86_64::Size64         => 33,
+            X86_64::Gotpc32Tlsdesc => 34,
+            X86_64::TlsdescCall    => 35,
+            X86_64::Tlsdesc        => 36,
+            X86_64::Irelative      => 37,
+            X86_64::Relative64     => 38,
+            X86_64::Gotpcrelx      => 41,
+            X86_64::RexGotpcrelx   => 42,
+
+            X86_64::Unknown(t) => t,
+        }
+    }
+}
+
+impl core::fmt::Display for X86_64 {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        let s = match *self {
+            X86_64::None           => "R_X86_64_NONE",
+            X86_64::Abs64          => "R_X86_64_64",
+            X86_64::Pc32           => "R_X86_64_PC32",
+            X86_64::Got32          => "R_X86_64_GOT32",
+            X86_64::Plt32          => "R_X86_64_PLT32",
+            X86_64::Copy           => "R_X86_64_COPY",
+            X86_64::GlobDat        => "R_X86_64_GLOB_DAT",
+            X86_64::JumpSlot       => "R_X86_64_JUMP_SLOT",
+            X86_64::Relative       => "R_X86_64_RELATIVE",
+            X86_64::Gotpcrel       => "R_X86_64_GOTPCREL",
+            X86_64::Abs32          => "R_X86_64_32",
+            X86_64::Abs32s         => "R_X86_64_32S",
+            X86_64::Abs16          => "R_X86_64_16",
+            X86_64::Pc16           => "R_X86_64_PC16",
+            X86_64::Abs8           => "R_X86_64_8",
+            X86_64::Pc8            => "R_X86_64_PC8",
+            X86_64::Dtpmod64       => "R_X86_64_DTPMOD64",
+            X86_64::Dtpoff64       => "R_X86_64_DTPOFF64",
+            X86_64::Tpoff64        => "R_X86_64_TPOFF64",
+            X86_64::Tlsgd          => "R_X86_64_TLSGD",
+            X86_64::Tlsld          => "R_X86_64_TLSLD",
+            X86_64::Dtpoff32       => "R_X86_64_DTPOFF32",
+            X86_64::Gottpoff       => "R_X86_64_GOTTPOFF",
+            X86_64::Tpoff32        => "R_X86_64_TPOFF32",
+            X86_64::Pc64           => "R_X86_64_PC64",
+            X86_64::Gotoff64       => "R_X86_64_GOTOFF64",
+            X86_64::Gotpc32        => "R_X86_64_GOTPC32",
+            X86_64::Got64          => "R_X86_64_GOT64",
+            X86_64::Gotpcrel64     => "R_X86_64_GOTPCREL64",
+            X86_64::Gotpc64        => "R_X86_64_GOTPC64",
+            X86_64::Gotplt64       => "R_X86_64_GOTPLT64",
+            X86_64::Pltoff64       => "R_X86_64_PLTOFF64",
+            X86_64::Size32         => "R_X86_64_SIZE32",
+            X86_64::Size64         => "R_X86_64_SIZE64",
+            X86_64::Gotpc32Tlsdesc => "R_X86_64_GOTPC32_TLSDESC",
+            X86_64::TlsdescCall    => "R_X86_64_TLSDESC_CALL",
+            X86_64::Tlsdesc        => "R_X86_64_TLSDESC",
+            X86_64::Irelative      => "R_X86_64_IRELATIVE",
+            X86_64::Relative64     => "R_X86_64_RELATIVE64",
+            X86_64::Gotpcrelx      => "R_X86_64_GOTPCRELX",
+            X86_64::RexGotpcrelx   => "R_X86_64_REX_GOTPCRELX",
+
+            X86_64::Unknown(t) => return write!(f, "Unknown ({})", t),
+        };
+
+        write!(f, "{}", s)
+    }
+}
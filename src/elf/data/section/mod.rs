@@ -11,16 +11,42 @@ mod sectiontype;
 pub use id::SectionID;
 pub use sectiontype::SectionType;
 
-use crate::common::address::Address;
+use crate::{
+    common::address::Address,
+    error::{ Error, Table },
+};
+
+use std::sync::Arc;
+
+
+
+/// First reserved section index (`SHN_LORESERVE`).
+/// Section indices from this value onwards do not fit in the 16 bit fields.
+pub const LORESERVE: u32 = 0xFF00;
+
+/// Escape value of section indices stored elsewhere (`SHN_XINDEX`).
+pub const XINDEX: u32 = 0xFFFF;
+
+/// Section flag of the sections that occupy memory during execution (`SHF_ALLOC`).
+pub const ALLOC: u64 = 0x2;
+
+/// Section flag marking the extra information as a section index (`SHF_INFO_LINK`).
+pub const INFO_LINK: u64 = 0x40;
+
+/// Section flag of the sections whose contents start with a compression header (`SHF_COMPRESSED`).
+pub const COMPRESSED: u64 = 0x800;
 
 
 
 /// A common section header structure. Will be instantiated by each implementator.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct SectionHeader {
     /// Section name.
     pub(super) name: String,
 
+    /// Raw bytes of the name, only kept if they are not valid UTF-8.
+    pub(super) rawname: Option<Vec<u8>>,
+
     /// Section name offset in the .shstrtab section.
     pub(super) nameidx: u32,
 
@@ -51,11 +77,17 @@ pub struct SectionHeader {
 
     /// Size in bytes of the entries in the section (for fixed sized entries).
     pub(super) entrysize: Address,
+
+    /// Contents of the section, if they were added or replaced after parsing.
+    pub(super) data: Option<Arc<[u8]>>,
+
+    /// `true` if the section is mapped by a segment and must keep its file offset.
+    pub(super) mapped: bool,
 }
 
 impl SectionHeader {
     /// Parses the given slice of data into an ELF file header.
-    pub fn parse<R: AsRef<[u8]>, const INC: usize>(raw: R, read: fn(&[u8]) -> Address, read32: fn(&[u8]) -> u32) -> Result<Self, ()> {
+    pub fn parse<R: AsRef<[u8]>, const INC: usize>(raw: R, read: fn(&[u8]) -> Address, read32: fn(&[u8]) -> u32) -> Result<Self, Error> {
         // Full header size constant.
         let hsize: usize = 16 + ( 6 * INC );
 
@@ -64,7 +96,7 @@ impl SectionHeader {
 
         // Check there is minimum length.
         if raw.len() < hsize {
-            return Err(());
+            return Err( Error::BadHeaderSize { table: Table::SectionHeaders, expected: hsize, found: raw.len() } );
         }
 
         // Get the section name index.
@@ -108,10 +140,11 @@ impl SectionHeader {
         let entrysize = read( &raw[i..i+INC] );
         i += INC;
 
-        assert_eq!(i, hsize);
+        debug_assert_eq!(i, hsize);
 
         Ok(Self {
             name: String::new(),
+            rawname: None,
             nameidx,
             sectiontype,
             flags,
@@ -122,14 +155,93 @@ impl SectionHeader {
             info,
             alignment,
             entrysize,
+            data: None,
+            mapped: false,
         })
     }
 
+    /// Creates an empty section with the given name and type.
+    /// All the addresses of the section have the width of the given address.
+    pub(super) fn new(name: &str, sectiontype: SectionType, zero: Address) -> Self {
+        Self {
+            name: String::from( name ),
+            rawname: None,
+            nameidx: 0,
+            sectiontype,
+            flags: zero.with(0),
+            vaddr: zero.with(0),
+            offset: zero.with(0),
+            filesize: zero.with(0),
+            link: 0,
+            info: 0,
+            alignment: zero.with(1),
+            entrysize: zero.with(0),
+            data: None,
+            mapped: false,
+        }
+    }
+
+    /// Writes the section header into the given slice of data.
+    /// The slice must be at least as long as a section header of the class.
+    pub fn write<const INC: usize>(&self, raw: &mut [u8], write: fn(&mut [u8], Address), write32: fn(&mut [u8], u32)) {
+        // Write the section name index.
+        write32( &mut raw[0x00..0x04], self.nameidx );
+
+        // Write the section type.
+        write32( &mut raw[0x04..0x08], u32::from(self.sectiontype) );
+
+        // Begin dynamic section.
+        let mut i = 0x08;
+
+        // Write the flags of the section.
+        write( &mut raw[i..i+INC], self.flags );
+        i += INC;
+
+        // Write the virtual address.
+        write( &mut raw[i..i+INC], self.vaddr );
+        i += INC;
+
+        // Write the file offset.
+        write( &mut raw[i..i+INC], self.offset );
+        i += INC;
+
+        // Write the file size.
+        write( &mut raw[i..i+INC], self.filesize );
+        i += INC;
+
+        // Write the link information.
+        write32( &mut raw[i..i+4], self.link );
+        i += 4;
+
+        // Write the extra information.
+        write32( &mut raw[i..i+4], self.info );
+        i += 4;
+
+        // Write the alignment.
+        write( &mut raw[i..i+INC], self.alignment );
+        i += INC;
+
+        // Write the entry size.
+        write( &mut raw[i..i+INC], self.entrysize );
+        i += INC;
+
+        debug_assert_eq!(i, 16 + (6 * INC));
+    }
+
     /// Returns a reference to the name of the section.
     pub fn name(&self) -> &String {
         &self.name
     }
 
+    /// Returns the raw bytes of the name of the section.
+    /// They only differ from the name if it is not valid UTF-8, in which case the name is converted lossily.
+    pub fn raw_name(&self) -> &[u8] {
+        match &self.rawname {
+            Some(raw) => raw,
+            _ => self.name.as_bytes(),
+        }
+    }
+
     /// Returns the section size.
     pub fn filesize(&self) -> Address {
         self.filesize
@@ -140,24 +252,112 @@ impl SectionHeader {
         self.vaddr
     }
 
+    /// Returns the section type.
+    pub fn sectiontype(&self) -> SectionType {
+        self.sectiontype
+    }
+
+    /// Returns the section flags.
+    pub fn flags(&self) -> Address {
+        self.flags
+    }
+
+    /// Returns the offset of the section in the file image.
+    pub fn offset(&self) -> Address {
+        self.offset
+    }
+
+    /// Returns the index of the associated section.
+    pub fn link(&self) -> u32 {
+        self.link
+    }
+
+    /// Returns the extra information of the section.
+    pub fn info(&self) -> u32 {
+        self.info
+    }
+
+    /// Returns the section alignment.
+    pub fn alignment(&self) -> Address {
+        self.alignment
+    }
+
+    /// Returns the size of the entries of the section.
+    pub fn entrysize(&self) -> Address {
+        self.entrysize
+    }
+
+    /// Sets the name of the section.
+    /// The section name table is regenerated when the object is written.
+    pub fn set_name(&mut self, name: &str) {
+        self.name = String::from( name );
+        self.rawname = None;
+    }
+
+    /// Sets the section flags.
+    pub fn set_flags(&mut self, flags: u64) {
+        self.flags = self.flags.with( flags );
+    }
+
+    /// Sets the section virtual address.
+    pub fn set_vaddr(&mut self, vaddr: u64) {
+        self.vaddr = self.vaddr.with( vaddr );
+    }
+
+    /// Sets the section alignment.
+    /// Sections that are not mapped by a segment are placed at an offset with this alignment.
+    pub fn set_alignment(&mut self, alignment: u64) {
+        self.alignment = self.alignment.with( alignment );
+    }
+
+    /// Sets the size of the entries of the section.
+    pub fn set_entrysize(&mut self, entrysize: u64) {
+        self.entrysize = self.entrysize.with( entrysize );
+    }
+
+    /// Sets the index of the associated section.
+    pub fn set_link(&mut self, link: u32) {
+        self.link = link;
+    }
+
+    /// Sets the extra information of the section.
+    pub fn set_info(&mut self, info: u32) {
+        self.info = info;
+    }
+
+    /// Returns `true` if the extra information of the section is a section index.
+    pub(super) fn infolink(&self) -> bool {
+        match self.sectiontype {
+            SectionType::Relocation | SectionType::RelocationA => true,
+            _ => (u64::from(self.flags) & INFO_LINK) != 0,
+        }
+    }
+
     /// Grabs the name of this section from the given raw strings.
-    pub(super) fn rename(&mut self, names: &[u8]) {
+    /// Returns `false` if the name index is outside of the given strings.
+    pub(super) fn rename(&mut self, names: &[u8]) -> bool {
         use core::ffi::CStr;
 
+        // Get the referenced raw string.
+        let raw = match names.get(self.nameidx as usize..) {
+            None => return false,
+            Some(raw) => raw,
+        };
+
         // Get the referenced C string.
-        let cstr = match CStr::from_bytes_until_nul( &names[self.nameidx as usize..] ) {
-            Err(_) => return,
+        let cstr = match CStr::from_bytes_until_nul( raw ) {
+            Err(_) => return false,
             Ok(s) => s,
         };
 
-        // Attempt to transform into a Rust str.
-        let string = match cstr.to_str() {
-            Err(_) => return,
-            Ok(s) => s,
+        // Create the name string, keeping the raw bytes of names that are not valid UTF-8.
+        self.name = String::from( cstr.to_string_lossy() );
+        self.rawname = match cstr.to_str() {
+            Err(_) => Some( cstr.to_bytes().to_vec() ),
+            Ok(_) => None,
         };
 
-        // Create the name string.
-        self.name = String::from( string );
+        true
     }
 
     /// Creates a pretty print of the section's information.
@@ -208,6 +408,10 @@ impl super::HasContent for SectionHeader {
     fn size(&self) -> usize {
         usize::from( self.filesize )
     }
+
+    fn data(&self) -> Option<Arc<[u8]>> {
+        self.data.clone()
+    }
 }
 
 impl super::HasContent for &SectionHeader {
@@ -222,4 +426,8 @@ impl super::HasContent for &SectionHeader {
     fn size(&self) -> usize {
         usize::from( self.filesize )
     }
+
+    fn data(&self) -> Option<Arc<[u8]>> {
+        self.data.clone()
+    }
 }
@@ -2,7 +2,7 @@
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum SectionType {
-    /// Null section type, used also for unknown.
+    /// Null section type.
     Null,
 
     /// Program data section.
@@ -56,8 +56,23 @@ pub enum SectionType {
     /// Number of defined types.
     Num,
 
+    /// GNU symbol hash table.
+    GnuHash,
+
+    /// GNU version definitions.
+    VerDef,
+
+    /// GNU version requirements.
+    VerNeed,
+
+    /// GNU version of each dynamic symbol.
+    VerSym,
+
     /// OS Specific.
     OS(u32),
+
+    /// Unknown section type, with its raw value.
+    Other(u32),
 }
 
 
@@ -84,20 +99,57 @@ impl core::convert::From<u32> for SectionType {
             0x11 => Group,
             0x12 => SymbolTableX,
             0x13 => Num,
+            0x6FFFFFF6 => GnuHash,
+            0x6FFFFFFD => VerDef,
+            0x6FFFFFFE => VerNeed,
+            0x6FFFFFFF => VerSym,
             x => match x {
                 0x60000000..=0xFFFFFFFF => OS(x),
-                _ => Null,
+                _ => Other(x),
             },
         }
     }
 }
 
+impl core::convert::From<SectionType> for u32 {
+    fn from(t: SectionType) -> u32 {
+        use SectionType::*;
+
+        match t {
+            Null          => 0x00,
+            ProgramData   => 0x01,
+            SymbolTable   => 0x02,
+            StringTable   => 0x03,
+            RelocationA   => 0x04,
+            Hash          => 0x05,
+            Dynamic       => 0x06,
+            Notes         => 0x07,
+            Bss           => 0x08,
+            Relocation    => 0x09,
+            SharedLib     => 0x0A,
+            DynamicSymbol => 0x0B,
+            Init          => 0x0E,
+            Fini          => 0x0F,
+            PreInit       => 0x10,
+            Group         => 0x11,
+            SymbolTableX  => 0x12,
+            Num           => 0x13,
+            GnuHash       => 0x6FFFFFF6,
+            VerDef        => 0x6FFFFFFD,
+            VerNeed       => 0x6FFFFFFE,
+            VerSym        => 0x6FFFFFFF,
+            OS(x)         => x,
+            Other(x)      => x,
+        }
+    }
+}
+
 impl core::fmt::Display for SectionType {
     fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
         use SectionType::*;
 
         let arg = match *self {
-            Null          => String::from("Null/Unused/Unknown"),
+            Null          => String::from("Null/Unused"),
             ProgramData   => String::from("Program data"),
             SymbolTable   => String::from("Symbol table"),
             StringTable   => String::from("String table"),
@@ -115,7 +167,12 @@ impl core::fmt::Display for SectionType {
             Group         => String::from("Section group"),
             SymbolTableX  => String::from("Extended section indices"),
             Num           => String::from("Number of defined types"),
+            GnuHash       => String::from("GNU symbol hash table"),
+            VerDef        => String::from("GNU version definitions"),
+            VerNeed       => String::from("GNU version requirements"),
+            VerSym        => String::from("GNU symbol versions"),
             OS(x)         => format!("OS Specific ({})", x),
+            Other(x)      => format!("Unknown ({})", x),
         };
 
         write!(f, "{}", arg)
@@ -12,6 +12,15 @@ pub enum Bind {
 	/// Weak bind.
 	Weak,
 
+	/// OS specific.
+	OSLow,
+
+	/// OS specific.
+	OSMid,
+
+	/// OS specific.
+	OSHigh,
+
 	/// Processor specific.
 	ProcessorLow,
 
@@ -21,7 +30,8 @@ pub enum Bind {
 	/// Processor specific.
 	ProcessorHigh,
 
-    None,
+    /// Unknown bind, with its raw value.
+    Other(u8),
 }
 
 impl core::convert::From<u8> for Bind {
@@ -31,11 +41,35 @@ impl core::convert::From<u8> for Bind {
             1 => Bind::Global,
             2 => Bind::Weak,
 
+            10 => Bind::OSLow,
+            11 => Bind::OSMid,
+            12 => Bind::OSHigh,
+
             13 => Bind::ProcessorLow,
             14 => Bind::ProcessorMid,
             15 => Bind::ProcessorHigh,
 
-            _ => Bind::None,
+            _ => Bind::Other(u),
+        }
+    }
+}
+
+impl core::convert::From<Bind> for u8 {
+    fn from(b: Bind) -> u8 {
+        match b {
+            Bind::Local  => 0,
+            Bind::Global => 1,
+            Bind::Weak   => 2,
+
+            Bind::OSLow  => 10,
+            Bind::OSMid  => 11,
+            Bind::OSHigh => 12,
+
+            Bind::ProcessorLow  => 13,
+            Bind::ProcessorMid  => 14,
+            Bind::ProcessorHigh => 15,
+
+            Bind::Other(u) => u,
         }
     }
 }
@@ -47,11 +81,15 @@ impl core::fmt::Display for Bind {
             Bind::Global => "Global",
             Bind::Weak  => "Weak",
 
+            Bind::OSLow  => "OS 10",
+            Bind::OSMid  => "OS 11",
+            Bind::OSHigh => "OS 12",
+
             Bind::ProcessorLow  => "Processor 13",
             Bind::ProcessorMid  => "Processor 14",
             Bind::ProcessorHigh => "Processor 15",
 
-            Bind::None => "No binding",
+            Bind::Other(u) => return write!(f, "Unknown {}", u),
         };
 
         write!(f, "{}", s)
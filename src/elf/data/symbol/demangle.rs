@@ -0,0 +1,54 @@
+//! Demangling of symbol names.
+//! Supports the legacy Rust mangling, the Rust v0 mangling and the Itanium C++ ABI mangling.
+
+
+
+/// Demangles the given symbol name, if it is a mangled Rust or C++ name.
+/// If `hash` is not set, the `::h<hash>` suffix of legacy Rust names and the crate disambiguators of
+/// v0 names are removed.
+/// The `@VERSION` suffix that the linker appends to versioned symbols is kept.
+pub(super) fn demangle(name: &str, hash: bool) -> Option<String> {
+    // Split the version suffix, as it is not part of the mangled name.
+    let (name, version) = match name.find('@') {
+        Some(at) => name.split_at(at),
+        _ => (name, ""),
+    };
+
+    rust(name, hash).or_else(|| cpp(name))
+        .map(|demangled| demangled + version)
+}
+
+/// Demangles a legacy or v0 Rust symbol name.
+#[cfg(feature = "demangle")]
+fn rust(name: &str, hash: bool) -> Option<String> {
+    let demangled = rustc_demangle::try_demangle(name).ok()?;
+
+    match hash {
+        true => Some( format!("{}", demangled) ),
+        _ => Some( format!("{:#}", demangled) ),
+    }
+}
+
+/// The Rust demangler is not enabled.
+#[cfg(not(feature = "demangle"))]
+fn rust(_: &str, _: bool) -> Option<String> {
+    None
+}
+
+/// Demangles an Itanium C++ ABI symbol name.
+#[cfg(feature = "demangle")]
+fn cpp(name: &str) -> Option<String> {
+    // Only mangled names are parsed, as plain names may be valid nested names.
+    if !name.starts_with("_Z") {
+        return None;
+    }
+
+    cpp_demangle::Symbol::new(name).ok()?
+        .demangle( &cpp_demangle::DemangleOptions::default() ).ok()
+}
+
+/// The C++ demangler is not enabled.
+#[cfg(not(feature = "demangle"))]
+fn cpp(_: &str) -> Option<String> {
+    None
+}
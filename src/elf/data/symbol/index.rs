@@ -0,0 +1,73 @@
+//! Name index of the symbols.
+
+
+
+use super::{ Symbol, SymbolType };
+
+use std::{
+    collections::HashMap,
+    sync::Arc,
+};
+
+
+
+/// An index of the symbols of an object by name and by demangled name.
+/// Each symbol is also associated to the file symbol (`STT_FILE`) that precedes it in the symbol table.
+#[derive(Debug, Clone, Default)]
+pub struct SymbolIndex {
+    /// Indices of the symbols with each name, in symbol table order.
+    names: HashMap<String, Vec<usize>>,
+
+    /// Indices of the mangled symbols with each demangled name (without hash), in symbol table order.
+    demangled: HashMap<String, Vec<usize>>,
+
+    /// Index of the file symbol that precedes each symbol.
+    files: Vec<Option<usize>>,
+}
+
+impl SymbolIndex {
+    /// Builds the index of the given symbol table.
+    /// The null symbol, the file symbols and the symbols without a name are not indexed.
+    pub fn new(symbols: &[Arc<Symbol>]) -> Self {
+        let mut names: HashMap<String, Vec<usize>> = HashMap::new();
+        let mut demangled: HashMap<String, Vec<usize>> = HashMap::new();
+        let mut files = Vec::with_capacity( symbols.len() );
+        let mut file = None;
+
+        for (index, symbol) in symbols.iter().enumerate() {
+            if symbol.symboltype == SymbolType::File {
+                file = Some( index );
+            } else if (index != 0) && !symbol.name.is_empty() {
+                names.entry( symbol.name.clone() ).or_default().push( index );
+
+                if let Some(name) = super::demangle::demangle(&symbol.name, false) {
+                    demangled.entry( name ).or_default().push( index );
+                }
+            }
+
+            files.push( file );
+        }
+
+        Self { names, demangled, files }
+    }
+
+    /// Returns the indices of the symbols with the given name, in symbol table order.
+    pub fn get(&self, name: &str) -> &[usize] {
+        self.names.get(name).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    /// Returns the indices of the symbols with the given name or demangled name (without hash), in symbol table order.
+    pub fn get_demangled(&self, name: &str) -> Vec<usize> {
+        let mut indices = self.get(name).to_vec();
+        indices.extend_from_slice( self.demangled.get(name).map(Vec::as_slice).unwrap_or(&[]) );
+        indices.sort_unstable();
+        indices.dedup();
+
+        indices
+    }
+
+    /// Returns the index of the file symbol that precedes the symbol at the given index.
+    pub fn file(&self, index: usize) -> Option<usize> {
+        self.files.get(index).copied().flatten()
+    }
+}
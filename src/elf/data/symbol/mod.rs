@@ -3,23 +3,44 @@
 
 
 mod bind;
+mod demangle;
+mod index;
 mod symtype;
 
 
 
 pub use bind::Bind;
+pub use index::SymbolIndex;
 pub use symtype::SymbolType;
 
-use crate::common::address::Address;
+use crate::{
+    common::address::Address,
+    error::{ Error, Table },
+};
+
+use super::version::SymbolVersion;
+
+use std::borrow::Cow;
+
+
+
+/// Section index of absolute symbols (`SHN_ABS`).
+pub const ABS: u32 = 0xFFF1;
+
+/// Section index of common symbols (`SHN_COMMON`).
+pub const COMMON: u32 = 0xFFF2;
 
 
 
 /// A common section header structure. Will be instantiated by each implementator.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Symbol {
     /// Section name.
     pub(super) name: String,
 
+    /// Raw bytes of the name, only kept if they are not valid UTF-8.
+    pub(super) rawname: Option<Vec<u8>>,
+
     /// Section name offset in the .shstrtab section.
     pub(super) nameidx: u32,
 
@@ -36,16 +57,57 @@ pub struct Symbol {
     /// The type of the symbol.
     pub(super) symboltype: SymbolType,
 
+    /// Other information of the symbol (visibility).
+    pub(super) other: u8,
+
     /// Index of the related section.
-    pub(super) shidx: u16,
+    /// Resolved through the extended section indices (`SHT_SYMTAB_SHNDX`) when it overflows.
+    pub(super) shidx: u32,
+
+    /// `true` if the section index is stored in the extended section indices.
+    pub(super) extended: bool,
+
+    /// Index of the symbol in the parsed symbol table.
+    /// Used to remap the references to the symbol when the table is written again.
+    pub(super) original: Option<u32>,
+
+    /// GNU version of the symbol, only for dynamic symbols.
+    pub(super) version: Option<SymbolVersion>,
 }
 
 impl Symbol {
+    /// Creates an undefined symbol with the given name, binding and type.
+    /// The value and size are set to zero.
+    pub fn new(name: &str, bind: Bind, symboltype: SymbolType) -> Self {
+        Self {
+            name: String::from( name ),
+            rawname: None,
+            nameidx: 0,
+            value: Address::U64(0),
+            size: Address::U64(0),
+            bind,
+            symboltype,
+            other: 0,
+            shidx: 0,
+            extended: false,
+            original: None,
+            version: None,
+        }
+    }
+
     /// Parses the given slice of data into an ELF file header.
-    pub fn parse<R: AsRef<[u8]>, const INC: usize>(raw: R, read: fn(&[u8]) -> Address, read16: fn(&[u8]) -> u16, read32: fn(&[u8]) -> u32) -> Result<Self, ()> {
+    pub fn parse<R: AsRef<[u8]>, const INC: usize>(raw: R, read: fn(&[u8]) -> Address, read16: fn(&[u8]) -> u16, read32: fn(&[u8]) -> u32) -> Result<Self, Error> {
+        // Symbol size constant.
+        let hsize: usize = 8 + (2 * INC);
+
         // Get the slice.
         let raw = raw.as_ref();
 
+        // Check there is minimum length.
+        if raw.len() < hsize {
+            return Err( Error::BadHeaderSize { table: Table::Symbols, expected: hsize, found: raw.len() } );
+        }
+
         // Start dynamic index.
         let mut i = 0;
 
@@ -68,13 +130,14 @@ impl Symbol {
                 i += 1;
 
                 // Read the other field.
+                let other = raw[i];
                 i += 1;
 
                 // Read the associated section.
-                let shidx = read16( &raw[i..i+2] );
+                let shidx = u32::from( read16( &raw[i..i+2] ) );
                 i += 2;
 
-                assert_eq!(i, 16);
+                debug_assert_eq!(i, 16);
 
                 // Process the information.
                 let bind = Bind::from( info >> 4 );
@@ -82,12 +145,17 @@ impl Symbol {
 
                 Ok(Self {
                     name: String::new(),
+                    rawname: None,
                     nameidx,
                     value,
                     size,
                     bind,
                     symboltype,
+                    other,
                     shidx,
+                    extended: false,
+                    original: None,
+                    version: None,
                 })
             },
 
@@ -101,10 +169,11 @@ impl Symbol {
                 i += 1;
 
                 // Read the other field.
+                let other = raw[i];
                 i += 1;
 
                 // Read the associated section.
-                let shidx = read16( &raw[i..i+2] );
+                let shidx = u32::from( read16( &raw[i..i+2] ) );
                 i += 2;
 
                 // Read the value.
@@ -115,7 +184,7 @@ impl Symbol {
                 let size = read( &raw[i..i+8] );
                 i += 8;
 
-                assert_eq!(i, 24);
+                debug_assert_eq!(i, 24);
 
                 // Process the information.
                 let bind = Bind::from( info >> 4 );
@@ -124,24 +193,118 @@ impl Symbol {
 
                 Ok(Self {
                     name: String::new(),
+                    rawname: None,
                     nameidx,
                     value,
                     size,
                     bind,
                     symboltype,
+                    other,
                     shidx,
+                    extended: false,
+                    original: None,
+                    version: None,
                 })
             },
 
-            _ => Err( () ),
+            // The class is encoded as the number of 32 bit words of an address.
+            _ => Err( Error::UnsupportedClass( (INC / 4) as u8 ) ),
         }
     }
 
+    /// Writes the symbol into the given slice of data.
+    /// The slice must be at least as long as a symbol of the class.
+    pub fn write<const INC: usize>(&self, raw: &mut [u8], write: fn(&mut [u8], Address), write16: fn(&mut [u8], u16), write32: fn(&mut [u8], u32)) {
+        // Build the information.
+        let info = (u8::from(self.bind) << 4) | (u8::from(self.symboltype) & 0xF);
+
+        // Escape the section index if it is stored in the extended section indices.
+        let shidx = match self.extended {
+            true => super::section::XINDEX as u16,
+            _ => self.shidx as u16,
+        };
+
+        // Start dynamic index.
+        let mut i = 0;
+
+        // Write the name index.
+        write32( &mut raw[i..i+4], self.nameidx );
+        i += 4;
+
+        if INC == 4 {
+            // Write the value.
+            write( &mut raw[i..i+4], self.value );
+            i += 4;
+
+            // Write the size.
+            write( &mut raw[i..i+4], self.size );
+            i += 4;
+        }
+
+        // Write the info.
+        raw[i] = info;
+        i += 1;
+
+        // Write the other field.
+        raw[i] = self.other;
+        i += 1;
+
+        // Write the associated section.
+        write16( &mut raw[i..i+2], shidx );
+        i += 2;
+
+        if INC == 8 {
+            // Write the value.
+            write( &mut raw[i..i+8], self.value );
+            i += 8;
+
+            // Write the size.
+            write( &mut raw[i..i+8], self.size );
+            i += 8;
+        }
+
+        debug_assert_eq!(i, 8 + (2 * INC));
+    }
+
     /// Returns a reference to the name of this symbol.
     pub fn name(&self) -> &String {
         &self.name
     }
 
+    /// Returns the raw bytes of the name of this symbol.
+    /// They only differ from the name if it is not valid UTF-8, in which case the name is converted lossily.
+    pub fn raw_name(&self) -> &[u8] {
+        match &self.rawname {
+            Some(raw) => raw,
+            _ => self.name.as_bytes(),
+        }
+    }
+
+    /// Returns the demangled name of this symbol, for legacy Rust, Rust v0 and Itanium C++ names.
+    /// If `hash` is not set, the `::h<hash>` suffix of legacy Rust names is removed.
+    /// Names that are not mangled, or all names if the `demangle` feature is not enabled, are returned unchanged.
+    pub fn demangled(&self, hash: bool) -> Cow<'_, str> {
+        match demangle::demangle(&self.name, hash) {
+            Some(name) => Cow::Owned( name ),
+            _ => Cow::Borrowed( &self.name ),
+        }
+    }
+
+    /// Returns the GNU version of the symbol, if it is a versioned dynamic symbol.
+    pub fn version(&self) -> Option<&SymbolVersion> {
+        self.version.as_ref()
+    }
+
+    /// Returns the name of the symbol followed by its version, as `name@@VERSION` for the default
+    /// version defined by the object and as `name@VERSION` for hidden and required versions.
+    pub fn versioned_name(&self) -> String {
+        match &self.version {
+            Some(version) if version.hidden || version.library.is_some() => format!("{}@{}", self.name, version.name),
+            Some(version) => format!("{}@@{}", self.name, version.name),
+            _ => self.name.clone(),
+        }
+    }
+
     /// Returns `true` if the symbol is an function.
     pub fn is_function(&self) -> bool {
         self.symboltype == SymbolType::Function
@@ -152,24 +315,31 @@ impl Symbol {
         self.symboltype == SymbolType::Object
     }
 
-    /// Grabs the name of this section from the given raw strings.
-    pub(super) fn rename(&mut self, names: &[u8]) {
+    /// Grabs the name of this symbol from the given raw strings.
+    /// Returns `false` if the name index is outside of the given strings.
+    pub(super) fn rename(&mut self, names: &[u8]) -> bool {
         use core::ffi::CStr;
 
+        // Get the referenced raw string.
+        let raw = match names.get(self.nameidx as usize..) {
+            None => return false,
+            Some(raw) => raw,
+        };
+
         // Get the referenced C string.
-        let cstr = match CStr::from_bytes_until_nul( &names[self.nameidx as usize..] ) {
-            Err(_) => return,
+        let cstr = match CStr::from_bytes_until_nul( raw ) {
+            Err(_) => return false,
             Ok(s) => s,
         };
 
-        // Attempt to transform into a Rust str.
-        let string = match cstr.to_str() {
-            Err(_) => return,
-            Ok(s) => s,
+        // Create the name string, keeping the raw bytes of names that are not valid UTF-8.
+        self.name = String::from( cstr.to_string_lossy() );
+        self.rawname = match cstr.to_str() {
+            Err(_) => Some( cstr.to_bytes().to_vec() ),
+            Ok(_) => None,
         };
 
-        // Create the name string.
-        self.name = String::from( string );
+        true
     }
 
     /// Creates a pretty print of the section's information.
@@ -180,6 +350,11 @@ impl Symbol {
         // Section name.
         args += &format!("Symbol \"{}\"\n", self.name);
 
+        // Demangled name, if the name is mangled.
+        if let Cow::Owned(demangled) = self.demangled(false) {
+            args += &format!("  - Demangled: {}\n", demangled);
+        }
+
         // Symbol value and size.
         args += &format!("  - Value: {:X}\n", self.value);
         args += &format!("  - Size : {} bytes\n", self.size);
@@ -213,6 +388,47 @@ impl Symbol {
     pub fn size(&self) -> Address {
         self.size
     }
+
+    /// Returns the index of the related section.
+    pub fn shidx(&self) -> u32 {
+        self.shidx
+    }
+
+    /// Sets the name of the symbol.
+    /// The string table is regenerated when the object is written.
+    pub fn set_name(&mut self, name: &str) {
+        self.name = String::from( name );
+        self.rawname = None;
+    }
+
+    /// Sets the binding of the symbol.
+    /// Local symbols are placed before all other symbols when the object is written.
+    pub fn set_bind(&mut self, bind: Bind) {
+        self.bind = bind;
+    }
+
+    /// Sets the kind of the symbol.
+    pub fn set_kind(&mut self, symboltype: SymbolType) {
+        self.symboltype = symboltype;
+    }
+
+    /// Sets the value of the symbol.
+    pub fn set_value(&mut self, value: u64) {
+        self.value = self.value.with( value );
+    }
+
+    /// Sets the size of the symbol.
+    pub fn set_size(&mut self, size: u64) {
+        self.size = self.size.with( size );
+    }
+
+    /// Sets the index of the related section.
+    /// Indices from `LORESERVE` onwards are stored in the extended section indices,
+    /// except for the absolute (`ABS`) and common (`COMMON`) indices.
+    pub fn set_shidx(&mut self, shidx: u32) {
+        self.shidx = shidx;
+        self.extended = (shidx >= super::section::LORESERVE) && (shidx != ABS) && (shidx != COMMON);
+    }
 }
 
 impl super::HasContent for Symbol {
@@ -3,7 +3,7 @@
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum SymbolType {
-    /// No type / Unknown type.
+    /// No type.
     None,
 
     /// Object.
@@ -18,6 +18,21 @@ pub enum SymbolType {
     /// File.
     File,
 
+    /// Common data object.
+    Common,
+
+    /// Thread local data object.
+    TLS,
+
+    /// OS specific.
+    OSLow,
+
+    /// OS specific.
+    OSMid,
+
+    /// OS specific.
+    OSHigh,
+
     /// Processor specific.
     ProcessorLow,
 
@@ -26,6 +41,9 @@ pub enum SymbolType {
 
     /// Processor specific.
     ProcessorHigh,
+
+    /// Unknown type, with its raw value.
+    Other(u8),
 }
 
 
@@ -36,12 +54,44 @@ impl core::convert::From<u8> for SymbolType {
             2 => SymbolType::Function,
             3 => SymbolType::Section,
             4 => SymbolType::File,
+            5 => SymbolType::Common,
+            6 => SymbolType::TLS,
+
+            10 => SymbolType::OSLow,
+            11 => SymbolType::OSMid,
+            12 => SymbolType::OSHigh,
 
             13 => SymbolType::ProcessorLow,
             14 => SymbolType::ProcessorMid,
             15 => SymbolType::ProcessorHigh,
 
-            _ => SymbolType::None,
+            0 => SymbolType::None,
+
+            _ => SymbolType::Other(u),
+        }
+    }
+}
+
+impl core::convert::From<SymbolType> for u8 {
+    fn from(t: SymbolType) -> u8 {
+        match t {
+            SymbolType::None     => 0,
+            SymbolType::Object   => 1,
+            SymbolType::Function => 2,
+            SymbolType::Section  => 3,
+            SymbolType::File     => 4,
+            SymbolType::Common   => 5,
+            SymbolType::TLS      => 6,
+
+            SymbolType::OSLow  => 10,
+            SymbolType::OSMid  => 11,
+            SymbolType::OSHigh => 12,
+
+            SymbolType::ProcessorLow  => 13,
+            SymbolType::ProcessorMid  => 14,
+            SymbolType::ProcessorHigh => 15,
+
+            SymbolType::Other(u) => u,
         }
     }
 }
@@ -53,12 +103,20 @@ impl core::fmt::Display for SymbolType {
             SymbolType::Function => "Function",
             SymbolType::Section  => "Section",
             SymbolType::File     => "File",
+            SymbolType::Common   => "Common",
+            SymbolType::TLS      => "Thread local",
+
+            SymbolType::OSLow  => "OS 10",
+            SymbolType::OSMid  => "OS 11",
+            SymbolType::OSHigh => "OS 12",
 
             SymbolType::ProcessorLow  => "Processor 13",
             SymbolType::ProcessorMid  => "Processor 14",
             SymbolType::ProcessorHigh => "Processor 15",
 
             SymbolType::None => "No type",
+
+            SymbolType::Other(u) => return write!(f, "Unknown {}", u),
         };
 
         write!(f, "{}", s)
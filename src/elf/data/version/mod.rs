@@ -0,0 +1,229 @@
+//! GNU symbol versioning.
+//! Parsing of the version definitions (`.gnu.version_d`), the version requirements
+//! (`.gnu.version_r`) and the version of each dynamic symbol (`.gnu.version`).
+
+
+
+use crate::error::{ Error, Table };
+
+
+
+/// Flag of the version definition of the object itself.
+pub const VER_FLG_BASE: u16 = 0x1;
+
+/// Flag of a weak version requirement.
+pub const VER_FLG_WEAK: u16 = 0x2;
+
+/// Bit of a version symbol entry that marks the version as hidden.
+pub const VERSYM_HIDDEN: u16 = 0x8000;
+
+
+
+/// The version of a dynamic symbol.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SymbolVersion {
+    /// Name of the version.
+    pub(super) name: String,
+
+    /// `true` if the version is hidden (it is not the default version of the symbol).
+    pub(super) hidden: bool,
+
+    /// Library that provides the version, if the version is required from another object.
+    pub(super) library: Option<String>,
+}
+
+impl SymbolVersion {
+    /// Returns the name of the version.
+    pub fn name(&self) -> &String {
+        &self.name
+    }
+
+    /// Returns `true` if the version is hidden (it is not the default version of the symbol).
+    pub fn hidden(&self) -> bool {
+        self.hidden
+    }
+
+    /// Returns the library that provides the version, if the version is required from another object.
+    pub fn library(&self) -> Option<&String> {
+        self.library.as_ref()
+    }
+}
+
+
+
+/// The versions required from a library (`DT_NEEDED`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VersionRequirement {
+    /// Name of the library.
+    pub(super) library: String,
+
+    /// Versions required from the library.
+    pub(super) versions: Vec<RequiredVersion>,
+}
+
+impl VersionRequirement {
+    /// Returns the name of the library.
+    pub fn library(&self) -> &String {
+        &self.library
+    }
+
+    /// Returns the versions required from the library.
+    pub fn versions(&self) -> &Vec<RequiredVersion> {
+        &self.versions
+    }
+}
+
+/// A version required from a library.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequiredVersion {
+    /// Name of the version.
+    pub(super) name: String,
+
+    /// Index of the version, referenced by the version symbol table.
+    pub(super) index: u16,
+
+    /// Hash of the name of the version.
+    pub(super) hash: u32,
+
+    /// Flags of the requirement.
+    pub(super) flags: u16,
+}
+
+impl RequiredVersion {
+    /// Returns the name of the version.
+    pub fn name(&self) -> &String {
+        &self.name
+    }
+
+    /// Returns the index of the version, referenced by the version symbol table.
+    pub fn index(&self) -> u16 {
+        self.index
+    }
+
+    /// Returns the hash of the name of the version.
+    pub fn hash(&self) -> u32 {
+        self.hash
+    }
+
+    /// Returns `true` if the requirement is weak.
+    pub fn weak(&self) -> bool {
+        (self.flags & VER_FLG_WEAK) != 0
+    }
+}
+
+
+
+/// Parses the given number of version definitions (`Elf_Verdef`) at the given offset of the file.
+/// The names are read from the given string table and its offset in the file.
+/// Returns the index and name of each version, except the version of the object itself.
+pub(super) fn definitions(raw: &[u8], offset: usize, count: usize, strings: (usize, &[u8]), read16: fn(&[u8]) -> u16, read32: fn(&[u8]) -> u32) -> Result<Vec<(u16, String)>, Error> {
+    let mut definitions = Vec::new();
+    let mut entry = offset;
+
+    for index in 0..count {
+        // Read the definition.
+        let data = get(raw, entry, 20).ok_or( Error::OutOfBounds { table: Table::Versions, index, offset: entry } )?;
+
+        let flags = read16( &data[2..4] );
+        let ndx   = read16( &data[4..6] );
+        let aux   = read32( &data[12..16] ) as usize;
+        let next  = read32( &data[16..20] ) as usize;
+
+        // The first auxiliary entry names the version.
+        let auxiliary = entry.checked_add(aux).and_then(|at| get(raw, at, 8)).ok_or( Error::OutOfBounds { table: Table::Versions, index, offset: entry.saturating_add(aux) } )?;
+        let nameidx = read32( &auxiliary[0..4] ) as usize;
+        let name = string(strings.1, nameidx).ok_or( Error::BadNameIndex { table: Table::Versions, index, offset: strings.0.saturating_add(nameidx) } )?;
+
+        if (flags & VER_FLG_BASE) == 0 {
+            definitions.push( (ndx, name) );
+        }
+
+        if next == 0 {
+            break;
+        }
+
+        entry = entry.checked_add(next).ok_or( Error::OutOfBounds { table: Table::Versions, index: index + 1, offset: usize::MAX } )?;
+    }
+
+    Ok( definitions )
+}
+
+/// Parses the given number of version requirements (`Elf_Verneed`) at the given offset of the file.
+/// The names are read from the given string table and its offset in the file.
+pub(super) fn requirements(raw: &[u8], offset: usize, count: usize, strings: (usize, &[u8]), read16: fn(&[u8]) -> u16, read32: fn(&[u8]) -> u32) -> Result<Vec<VersionRequirement>, Error> {
+    let mut requirements = Vec::new();
+    let mut entry = offset;
+
+    for index in 0..count {
+        // Read the requirement.
+        let data = get(raw, entry, 16).ok_or( Error::OutOfBounds { table: Table::Versions, index, offset: entry } )?;
+
+        let cnt  = read16( &data[2..4] ) as usize;
+        let file = read32( &data[4..8] ) as usize;
+        let aux  = read32( &data[8..12] ) as usize;
+        let next = read32( &data[12..16] ) as usize;
+
+        let library = string(strings.1, file).ok_or( Error::BadNameIndex { table: Table::Versions, index, offset: strings.0.saturating_add(file) } )?;
+
+        // Read the required versions.
+        let mut versions = Vec::new();
+        let mut auxiliary = entry.checked_add(aux);
+
+        for _ in 0..cnt {
+            let at = auxiliary.unwrap_or(usize::MAX);
+            let data = get(raw, at, 16).ok_or( Error::OutOfBounds { table: Table::Versions, index, offset: at } )?;
+
+            let nameidx = read32( &data[8..12] ) as usize;
+            let name = string(strings.1, nameidx).ok_or( Error::BadNameIndex { table: Table::Versions, index, offset: strings.0.saturating_add(nameidx) } )?;
+
+            versions.push( RequiredVersion { name, index: read16( &data[6..8] ), hash: read32( &data[0..4] ), flags: read16( &data[4..6] ) } );
+
+            match read32( &data[12..16] ) as usize {
+                0 => break,
+                n => auxiliary = at.checked_add(n),
+            }
+        }
+
+        requirements.push( VersionRequirement { library, versions } );
+
+        if next == 0 {
+            break;
+        }
+
+        entry = entry.checked_add(next).ok_or( Error::OutOfBounds { table: Table::Versions, index: index + 1, offset: usize::MAX } )?;
+    }
+
+    Ok( requirements )
+}
+
+/// Returns the version of a symbol given its raw version symbol entry.
+/// Returns `None` for local and global symbols without a named version.
+pub(super) fn version(versym: u16, definitions: &[(u16, String)], requirements: &[VersionRequirement]) -> Option<SymbolVersion> {
+    let index = versym & !VERSYM_HIDDEN;
+    let hidden = (versym & VERSYM_HIDDEN) != 0;
+
+    if index < 2 {
+        return None;
+    }
+
+    // Look for the version in the definitions of the object.
+    if let Some((_, name)) = definitions.iter().find(|(ndx, _)| *ndx == index) {
+        return Some( SymbolVersion { name: name.clone(), hidden, library: None } );
+    }
+
+    // Look for the version in the requirements of the object.
+    requirements.iter()
+        .find_map(|requirement| requirement.versions.iter().find(|version| version.index == index).map(|version| (requirement, version)))
+        .map(|(requirement, version)| SymbolVersion { name: version.name.clone(), hidden, library: Some( requirement.library.clone() ) })
+}
+
+/// Returns the slice of the given size at the given offset, if it is in the data.
+fn get(raw: &[u8], offset: usize, size: usize) -> Option<&[u8]> {
+    raw.get( offset..offset.checked_add(size)? )
+}
+
+/// Reads a string of the dynamic string table.
+fn string(strings: &[u8], offset: usize) -> Option<String> {
+    let cstr = core::ffi::CStr::from_bytes_until_nul( strings.get(offset..)? ).ok()?;
+    Some( cstr.to_string_lossy().into_owned() )
+}
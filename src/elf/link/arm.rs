@@ -0,0 +1,130 @@
+//! Arm (Aarch32) relocations.
+//! Relocatable files use `Rel` entries, so the addends are read from the relocated instructions.
+
+
+
+use crate::elf::data::relocation::ARM;
+
+use super::Place;
+
+
+
+/// Returns the size in bytes of the field relocated by the given type.
+/// Returns `None` if the relocation type is not supported.
+pub(super) fn size(kind: ARM) -> Option<usize> {
+    match kind {
+        ARM::None | ARM::V4bx => Some(0),
+
+        ARM::Abs32 | ARM::Target1 | ARM::Rel32 | ARM::Prel31 => Some(4),
+
+        ARM::ThmCall | ARM::ThmJump24 => Some(4),
+
+        ARM::ThmMovwAbsNc | ARM::ThmMovtAbs | ARM::MovwAbsNc | ARM::MovtAbs => Some(4),
+
+        _ => None,
+    }
+}
+
+/// Applies the relocation to the given place.
+/// `s` is the address of the symbol and `t` is 1 if it is a Thumb function.
+/// Returns `false` if the value does not fit in the relocated field.
+pub(super) fn apply(kind: ARM, place: &mut Place, s: u32, t: u32, addend: Option<i64>) -> bool {
+    match kind {
+        ARM::Abs32 | ARM::Target1 => {
+            let a = addend.unwrap_or( i64::from( place.read32(0) as i32 ) ) as u32;
+            place.write32(0, s.wrapping_add(a) | t);
+        },
+
+        ARM::Rel32 => {
+            let a = addend.unwrap_or( i64::from( place.read32(0) as i32 ) ) as u32;
+            let x = place.relative( u64::from( s.wrapping_add(a) | t ) );
+            place.write32(0, x as u32);
+        },
+
+        ARM::Prel31 => {
+            // The top bit of the word is not part of the field.
+            let word = place.read32(0);
+            let a = addend.unwrap_or( i64::from( ((word << 1) as i32) >> 1 ) ) as u32;
+            let x = place.relative( u64::from( s.wrapping_add(a) | t ) );
+
+            if !fits(x, 31) {
+                return false;
+            }
+
+            place.write32(0, (word & 0x8000_0000) | (x as u32 & 0x7FFF_FFFF));
+        },
+
+        ARM::ThmCall | ARM::ThmJump24 => {
+            let (hi, lo) = (u32::from( place.read16(0) ), u32::from( place.read16(2) ));
+
+            // Decode the 25 bit branch offset (S:I1:I2:imm10:imm11:0).
+            let a = addend.unwrap_or_else(|| {
+                let sign = (hi >> 10) & 1;
+                let i1 = !((lo >> 13) ^ sign) & 1;
+                let i2 = !((lo >> 11) ^ sign) & 1;
+                let imm = (sign << 24) | (i1 << 23) | (i2 << 22) | ((hi & 0x3FF) << 12) | ((lo & 0x7FF) << 1);
+
+                i64::from( ((imm << 7) as i32) >> 7 )
+            }) as u32;
+
+            let x = place.relative( u64::from( s.wrapping_add(a) | t ) );
+
+            if !fits(x, 25) {
+                return false;
+            }
+
+            // Encode the offset, dropping the Thumb bit.
+            let x = (x as u32) & !1;
+            let sign = (x >> 24) & 1;
+            let j1 = (!(x >> 23) & 1) ^ sign;
+            let j2 = (!(x >> 22) & 1) ^ sign;
+
+            place.write16(0, ((hi & 0xF800) | (sign << 10) | ((x >> 12) & 0x3FF)) as u16);
+            place.write16(2, ((lo & 0xD000) | (j1 << 13) | (j2 << 11) | ((x >> 1) & 0x7FF)) as u16);
+        },
+
+        ARM::ThmMovwAbsNc | ARM::ThmMovtAbs => {
+            let (hi, lo) = (u32::from( place.read16(0) ), u32::from( place.read16(2) ));
+
+            // Decode the 16 bit immediate (imm4:i:imm3:imm8).
+            let a = addend.unwrap_or_else(|| {
+                let imm = ((hi & 0xF) << 12) | (((hi >> 10) & 1) << 11) | (((lo >> 12) & 0x7) << 8) | (lo & 0xFF);
+                i64::from( imm as u16 as i16 )
+            }) as u32;
+
+            let imm = match kind {
+                ARM::ThmMovwAbsNc => (s.wrapping_add(a) | t) & 0xFFFF,
+                _ => s.wrapping_add(a) >> 16,
+            };
+
+            place.write16(0, ((hi & 0xFBF0) | (((imm >> 11) & 1) << 10) | (imm >> 12)) as u16);
+            place.write16(2, ((lo & 0x8F00) | (((imm >> 8) & 0x7) << 12) | (imm & 0xFF)) as u16);
+        },
+
+        ARM::MovwAbsNc | ARM::MovtAbs => {
+            let word = place.read32(0);
+
+            // Decode the 16 bit immediate (imm4:imm12).
+            let a = addend.unwrap_or_else(|| {
+                let imm = ((word >> 4) & 0xF000) | (word & 0xFFF);
+                i64::from( imm as u16 as i16 )
+            }) as u32;
+
+            let imm = match kind {
+                ARM::MovwAbsNc => (s.wrapping_add(a) | t) & 0xFFFF,
+                _ => s.wrapping_add(a) >> 16,
+            };
+
+            place.write32(0, (word & 0xFFF0_F000) | ((imm & 0xF000) << 4) | (imm & 0xFFF));
+        },
+
+        _ => (),
+    }
+
+    true
+}
+
+/// Returns `true` if the value fits in a signed field of the given number of bits.
+fn fits(value: i64, bits: u32) -> bool {
+    (-(1 << (bits - 1))..(1 << (bits - 1))).contains(&value)
+}
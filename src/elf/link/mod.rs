@@ -0,0 +1,482 @@
+//! Static relocation of ELF objects.
+//! Links a relocatable object (`ET_REL`) to a fixed address, producing a flat
+//! image of its allocated sections and a report of what could not be linked.
+
+
+
+mod arm;
+mod riscv;
+
+
+
+use crate::error::{ Error, Table };
+
+use super::data::{
+    ELFData, Symbol,
+    header::{ Architecture, FileType },
+    relocation::{ RelocationType, Relocation },
+    section::{ SectionType, ALLOC },
+    symbol::{ Bind, SymbolType, ABS },
+};
+
+use std::collections::HashMap;
+
+
+
+/// A section of the object placed at a fixed address.
+#[derive(Debug, Clone)]
+pub struct PlacedSection {
+    /// Index of the section in the object.
+    pub(super) index: usize,
+
+    /// Name of the section.
+    pub(super) name: String,
+
+    /// Address assigned to the section.
+    pub(super) address: u64,
+
+    /// Size of the section in memory.
+    pub(super) size: u64,
+
+    /// `true` if the section has no contents in the image (`SHT_NOBITS`).
+    pub(super) nobits: bool,
+}
+
+impl PlacedSection {
+    /// Returns the index of the section in the object.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Returns the name of the section.
+    pub fn name(&self) -> &String {
+        &self.name
+    }
+
+    /// Returns the address assigned to the section.
+    pub fn address(&self) -> u64 {
+        self.address
+    }
+
+    /// Returns the size of the section in memory.
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    /// Returns `true` if the section has no contents in the image (`SHT_NOBITS`).
+    pub fn nobits(&self) -> bool {
+        self.nobits
+    }
+}
+
+
+
+/// A relocation that could not be applied.
+#[derive(Debug, Clone)]
+pub struct RelocationIssue {
+    /// Name of the relocated section.
+    pub(super) section: String,
+
+    /// Address of the relocated location.
+    pub(super) address: u64,
+
+    /// Type of the relocation.
+    pub(super) kind: RelocationType,
+
+    /// Name of the referenced symbol.
+    pub(super) symbol: String,
+}
+
+impl RelocationIssue {
+    /// Returns the name of the relocated section.
+    pub fn section(&self) -> &String {
+        &self.section
+    }
+
+    /// Returns the address of the relocated location.
+    pub fn address(&self) -> u64 {
+        self.address
+    }
+
+    /// Returns the type of the relocation.
+    pub fn kind(&self) -> RelocationType {
+        self.kind
+    }
+
+    /// Returns the name of the referenced symbol.
+    pub fn symbol(&self) -> &String {
+        &self.symbol
+    }
+}
+
+impl core::fmt::Display for RelocationIssue {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "{} against \"{}\" at 0x{:X} ({})", self.kind, self.symbol, self.address, self.section)
+    }
+}
+
+
+
+/// Report of the symbols and relocations that could not be linked.
+#[derive(Debug, Clone, Default)]
+pub struct LinkReport {
+    /// Names of the referenced symbols that are not defined in the object nor in the symbol map.
+    pub(super) unresolved: Vec<String>,
+
+    /// Relocations whose value does not fit in the relocated field.
+    pub(super) overflowed: Vec<RelocationIssue>,
+
+    /// Relocations of a type the linker does not support.
+    pub(super) unsupported: Vec<RelocationIssue>,
+}
+
+impl LinkReport {
+    /// Returns the names of the referenced symbols that could not be resolved.
+    pub fn unresolved(&self) -> &Vec<String> {
+        &self.unresolved
+    }
+
+    /// Returns the relocations whose value does not fit in the relocated field.
+    /// The relocated locations are left unchanged.
+    pub fn overflowed(&self) -> &Vec<RelocationIssue> {
+        &self.overflowed
+    }
+
+    /// Returns the relocations of a type the linker does not support.
+    /// The relocated locations are left unchanged.
+    pub fn unsupported(&self) -> &Vec<RelocationIssue> {
+        &self.unsupported
+    }
+
+    /// Returns `true` if all symbols were resolved and all relocations were applied.
+    pub fn complete(&self) -> bool {
+        self.unresolved.is_empty() && self.overflowed.is_empty() && self.unsupported.is_empty()
+    }
+
+    /// Creates a pretty print of the report.
+    pub fn prettyprint(&self) -> String {
+        // Create output string.
+        let mut args = String::new();
+
+        for name in &self.unresolved {
+            args += &format!("Unresolved symbol \"{}\"\n", name);
+        }
+
+        for issue in &self.overflowed {
+            args += &format!("Relocation overflow: {}\n", issue);
+        }
+
+        for issue in &self.unsupported {
+            args += &format!("Unsupported relocation: {}\n", issue);
+        }
+
+        args
+    }
+}
+
+
+
+/// A relocatable object linked to a fixed address.
+#[derive(Debug, Clone)]
+pub struct LinkedImage {
+    /// Address of the first byte of the image.
+    pub(super) base: u64,
+
+    /// Contents of the sections with data, from the base address on.
+    pub(super) image: Vec<u8>,
+
+    /// Size in memory of all the placed sections, including those without data.
+    pub(super) size: u64,
+
+    /// The placed sections, in address order.
+    pub(super) sections: Vec<PlacedSection>,
+
+    /// Addresses of the global and weak symbols defined in the object.
+    pub(super) symbols: HashMap<String, u64>,
+
+    /// The symbols and relocations that could not be linked.
+    pub(super) report: LinkReport,
+}
+
+impl LinkedImage {
+    /// Returns the address of the first byte of the image.
+    pub fn base(&self) -> u64 {
+        self.base
+    }
+
+    /// Returns the flat image of the sections with data.
+    /// Sections without data (`SHT_NOBITS`) are placed after the image and must be zeroed by the loader.
+    pub fn image(&self) -> &[u8] {
+        &self.image
+    }
+
+    /// Consumes the linked object and returns its flat image.
+    pub fn into_image(self) -> Vec<u8> {
+        self.image
+    }
+
+    /// Returns the size in memory of all the placed sections, including those without data.
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    /// Returns the placed sections, in address order.
+    pub fn sections(&self) -> &Vec<PlacedSection> {
+        &self.sections
+    }
+
+    /// Returns the address of the global or weak symbol with the given name defined in the object.
+    pub fn symbol(&self, name: &str) -> Option<u64> {
+        self.symbols.get(name).cloned()
+    }
+
+    /// Returns the report of the symbols and relocations that could not be linked.
+    pub fn report(&self) -> &LinkReport {
+        &self.report
+    }
+}
+
+
+
+/// A relocated location in the image.
+pub(super) struct Place<'a> {
+    /// Contents from the relocated location on, as long as the relocated field.
+    pub(super) data: &'a mut [u8],
+
+    /// Address of the relocated location (`P`).
+    pub(super) address: u64,
+
+    /// `true` if the data is big endian.
+    pub(super) big: bool,
+
+    /// `true` if the addresses are 64 bits wide.
+    pub(super) wide: bool,
+}
+
+impl<'a> Place<'a> {
+    /// Reads the value of the given size in bytes at the given offset.
+    pub(super) fn read(&self, offset: usize, size: usize) -> u64 {
+        let bytes = &self.data[offset..offset+size];
+
+        match self.big {
+            true => bytes.iter().fold(0, |n, b| (n << 8) | u64::from(*b)),
+            _ => bytes.iter().rev().fold(0, |n, b| (n << 8) | u64::from(*b)),
+        }
+    }
+
+    /// Writes the value truncated to the given size in bytes at the given offset.
+    pub(super) fn write(&mut self, offset: usize, size: usize, value: u64) {
+        let big = self.big;
+
+        for (i, byte) in self.data[offset..offset+size].iter_mut().enumerate() {
+            let shift = match big {
+                true => 8 * (size - 1 - i),
+                _ => 8 * i,
+            };
+
+            *byte = (value >> shift) as u8;
+        }
+    }
+
+    /// Reads the 16 bit value at the given offset.
+    pub(super) fn read16(&self, offset: usize) -> u16 {
+        self.read(offset, 2) as u16
+    }
+
+    /// Writes the 16 bit value at the given offset.
+    pub(super) fn write16(&mut self, offset: usize, value: u16) {
+        self.write(offset, 2, u64::from(value))
+    }
+
+    /// Reads the 32 bit value at the given offset.
+    pub(super) fn read32(&self, offset: usize) -> u32 {
+        self.read(offset, 4) as u32
+    }
+
+    /// Writes the 32 bit value at the given offset.
+    pub(super) fn write32(&mut self, offset: usize, value: u32) {
+        self.write(offset, 4, u64::from(value))
+    }
+
+    /// Returns the signed distance from the relocated location to the given address.
+    /// The distance wraps around the address space of the class.
+    pub(super) fn relative(&self, address: u64) -> i64 {
+        let distance = address.wrapping_sub(self.address);
+
+        match self.wide {
+            true => distance as i64,
+            _ => distance as u32 as i32 as i64,
+        }
+    }
+}
+
+
+
+/// Links the relocatable object to the given base address.
+/// Undefined symbols are resolved against the given symbol map.
+pub(super) fn link(metadata: &ELFData, raw: &[u8], base: u64, map: &HashMap<String, u64>) -> Result<LinkedImage, Error> {
+    use super::data::header::Endianness;
+
+    // Only relocatable files can be placed at an arbitrary address.
+    if metadata.header.filetype() != FileType::Relocatable {
+        return Err( Error::NotRelocatable );
+    }
+
+    // Get the class and the data encoding of the file.
+    let wide = metadata.header.entry().bytes() == 8;
+    let big = matches!(metadata.header.endianness(), Endianness::Big);
+
+    // Get the end of the address space of the class.
+    let limit = match wide {
+        true => u64::MAX,
+        _ => u64::from( u32::MAX ),
+    };
+
+    // Place the allocated sections with data first, then those without data.
+    let alloc = |nobits: bool| {
+        metadata.sections.iter()
+            .enumerate()
+            .skip(1)
+            .filter(move |(_, section)| (u64::from( section.flags() ) & ALLOC) != 0)
+            .filter(move |(_, section)| (section.sectiontype() == SectionType::Bss) == nobits)
+    };
+
+    let mut addresses = vec![None; metadata.sections.len()];
+    let mut sections = Vec::new();
+    let mut cursor = base;
+
+    for (index, section) in alloc(false).chain( alloc(true) ) {
+        // Only honor power of two alignments.
+        let alignment = match u64::from( section.alignment() ) {
+            a if a.is_power_of_two() => a,
+            _ => 1,
+        };
+
+        let size = u64::from( section.filesize() );
+
+        // Check the section fits in the address space.
+        let address = match cursor.checked_next_multiple_of(alignment) {
+            Some(address) if address.checked_add(size).is_some_and(|end| end <= limit) => address,
+            _ => return Err( Error::OutOfBounds { table: Table::SectionHeaders, index, offset: usize::from( section.offset() ) } ),
+        };
+
+        addresses[index] = Some( address );
+        cursor = address + size;
+
+        sections.push( PlacedSection { index, name: section.name().clone(), address, size, nobits: section.sectiontype() == SectionType::Bss } );
+    }
+
+    // Get the contents of the sections with data, checking they are in the file.
+    let contents = sections.iter()
+        .filter(|placed| !placed.nobits)
+        .map(|placed| Ok( (placed.address, metadata.section_content(raw, placed.index)?) ))
+        .collect::<Result<Vec<_>, Error>>()?;
+
+    // Copy the contents into the image.
+    let end = contents.iter().map(|(address, content)| address + content.len() as u64).max().unwrap_or(base);
+    let mut image = vec![0u8; (end - base) as usize];
+
+    for (address, content) in contents {
+        let start = (address - base) as usize;
+        image[start..start + content.len()].copy_from_slice( content );
+    }
+
+    // Resolve the address of a symbol. Undefined weak symbols resolve to zero.
+    let resolve = |symbol: &Symbol| -> Option<u64> {
+        let value = u64::from( symbol.value() );
+
+        match symbol.shidx() {
+            0 => map.get( symbol.name() ).cloned().or( Some(0).filter(|_| symbol.bind() == Bind::Weak) ),
+            ABS => Some( value ),
+            shidx => match addresses.get(shidx as usize) {
+                Some(Some(address)) => Some( address.wrapping_add(value) & limit ),
+                _ => map.get( symbol.name() ).cloned(),
+            },
+        }
+    };
+
+    // Collect the addresses of the global symbols defined in the object.
+    let symbols = metadata.symbols.iter()
+        .filter(|symbol| (symbol.shidx() != 0) && ((symbol.bind() == Bind::Global) || (symbol.bind() == Bind::Weak)))
+        .filter_map(|symbol| Some( (symbol.name().clone(), resolve(symbol)?) ))
+        .collect();
+
+    // Apply the relocations of the placed sections.
+    let mut report = LinkReport::default();
+
+    for table in metadata.relocations(raw)? {
+        // Get the relocated section.
+        let target = table.section().info() as usize;
+
+        let placed = match sections.iter().find(|placed| placed.index == target) {
+            Some(placed) => placed,
+            _ => continue,
+        };
+
+        for (entry, relocation) in table.relocations().iter().enumerate() {
+            let offset = u64::from( relocation.offset() );
+            let address = placed.address.wrapping_add(offset) & limit;
+
+            // Resolve the symbol. Thumb functions have the lowest bit of their address set.
+            let (name, symbol) = match (relocation.symidx(), relocation.symbol()) {
+                (0, _) => (String::new(), Some( (0, false) )),
+
+                (_, Some(symbol)) => {
+                    let thumb = (symbol.shidx() == 0) || (symbol.kind() == SymbolType::Function);
+                    (symbol.name().clone(), resolve(symbol).map(|s| (s, thumb && ((s & 1) != 0))))
+                },
+
+                (symidx, _) => (format!("#{}", symidx), None),
+            };
+
+            let (s, thumb) = match symbol {
+                Some(symbol) => symbol,
+                _ => {
+                    if !report.unresolved.contains(&name) {
+                        report.unresolved.push( name );
+                    }
+
+                    continue;
+                },
+            };
+
+            let issue = |relocation: &Relocation| RelocationIssue { section: placed.name.clone(), address, kind: relocation.kind(), symbol: name.clone() };
+
+            // Get the size of the relocated field.
+            let size = match (metadata.header.architecture(), relocation.kind()) {
+                (Architecture::Aarch32, RelocationType::ARM(kind)) => arm::size(kind),
+                (Architecture::RISCV, RelocationType::RISCV(kind)) => riscv::size(kind),
+                _ => None,
+            };
+
+            let size = match size {
+                Some(size) => size as u64,
+                _ => {
+                    report.unsupported.push( issue(relocation) );
+                    continue;
+                },
+            };
+
+            // Check the relocated field is in the section.
+            if placed.nobits || offset.checked_add(size).is_none_or(|end| end > placed.size) {
+                let entrysize = usize::from( table.section().entrysize() );
+                return Err( Error::OutOfBounds { table: Table::Relocations, index: entry, offset: usize::from( table.section().offset() ).saturating_add(entry.saturating_mul(entrysize)) } );
+            }
+
+            let start = (placed.address - base + offset) as usize;
+            let mut place = Place { data: &mut image[start..start + size as usize], address, big, wide };
+
+            let applied = match relocation.kind() {
+                RelocationType::ARM(kind) => arm::apply(kind, &mut place, (s as u32) & !u32::from(thumb), u32::from(thumb), relocation.addend()),
+                RelocationType::RISCV(kind) => riscv::apply(kind, &mut place, s, relocation.addend()),
+                _ => true,
+            };
+
+            if !applied {
+                report.overflowed.push( issue(relocation) );
+            }
+        }
+    }
+
+    Ok( LinkedImage { base, image, size: cursor - base, sections, symbols, report } )
+}
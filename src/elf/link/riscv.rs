@@ -0,0 +1,207 @@
+//! RISC-V relocations.
+//! Relocatable files use `Rela` entries, so the addends are always explicit.
+
+
+
+use crate::elf::data::relocation::RISCV;
+
+use super::Place;
+
+
+
+/// Returns the size in bytes of the field relocated by the given type.
+/// Returns `None` if the relocation type is not supported.
+pub(super) fn size(kind: RISCV) -> Option<usize> {
+    match kind {
+        // Relaxation hints. The code is valid as is when it is not relaxed.
+        RISCV::None | RISCV::Relax | RISCV::Align => Some(0),
+
+        RISCV::Add8 | RISCV::Sub8 | RISCV::Set8 | RISCV::Sub6 | RISCV::Set6 => Some(1),
+        RISCV::Add16 | RISCV::Sub16 | RISCV::Set16 => Some(2),
+        RISCV::Abs32 | RISCV::Pcrel32 | RISCV::Add32 | RISCV::Sub32 | RISCV::Set32 => Some(4),
+        RISCV::Abs64 | RISCV::Add64 | RISCV::Sub64 => Some(8),
+
+        RISCV::Hi20 | RISCV::Lo12I | RISCV::Lo12S => Some(4),
+
+        RISCV::Branch | RISCV::Jal => Some(4),
+        RISCV::RvcBranch | RISCV::RvcJump => Some(2),
+
+        // Pair of `auipc` and `jalr` instructions.
+        RISCV::Call | RISCV::CallPlt => Some(8),
+
+        _ => None,
+    }
+}
+
+/// Applies the relocation to the given place.
+/// `s` is the address of the symbol.
+/// Returns `false` if the value does not fit in the relocated field.
+pub(super) fn apply(kind: RISCV, place: &mut Place, s: u64, addend: Option<i64>) -> bool {
+    // Get the value of the symbol plus the addend.
+    let value = s.wrapping_add( addend.unwrap_or(0) as u64 );
+
+    match kind {
+        RISCV::Abs32 => {
+            if place.wide && (value > u64::from(u32::MAX)) && !fits(value as i64, 32) {
+                return false;
+            }
+
+            place.write32(0, value as u32);
+        },
+
+        RISCV::Abs64 => place.write(0, 8, value),
+
+        RISCV::Pcrel32 => {
+            let offset = place.relative(value);
+
+            if !fits(offset, 32) {
+                return false;
+            }
+
+            place.write32(0, offset as u32);
+        },
+
+        RISCV::Add8 | RISCV::Add16 | RISCV::Add32 | RISCV::Add64 => {
+            let size = place.data.len();
+            let old = place.read(0, size);
+            place.write(0, size, old.wrapping_add(value));
+        },
+
+        RISCV::Sub8 | RISCV::Sub16 | RISCV::Sub32 | RISCV::Sub64 => {
+            let size = place.data.len();
+            let old = place.read(0, size);
+            place.write(0, size, old.wrapping_sub(value));
+        },
+
+        RISCV::Set8 | RISCV::Set16 | RISCV::Set32 => {
+            let size = place.data.len();
+            place.write(0, size, value);
+        },
+
+        // The 6 bit fields are the low bits of a byte.
+        RISCV::Sub6 => {
+            let old = place.read(0, 1);
+            place.write(0, 1, (old & 0xC0) | (old.wrapping_sub(value) & 0x3F));
+        },
+
+        RISCV::Set6 => {
+            let old = place.read(0, 1);
+            place.write(0, 1, (old & 0xC0) | (value & 0x3F));
+        },
+
+        RISCV::Hi20 => {
+            if place.wide && !hi20( value as i64 ) {
+                return false;
+            }
+
+            let insn = place.read32(0);
+            place.write32(0, utype(insn, value as u32));
+        },
+
+        RISCV::Lo12I => {
+            let insn = place.read32(0);
+            place.write32(0, itype(insn, value as u32));
+        },
+
+        RISCV::Lo12S => {
+            let insn = place.read32(0);
+            place.write32(0, stype(insn, value as u32));
+        },
+
+        RISCV::Branch => {
+            let offset = place.relative(value);
+
+            if !fits(offset, 13) || ((offset & 1) != 0) {
+                return false;
+            }
+
+            let (insn, offset) = (place.read32(0), offset as u32);
+            let imm = (((offset >> 12) & 1) << 31) | (((offset >> 5) & 0x3F) << 25) | (((offset >> 1) & 0xF) << 8) | (((offset >> 11) & 1) << 7);
+
+            place.write32(0, (insn & 0x01FF_F07F) | imm);
+        },
+
+        RISCV::Jal => {
+            let offset = place.relative(value);
+
+            if !fits(offset, 21) || ((offset & 1) != 0) {
+                return false;
+            }
+
+            let (insn, offset) = (place.read32(0), offset as u32);
+            let imm = (((offset >> 20) & 1) << 31) | (((offset >> 1) & 0x3FF) << 21) | (((offset >> 11) & 1) << 20) | (((offset >> 12) & 0xFF) << 12);
+
+            place.write32(0, (insn & 0xFFF) | imm);
+        },
+
+        RISCV::RvcBranch => {
+            let offset = place.relative(value);
+
+            if !fits(offset, 9) || ((offset & 1) != 0) {
+                return false;
+            }
+
+            let (insn, offset) = (u32::from( place.read16(0) ), offset as u32);
+            let imm = (((offset >> 8) & 1) << 12) | (((offset >> 3) & 0x3) << 10) | (((offset >> 6) & 0x3) << 5) | (((offset >> 1) & 0x3) << 3) | (((offset >> 5) & 1) << 2);
+
+            place.write16(0, ((insn & 0xE383) | imm) as u16);
+        },
+
+        RISCV::RvcJump => {
+            let offset = place.relative(value);
+
+            if !fits(offset, 12) || ((offset & 1) != 0) {
+                return false;
+            }
+
+            let (insn, offset) = (u32::from( place.read16(0) ), offset as u32);
+            let imm = (((offset >> 11) & 1) << 12) | (((offset >> 4) & 1) << 11) | (((offset >> 8) & 0x3) << 9) | (((offset >> 10) & 1) << 8)
+                | (((offset >> 6) & 1) << 7) | (((offset >> 7) & 1) << 6) | (((offset >> 1) & 0x7) << 3) | (((offset >> 5) & 1) << 2);
+
+            place.write16(0, ((insn & 0xE003) | imm) as u16);
+        },
+
+        RISCV::Call | RISCV::CallPlt => {
+            let offset = place.relative(value);
+
+            if place.wide && !hi20(offset) {
+                return false;
+            }
+
+            // Split the offset between the `auipc` and the `jalr`.
+            let (auipc, jalr) = (place.read32(0), place.read32(4));
+
+            place.write32(0, utype(auipc, offset as u32));
+            place.write32(4, itype(jalr, offset as u32));
+        },
+
+        _ => (),
+    }
+
+    true
+}
+
+/// Returns `true` if the value fits in a signed field of the given number of bits.
+fn fits(value: i64, bits: u32) -> bool {
+    (-(1 << (bits - 1))..(1 << (bits - 1))).contains(&value)
+}
+
+/// Returns `true` if the value can be built by a 20 bit upper immediate and a 12 bit signed immediate.
+fn hi20(value: i64) -> bool {
+    (-(1 << 31) - 0x800..(1 << 31) - 0x800).contains(&value)
+}
+
+/// Sets the upper immediate of an U-type instruction, rounded for the signed lower 12 bits.
+fn utype(insn: u32, value: u32) -> u32 {
+    (insn & 0xFFF) | (value.wrapping_add(0x800) & 0xFFFF_F000)
+}
+
+/// Sets the immediate of an I-type instruction to the lower 12 bits of the value.
+fn itype(insn: u32, value: u32) -> u32 {
+    (insn & 0x000F_FFFF) | ((value & 0xFFF) << 20)
+}
+
+/// Sets the immediate of an S-type instruction to the lower 12 bits of the value.
+fn stype(insn: u32, value: u32) -> u32 {
+    (insn & 0x01FF_F07F) | (((value >> 5) & 0x7F) << 25) | ((value & 0x1F) << 7)
+}
@@ -5,16 +5,31 @@
 
 
 pub mod data;
+pub mod link;
+pub mod symbolizer;
 
 
 
-use crate::common::subslice::SubSlice;
+use crate::{
+    common::subslice::SubSlice,
+    error::Error,
+    image::{ HexOptions, MemoryImage, SrecOptions, Uf2Options },
+};
 
 use data::{
-    ELFData, ProgramHeader, SectionHeader, Symbol,
+    AddressSpace, CompressionHeader, DynamicEntry, ELFData, HashTable, NoteIterator, ProgramHeader, RelocationTable, SectionHeader, Symbol, VersionRequirement,
+    symbol::{ Bind, SymbolIndex },
 };
 
-use std::sync::Arc;
+use link::LinkedImage;
+
+use symbolizer::Symbolizer;
+
+use std::{
+    borrow::Cow,
+    collections::HashMap,
+    sync::{ Arc, OnceLock },
+};
 
 
 
@@ -28,15 +43,19 @@ pub struct ELFObject<R: AsRef<[u8]>> {
 
     /// The raw ELF data.
     raw: R,
+
+    /// The index of the symbols by name.
+    /// Built on the first lookup and cleared when the symbols are modified.
+    names: OnceLock<SymbolIndex>,
 }
 
 impl<R: AsRef<[u8]>> ELFObject<R> {
     /// Parses the given data into an ELF object.
-    pub fn parse(raw: R) -> Result<Self, ()> {
+    pub fn parse(raw: R) -> Result<Self, Error> {
         // Parse the data.
         let metadata = ELFData::parse(raw.as_ref())?;
 
-        Ok( Self { metadata, raw } )
+        Ok( Self { metadata, raw, names: OnceLock::new() } )
     }
 
     /// Returns the target architecture.
@@ -44,6 +63,16 @@ impl<R: AsRef<[u8]>> ELFObject<R> {
         self.metadata.header.architecture()
     }
 
+    /// Returns the raw target architecture (`e_machine`), also for the unknown architectures.
+    pub fn machine(&self) -> u16 {
+        self.metadata.header.machine()
+    }
+
+    /// Returns the architecture flags of the file header, decoded for the target architecture.
+    pub fn archflags(&self) -> data::header::ArchFlags {
+        self.metadata.header.archflags()
+    }
+
     /// Returns the target OS.
     pub fn os(&self) -> data::header::TargetOS {
         self.metadata.header.os()
@@ -64,53 +93,384 @@ impl<R: AsRef<[u8]>> ELFObject<R> {
         &self.metadata.symbols
     }
 
+    /// Returns a reference to the list of dynamic symbols.
+    /// If the section headers are stripped, the table is found through the dynamic section
+    /// and the number of symbols is taken from its hash table. The list is empty if the table is malformed.
+    pub fn dynamic_symbols(&self) -> &Vec<Arc<Symbol>> {
+        &self.metadata.dynsyms
+    }
+
+    /// Returns the versions required from each library (`DT_NEEDED`) by the GNU symbol versioning.
+    /// The version of each dynamic symbol is available through `Symbol::version`.
+    /// The list is empty, and the symbols have no versions, if the version tables are malformed.
+    pub fn required_versions(&self) -> &Vec<VersionRequirement> {
+        &self.metadata.requirements
+    }
+
+    /// Returns the hash table of the dynamic symbols, if the object has a valid one.
+    /// The GNU hash table is preferred when both tables exist.
+    pub fn hash_table(&self) -> Option<&HashTable> {
+        self.metadata.hash.as_ref()
+    }
+
+    /// Looks up a defined dynamic symbol by name through the hash table, like the dynamic loader.
+    /// Returns `None` if the symbol is not defined or the object has no hash table.
+    pub fn lookup_dynamic(&self, name: &str) -> Option<&Arc<Symbol>> {
+        let index = self.metadata.hash.as_ref()?.lookup(name, &self.metadata.dynsyms)?;
+        self.metadata.dynsyms.get(index)
+    }
+
+    /// Returns all the symbols with the given name, in symbol table order.
+    /// Names may repeat, e.g. static functions of different files; `symbol_in_file` tells them apart.
+    /// The symbols are found through a name index, built on the first lookup.
+    pub fn symbol(&self, name: &str) -> Vec<&Arc<Symbol>> {
+        self.symbol_index().get(name).iter()
+            .filter_map(|index| self.symbols().get(*index))
+            .collect()
+    }
+
+    /// Returns the symbol with the given name that follows the file symbol (`STT_FILE`) with the given name.
+    /// Local symbols follow the file symbol of their source file in the symbol table.
+    pub fn symbol_in_file(&self, file: &str, name: &str) -> Option<&Arc<Symbol>> {
+        self.find_in_file(file, self.symbol_index().get(name))
+    }
+
+    /// Returns all the symbols with the given name or demangled name, in symbol table order.
+    /// Demangled names are matched without the `::h<hash>` suffix of legacy Rust names.
+    pub fn symbol_demangled(&self, name: &str) -> Vec<&Arc<Symbol>> {
+        self.symbol_index().get_demangled(name).iter()
+            .filter_map(|index| self.symbols().get(*index))
+            .collect()
+    }
+
+    /// Returns the symbol with the given name or demangled name that follows the file symbol (`STT_FILE`) with the given name.
+    pub fn symbol_in_file_demangled(&self, file: &str, name: &str) -> Option<&Arc<Symbol>> {
+        self.find_in_file(file, &self.symbol_index().get_demangled(name))
+    }
+
+    /// Builds an index of the function and object symbols (static and dynamic) by address range,
+    /// to resolve addresses into `symbol+offset` form. On ARM the Thumb bit of the functions is cleared.
+    pub fn symbolizer(&self) -> Symbolizer<'_> {
+        let thumb = self.architecture() == data::header::Architecture::Aarch32;
+        Symbolizer::new(self.symbols().iter().chain( self.dynamic_symbols() ), self.sections(), thumb)
+    }
+
     /// Returns the section given an ID (String, &str or usize).
     pub fn section<I: data::section::SectionID>(&self, id: I) -> Option<&Arc<SectionHeader>> {
+        self.sections().get( self.index(id)? )
+    }
+
+    /// Returns a mutable reference to the section given an ID (String, &str or usize).
+    /// The file is laid out again when the object is written.
+    pub fn section_mut<I: data::section::SectionID>(&mut self, id: I) -> Option<&mut SectionHeader> {
+        let index = self.index(id)?;
+        self.metadata.section_mut(index)
+    }
+
+    /// Adds a new section at the end of the section list and returns a reference to it.
+    /// The section is placed in the file after all other contents when the object is written.
+    pub fn add_section(&mut self, name: &str, sectiontype: data::section::SectionType, data: Vec<u8>) -> &mut SectionHeader {
+        self.metadata.add_section(name, sectiontype, data)
+    }
+
+    /// Removes the section given an ID (String, &str or usize) and returns it.
+    /// All section indices in the object are remapped and references to the removed section are cleared.
+    pub fn remove_section<I: data::section::SectionID>(&mut self, id: I) -> Option<Arc<SectionHeader>> {
+        let index = self.index(id)?;
+        self.names.take();
+        self.metadata.remove_section(index)
+    }
+
+    /// Renames the section given an ID (String, &str or usize).
+    /// Returns `false` if the section does not exist.
+    pub fn rename_section<I: data::section::SectionID>(&mut self, id: I, name: &str) -> bool {
+        match self.section_mut(id) {
+            Some(section) => { section.set_name(name); true },
+            _ => false,
+        }
+    }
+
+    /// Resizes the section given an ID (String, &str or usize), truncating its contents or padding them with zeros.
+    /// Returns `false` if the section does not exist or it is mapped by a segment and cannot grow.
+    pub fn resize_section<I: data::section::SectionID>(&mut self, id: I, size: usize) -> bool {
+        match self.index(id) {
+            Some(index) => self.metadata.resize(self.raw.as_ref(), index, size),
+            _ => false,
+        }
+    }
+
+    /// Replaces the contents of the section given an ID (String, &str or usize).
+    /// Returns `false` if the section does not exist or it is mapped by a segment and cannot grow.
+    pub fn set_section_content<I: data::section::SectionID>(&mut self, id: I, data: Vec<u8>) -> bool {
+        match self.index(id) {
+            Some(index) => self.metadata.set_content(index, data),
+            _ => false,
+        }
+    }
+
+    /// Returns the compression header of the section given an ID (String, &str or usize).
+    /// Legacy `.zdebug_*` sections report a zlib header with the size that follows their `ZLIB` magic.
+    /// Returns `None` if the section is not compressed.
+    /// Fails with `Error::MissingSection` if the section does not exist.
+    pub fn compression_header<I: data::section::SectionID>(&self, id: I) -> Result<Option<CompressionHeader>, Error> {
+        let index = self.index(id).ok_or( Error::MissingSection )?;
+        Ok( self.metadata.compression(self.raw.as_ref(), index)?.map(|(header, _)| header) )
+    }
+
+    /// Returns the uncompressed contents of the section given an ID (String, &str or usize).
+    /// `SHF_COMPRESSED` sections and legacy `.zdebug_*` sections are decompressed with the decoders
+    /// enabled by the `zlib` and `zstd` features. Other sections are returned as they are.
+    /// Fails with `Error::MissingSection` if the section does not exist.
+    pub fn decompressed_content<I: data::section::SectionID>(&self, id: I) -> Result<Cow<'_, [u8]>, Error> {
+        let index = self.index(id).ok_or( Error::MissingSection )?;
+        self.metadata.decompressed(self.raw.as_ref(), index)
+    }
+
+    /// Parses the relocations of all the relocation sections.
+    /// Each table is linked to the section it applies to and to its symbol table.
+    pub fn relocations(&self) -> Result<Vec<RelocationTable>, Error> {
+        self.metadata.relocations( self.raw.as_ref() )
+    }
+
+    /// Returns an iterator over the notes of the note sections or, if the section headers are stripped,
+    /// of the note segments. Each note is yielded with its name, type and descriptor.
+    pub fn notes(&self) -> NoteIterator<'_> {
+        self.metadata.notes( self.raw.as_ref() )
+    }
+
+    /// Returns the GNU build ID of the object (`NT_GNU_BUILD_ID`), if it has one.
+    pub fn build_id(&self) -> Option<&[u8]> {
+        self.notes()
+            .filter_map(Result::ok)
+            .find(|note| (note.name() == "GNU") && (note.kind() == data::note::NT_GNU_BUILD_ID))
+            .map(|note| note.descriptor())
+    }
+
+    /// Parses the entries of the dynamic section, up to the terminating entry.
+    /// The dynamic section is found through the `PT_DYNAMIC` segment and its strings and addresses
+    /// are resolved through the loadable segments, so the section headers may be stripped.
+    /// Returns an empty list if the object has no dynamic section.
+    pub fn dynamic(&self) -> Result<Vec<DynamicEntry>, Error> {
+        self.metadata.dynamic( self.raw.as_ref() )
+    }
+
+    /// Reads the given number of bytes at the given address of the loaded image, in virtual (VMA)
+    /// or physical (LMA) addresses. The address is translated through the loadable segments, or through
+    /// the allocated sections if there are none. The zero filled part of a segment (`memsize > filesize`)
+    /// and the sections without data read as zeros. Unmapped addresses return an error.
+    pub fn read_at(&self, address: u64, len: usize, space: AddressSpace) -> Result<Cow<'_, [u8]>, Error> {
+        self.metadata.read(self.raw.as_ref(), address, len, space)
+    }
+
+    /// Reads a 16 bit value in the endianness of the object at the given address of the loaded image.
+    pub fn read_u16_at(&self, address: u64, space: AddressSpace) -> Result<u16, Error> {
+        self.read_value(address, 2, space, crate::common::bytes::read16::<byteorder::LittleEndian>, crate::common::bytes::read16::<byteorder::BigEndian>)
+    }
+
+    /// Reads a 32 bit value in the endianness of the object at the given address of the loaded image.
+    pub fn read_u32_at(&self, address: u64, space: AddressSpace) -> Result<u32, Error> {
+        self.read_value(address, 4, space, crate::common::bytes::read32::<byteorder::LittleEndian>, crate::common::bytes::read32::<byteorder::BigEndian>)
+    }
+
+    /// Reads a 64 bit value in the endianness of the object at the given address of the loaded image.
+    pub fn read_u64_at(&self, address: u64, space: AddressSpace) -> Result<u64, Error> {
+        self.read_value(address, 8, space, crate::common::bytes::read64::<byteorder::LittleEndian>, crate::common::bytes::read64::<byteorder::BigEndian>)
+    }
+
+    /// Reads the null terminated string at the given address of the loaded image.
+    /// Invalid UTF-8 sequences are replaced, and an unterminated string is an error.
+    pub fn read_cstr_at(&self, address: u64, space: AddressSpace) -> Result<String, Error> {
+        self.metadata.read_cstr(self.raw.as_ref(), address, space)
+    }
+
+    /// Builds the memory image of the object as it is programmed: the loadable segments placed at
+    /// their physical (load) addresses, or their virtual addresses if no segment has a physical address,
+    /// without their zero filled part. Adjacent segments are merged and overlapping segments are an error.
+    pub fn memory_image(&self) -> Result<MemoryImage, Error> {
+        self.metadata.image( self.raw.as_ref() )
+    }
+
+    /// Writes the sections loaded in memory as Intel HEX records at their load addresses, with the
+    /// same output as the GNU binutils (`objcopy -O ihex`). Unless a start address is given in the
+    /// options, the start address record is taken from the entry point of the object.
+    pub fn to_ihex(&self, options: &HexOptions) -> Result<String, Error> {
+        let chunks = self.metadata.loadable( self.raw.as_ref() )?;
+        let start = options.start.or( Some( u64::from( self.metadata.header.entry() ) ) );
+
+        crate::image::ihex::write( chunks, &HexOptions { start, ..*options } )
+    }
+
+    /// Writes the sections loaded in memory as S-records at their load addresses, with the same
+    /// output as the GNU binutils (`objcopy -O srec`) when the header is the name of the output file.
+    /// Unless a start address is given in the options, the termination record holds the entry point.
+    pub fn to_srec(&self, options: &SrecOptions) -> Result<String, Error> {
+        let chunks = self.metadata.loadable( self.raw.as_ref() )?;
+        let start = options.start.or( Some( u64::from( self.metadata.header.entry() ) ) );
+
+        crate::image::srec::write( chunks, &SrecOptions { start, ..options.clone() } )
+    }
+
+    /// Writes the loadable segments as a UF2 file at their load addresses.
+    /// The payloads are aligned to their size (the target page), filling the gaps with the fill byte.
+    pub fn to_uf2(&self, options: &Uf2Options) -> Result<Vec<u8>, Error> {
+        self.memory_image()?.to_uf2(options)
+    }
+
+    /// Links the relocatable object to the given base address.
+    /// Allocated sections are placed in order, followed by those without data, and undefined symbols
+    /// are resolved against the given symbol map. Unresolved symbols and relocations that cannot be
+    /// applied are listed in the report of the linked image.
+    pub fn link(&self, base: u64, symbols: &HashMap<String, u64>) -> Result<LinkedImage, Error> {
+        link::link(&self.metadata, self.raw.as_ref(), base, symbols)
+    }
+
+    /// Returns a mutable reference to the symbol at the given index.
+    /// The symbol table is regenerated when the object is written.
+    pub fn symbol_mut(&mut self, index: usize) -> Option<&mut Symbol> {
+        self.names.take();
+        self.metadata.symbol_mut(index)
+    }
+
+    /// Adds a new symbol at the end of the symbol list and returns its index.
+    /// Local symbols are placed before all other symbols when the object is written.
+    pub fn add_symbol(&mut self, symbol: Symbol) -> usize {
+        self.names.take();
+        self.metadata.add_symbol(symbol)
+    }
+
+    /// Removes the symbol at the given index and returns it.
+    /// Returns `None` for the null symbol and for symbols referenced by relocations or section groups.
+    pub fn remove_symbol(&mut self, index: usize) -> Option<Arc<Symbol>> {
+        self.names.take();
+        self.metadata.remove_symbol(self.raw.as_ref(), index)
+    }
+
+    /// Renames all the symbols with the given name.
+    /// Returns `false` if there is no symbol with that name.
+    pub fn rename_symbol(&mut self, name: &str, new: &str) -> bool {
+        self.edit_symbols(name, |symbol| symbol.set_name(new))
+    }
+
+    /// Makes all the defined symbols with the given name local.
+    /// Returns `false` if there is no symbol with that name.
+    pub fn localize_symbol(&mut self, name: &str) -> bool {
+        self.edit_symbols(name, |symbol| if symbol.shidx() != 0 { symbol.set_bind(Bind::Local) })
+    }
+
+    /// Makes all the symbols with the given name global.
+    /// Returns `false` if there is no symbol with that name.
+    pub fn globalize_symbol(&mut self, name: &str) -> bool {
+        self.edit_symbols(name, |symbol| symbol.set_bind(Bind::Global))
+    }
+
+    /// Makes all the global symbols with the given name weak.
+    /// Returns `false` if there is no symbol with that name.
+    pub fn weaken_symbol(&mut self, name: &str) -> bool {
+        self.edit_symbols(name, |symbol| if symbol.bind() == Bind::Global { symbol.set_bind(Bind::Weak) })
+    }
+
+    /// Returns the endianness of the ELF object.
+    pub fn endianness(&self) -> endianness::Endianness {
+        self.metadata.endianness()
+    }
+
+    /// Internal function to apply an edit to all the symbols with the given name.
+    fn edit_symbols<F: Fn(&mut Symbol)>(&mut self, name: &str, edit: F) -> bool {
+        self.names.take();
+
+        // Get the symbols with the given name, skipping the null symbol.
+        let indices = self.symbols().iter()
+            .enumerate()
+            .skip(1)
+            .filter(|(_, symbol)| symbol.name() == name)
+            .map(|(index, _)| index)
+            .collect::<Vec<_>>();
+
+        for index in &indices {
+            if let Some(symbol) = self.metadata.symbol_mut(*index) {
+                edit(symbol);
+            }
+        }
+
+        !indices.is_empty()
+    }
+
+    /// Internal function to read a value in the endianness of the object at the given address.
+    fn read_value<T>(&self, address: u64, size: usize, space: AddressSpace, little: fn(&[u8]) -> T, big: fn(&[u8]) -> T) -> Result<T, Error> {
+        use data::header::Endianness;
+
+        let bytes = self.read_at(address, size, space)?;
+
+        match self.metadata.header.endianness() {
+            Endianness::Little => Ok( little(&bytes) ),
+            Endianness::Big    => Ok( big(&bytes) ),
+        }
+    }
+
+    /// Internal function to get the name index of the symbols, building it if needed.
+    fn symbol_index(&self) -> &SymbolIndex {
+        self.names.get_or_init(|| SymbolIndex::new( self.symbols() ))
+    }
+
+    /// Internal function to find the first of the given symbols that follows the file symbol with the given name.
+    fn find_in_file(&self, file: &str, indices: &[usize]) -> Option<&Arc<Symbol>> {
+        let index = self.symbol_index();
+
+        indices.iter()
+            .find(|symbol| index.file(**symbol).and_then(|file| self.symbols().get(file)).is_some_and(|symbol| symbol.name() == file))
+            .and_then(|symbol| self.symbols().get(*symbol))
+    }
+
+    /// Internal function to get the index of a section given an ID.
+    fn index<I: data::section::SectionID>(&self, id: I) -> Option<usize> {
         if I::NUMERIC {
-            // Get the section at the given index.
-            self.sections().get(id.index())
+            // Check the section exists.
+            Some( id.index() ).filter(|index| *index < self.sections().len())
         } else {
             // Get the name.
             let name = id.name();
 
             // Find the section with the given name.
-            self.sections().iter().find(|section| section.name() == &name)
+            self.sections().iter().position(|section| section.name() == &name)
         }
     }
 
-    /// Returns the endianness of the ELF object.
-    pub fn endianness(&self) -> endianness::Endianness {
-        self.metadata.endianness()
+    /// Serializes the ELF object into a file image.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.metadata.write( self.raw.as_ref() )
+    }
+
+    /// Writes the ELF object file image into the given writer.
+    pub fn write<W: std::io::Write>(&self, mut writer: W) -> std::io::Result<()> {
+        writer.write_all( &self.to_bytes() )
     }
 }
 
 impl ELFObject<Arc<[u8]>> {
     /// Returns the contents of the given item.
+    /// Returns `None` if the item is empty or its contents are not in the file.
     pub fn content<I: data::HasContent>(&self, item: I) -> Option<SubSlice> {
-        if I::SECTION {
-            // Get the file size of the section.
+        if I::SECTION || I::SYMBOL {
+            // Get the file size of the item.
             let size = item.size();
 
-            match size {
-                0 => None,
-                _ => {
-                    // Get the offset.
-                    let offset = item.offset();
+            match (size, item.data()) {
+                (0, _) => None,
 
-                    Some( SubSlice::new( self.raw.clone(), offset, offset+size ) )
-                },
-            }
-        } else if I::SYMBOL {
-            // Get the file size of the section.
-            let size = item.size();
+                // Contents added or replaced after parsing.
+                (_, Some(data)) => Some( SubSlice::new( data, 0, size ) ),
 
-            match size {
-                0 => None,
                 _ => {
-                    // Get the offset.
+                    // Get the offset and the end of the item.
                     let offset = item.offset();
+                    let end = offset.checked_add(size)?;
+
+                    // Check the item is in the file.
+                    if end > self.raw.len() {
+                        return None;
+                    }
 
-                    Some( SubSlice::new( self.raw.clone(), offset, offset+size ) )
+                    Some( SubSlice::new( self.raw.clone(), offset, end ) )
                 },
             }
         } else {
@@ -0,0 +1,178 @@
+//! Address to symbol lookup.
+//! Indexes the function and object symbols of an ELF object by address range, so that addresses
+//! (e.g. the program counters of a crash log) can be resolved into `symbol+offset` form.
+
+
+
+use super::data::{
+    SectionHeader, Symbol,
+    section::ALLOC,
+    symbol::{ Bind, SymbolType, COMMON },
+};
+
+use std::{
+    cmp::Reverse,
+    collections::BinaryHeap,
+    sync::Arc,
+};
+
+
+
+/// An index of the symbols of an object by address range.
+/// Overlapping symbols are resolved when the index is built, so each address maps to at most one symbol.
+#[derive(Debug, Clone)]
+pub struct Symbolizer<'a> {
+    /// Start address of each range, sorted.
+    starts: Vec<u64>,
+
+    /// End address (exclusive), symbol address and symbol of each range.
+    ranges: Vec<(u64, u64, &'a Symbol)>,
+}
+
+impl<'a> Symbolizer<'a> {
+    /// Builds the index over the given symbols.
+    /// The sections are used to bound the range of the last symbol without size of each section.
+    /// If `thumb` is set, the lowest bit of the function addresses is cleared.
+    pub fn new<I: IntoIterator<Item = &'a Arc<Symbol>>>(symbols: I, sections: &[Arc<SectionHeader>], thumb: bool) -> Self {
+        // Collect the defined functions and objects.
+        let symbols = symbols.into_iter()
+            .map(|symbol| &**symbol)
+            .filter(|symbol| matches!(symbol.kind(), SymbolType::Function | SymbolType::Object))
+            .filter(|symbol| (symbol.shidx() != 0) && (symbol.shidx() != COMMON))
+            .map(|symbol| match thumb && symbol.is_function() {
+                true => (u64::from( symbol.value() ) & !1, symbol),
+                _ => (u64::from( symbol.value() ), symbol),
+            })
+            .collect::<Vec<_>>();
+
+        // Get all the symbol addresses, to size the symbols without size.
+        let mut addresses = symbols.iter().map(|(address, _)| *address).collect::<Vec<_>>();
+        addresses.sort_unstable();
+        addresses.dedup();
+
+        // Get the range and priority of each symbol.
+        let mut candidates = symbols.iter()
+            .enumerate()
+            .filter_map(|(index, (start, symbol))| {
+                let end = match u64::from( symbol.size() ) {
+                    0 => Self::next(&addresses, sections, *start, symbol),
+                    size => start.saturating_add(size),
+                };
+
+                let bind = match symbol.bind() {
+                    Bind::Global => 2,
+                    Bind::Weak => 1,
+                    _ => 0,
+                };
+
+                let priority = (symbol.is_function(), bind, u64::from( symbol.size() ) != 0, Reverse(index));
+
+                Some( (*start, end, priority, *symbol) ).filter(|_| end > *start)
+            })
+            .collect::<Vec<_>>();
+
+        candidates.sort_unstable_by_key(|(start, ..)| *start);
+
+        // Get the boundaries of the ranges.
+        let mut boundaries = candidates.iter().flat_map(|(start, end, ..)| [*start, *end]).collect::<Vec<_>>();
+        boundaries.sort_unstable();
+        boundaries.dedup();
+
+        // Sweep the boundaries, keeping the symbols that cover the current range by priority.
+        let mut starts: Vec<u64> = Vec::new();
+        let mut ranges: Vec<(u64, u64, &'a Symbol)> = Vec::new();
+
+        let mut active = BinaryHeap::new();
+        let mut next = 0;
+        let mut last = None;
+
+        for window in boundaries.windows(2) {
+            let (start, end) = (window[0], window[1]);
+
+            // Add the symbols that start in this range.
+            while let Some((_, stop, priority, _)) = candidates.get(next).filter(|(address, ..)| *address <= start) {
+                active.push( (*priority, *stop, next) );
+                next += 1;
+            }
+
+            // Remove the symbols that ended before this range.
+            while active.peek().is_some_and(|(_, stop, _)| *stop <= start) {
+                active.pop();
+            }
+
+            let winner = match active.peek() {
+                Some((_, _, index)) => *index,
+                _ => continue,
+            };
+
+            // Extend the previous range if it is the same symbol.
+            if (last == Some(winner)) && ranges.last().is_some_and(|(stop, ..)| *stop == start) {
+                if let Some(range) = ranges.last_mut() {
+                    range.0 = end;
+                }
+
+                continue;
+            }
+
+            let (address, _, _, symbol) = candidates[winner];
+
+            starts.push( start );
+            ranges.push( (end, address, symbol) );
+            last = Some( winner );
+        }
+
+        Self { starts, ranges }
+    }
+
+    /// Returns the symbol that contains the given address and the offset of the address into the symbol.
+    /// Functions are preferred over objects, and global symbols over weak and local symbols.
+    pub fn symbolize(&self, address: u64) -> Option<(&'a Symbol, u64)> {
+        let index = self.starts.partition_point(|start| *start <= address).checked_sub(1)?;
+        let (end, start, symbol) = self.ranges[index];
+
+        match address < end {
+            true => Some( (symbol, address - start) ),
+            _ => None,
+        }
+    }
+
+    /// Returns the given address in `symbol+0xoffset` form, if it is in a symbol.
+    pub fn describe(&self, address: u64) -> Option<String> {
+        self.symbolize(address).map(|(symbol, offset)| format!("{}+0x{:x}", symbol.name(), offset))
+    }
+
+    /// Returns the given address in `symbol+0xoffset` form with the demangled name of the symbol
+    /// (without hash), if it is in a symbol.
+    pub fn describe_demangled(&self, address: u64) -> Option<String> {
+        self.symbolize(address).map(|(symbol, offset)| format!("{}+0x{:x}", symbol.demangled(false), offset))
+    }
+
+    /// Returns the number of address ranges in the index.
+    pub fn len(&self) -> usize {
+        self.ranges.len()
+    }
+
+    /// Returns `true` if the index has no address ranges.
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// Internal function to get the end of a symbol without size.
+    /// The symbol extends up to the next symbol, without going past the end of its section.
+    fn next(addresses: &[u64], sections: &[Arc<SectionHeader>], start: u64, symbol: &Symbol) -> u64 {
+        // Get the address of the next symbol.
+        let next = addresses.get( addresses.partition_point(|address| *address <= start) ).cloned();
+
+        // Get the end of the section of the symbol.
+        let end = sections.get( symbol.shidx() as usize )
+            .filter(|section| (u64::from( section.flags() ) & ALLOC) != 0)
+            .and_then(|section| u64::from( section.vaddr() ).checked_add( u64::from( section.filesize() ) ))
+            .filter(|end| *end > start);
+
+        match (next, end) {
+            (Some(next), Some(end)) => core::cmp::min(next, end),
+            (Some(end), _) | (_, Some(end)) => end,
+            _ => start.saturating_add(1),
+        }
+    }
+}
@@ -0,0 +1,303 @@
+//! Errors of the crate.
+//! Describes what failed while parsing or processing an object file, where it
+//! failed and at which offset of the file image.
+
+
+
+/// All the errors that can happen while parsing an object file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Error {
+    /// The ELF file magic number is not correct.
+    BadMagic,
+
+    /// The ELF class (32 or 64 bit) is not supported.
+    /// Contains the raw `EI_CLASS` value.
+    UnsupportedClass(u8),
+
+    /// The ELF data encoding (endianness) is not supported.
+    /// Contains the raw `EI_DATA` value.
+    UnsupportedEndianness(u8),
+
+    /// The entries of a table do not have the size required by the ELF class.
+    BadHeaderSize {
+        /// The table with the bad entry size.
+        table: Table,
+
+        /// The expected size in bytes.
+        expected: usize,
+
+        /// The size in bytes found in the file.
+        found: usize,
+    },
+
+    /// An entry of a table lies (partially) outside of the file image.
+    OutOfBounds {
+        /// The table that contains the entry.
+        table: Table,
+
+        /// Index of the entry in the table.
+        index: usize,
+
+        /// Offset of the entry in the file image.
+        offset: usize,
+    },
+
+    /// A table required to parse the file does not exist.
+    MissingTable(Table),
+
+    /// The section with the given name or index does not exist.
+    MissingSection,
+
+    /// The string table used to name the entries of a table does not exist.
+    MissingStringTable {
+        /// The table whose names could not be loaded.
+        table: Table,
+
+        /// Index of the referenced string table section.
+        index: usize,
+    },
+
+    /// The name of an entry points outside of its string table.
+    BadNameIndex {
+        /// The table that contains the entry.
+        table: Table,
+
+        /// Index of the entry in the table.
+        index: usize,
+
+        /// Offset of the name in the file image.
+        offset: usize,
+    },
+
+    /// The operation requires a relocatable object file (`ET_REL`).
+    NotRelocatable,
+
+    /// The compression algorithm of a section is not supported or its decoder is not enabled.
+    /// Contains the raw `ch_type` value.
+    UnsupportedCompression(u32),
+
+    /// The compressed data of a section is corrupt or does not have the size given by its header.
+    BadCompressedData {
+        /// Index of the section.
+        index: usize,
+
+        /// Offset of the section in the file image.
+        offset: usize,
+    },
+
+    /// The address is not mapped by any segment (or allocated section) of the object.
+    Unmapped(u64),
+
+    /// The data overlaps the contents of a memory image.
+    /// Contains the address of the first overlapping byte.
+    Overlap(u64),
+
+    /// The data at the given address extends past the end of the address space (of the image or of the output format).
+    Overflow(u64),
+
+    /// A record of a text object file (Intel HEX or S-record) is not valid.
+    BadRecord {
+        /// Line of the record, starting at 1.
+        line: usize,
+
+        /// Column of the first invalid character, starting at 1.
+        column: usize,
+
+        /// What is wrong with the record.
+        kind: RecordError,
+    },
+
+    /// A block of a UF2 file is not valid.
+    BadBlock {
+        /// Index of the block in the file, starting at 0.
+        index: usize,
+
+        /// What is wrong with the block.
+        kind: BlockError,
+    },
+}
+
+impl core::fmt::Display for Error {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        use Error::*;
+
+        match *self {
+            BadMagic => write!(f, "Bad ELF magic number"),
+
+            UnsupportedClass(c) => write!(f, "Unsupported ELF class {}", c),
+
+            UnsupportedEndianness(e) => write!(f, "Unsupported ELF data encoding {}", e),
+
+            BadHeaderSize { table, expected, found } => write!(f, "Bad {} entry size: expected {} bytes, found {} bytes", table, expected, found),
+
+            OutOfBounds { table, index, offset } => write!(f, "{} entry {} at offset 0x{:X} is out of bounds", table, index, offset),
+
+            MissingTable(table) => write!(f, "Missing {}", table),
+
+            MissingSection => write!(f, "Missing section"),
+
+            MissingStringTable { table, index } => write!(f, "Missing string table (section {}) of the {}", index, table),
+
+            BadNameIndex { table, index, offset } => write!(f, "{} entry {} has a name out of bounds at offset 0x{:X}", table, index, offset),
+
+            NotRelocatable => write!(f, "The object is not a relocatable file"),
+
+            UnsupportedCompression(c) => write!(f, "Unsupported section compression type {}", c),
+
+            BadCompressedData { index, offset } => write!(f, "Section {} at offset 0x{:X} has corrupt compressed data", index, offset),
+
+            Unmapped(address) => write!(f, "Address 0x{:X} is not mapped by the object", address),
+
+            Overlap(address) => write!(f, "Overlapping contents at address 0x{:X}", address),
+
+            Overflow(address) => write!(f, "The contents at address 0x{:X} extend past the end of the address space", address),
+
+            BadRecord { line, column, kind } => write!(f, "Line {}, column {}: {}", line, column, kind),
+
+            BadBlock { index, kind } => write!(f, "UF2 block {} at offset 0x{:X}: {}", index, index * 512, kind),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+
+
+/// The tables (or structures) of an object file that can fail to parse.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Table {
+    /// The ELF file header.
+    FileHeader,
+
+    /// The Program Header Table.
+    ProgramHeaders,
+
+    /// The Section Header Table.
+    SectionHeaders,
+
+    /// The symbol table.
+    Symbols,
+
+    /// A relocation table.
+    Relocations,
+
+    /// The dynamic section.
+    Dynamic,
+
+    /// A symbol hash table.
+    Hash,
+
+    /// The symbol version tables.
+    Versions,
+
+    /// A note section or segment.
+    Notes,
+
+    /// The compression header of a section.
+    Compression,
+}
+
+impl core::fmt::Display for Table {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        let s = match *self {
+            Table::FileHeader     => "File header",
+            Table::ProgramHeaders => "Program Header Table",
+            Table::SectionHeaders => "Section Header Table",
+            Table::Symbols        => "Symbol table",
+            Table::Relocations    => "Relocation table",
+            Table::Dynamic        => "Dynamic section",
+            Table::Hash           => "Symbol hash table",
+            Table::Versions       => "Symbol version table",
+            Table::Notes          => "Note",
+            Table::Compression    => "Compression header",
+        };
+
+        write!(f, "{}", s)
+    }
+}
+
+
+
+/// The ways in which a record of a text object file can be invalid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecordError {
+    /// The record does not start with the start character of the format.
+    Start,
+
+    /// The character is not a hexadecimal digit.
+    Digit,
+
+    /// The length of the record does not match its byte count or its type.
+    Length,
+
+    /// The checksum of the record is not correct.
+    Checksum,
+
+    /// The record type is not known.
+    Type,
+
+    /// The record is not allowed at this point of the file (e.g. after the end of file record).
+    Order,
+
+    /// The record count does not match the number of data records.
+    Count,
+
+    /// The data of the record overlaps the data of a previous record.
+    Overlap,
+
+    /// The file ends without an end of file (or termination) record.
+    MissingEnd,
+}
+
+impl core::fmt::Display for RecordError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        let s = match *self {
+            RecordError::Start      => "Missing record start character",
+            RecordError::Digit      => "Invalid hexadecimal digit",
+            RecordError::Length     => "Bad record length",
+            RecordError::Checksum   => "Bad record checksum",
+            RecordError::Type       => "Unknown record type",
+            RecordError::Order      => "Record out of order",
+            RecordError::Count      => "Record count does not match the data records",
+            RecordError::Overlap    => "Record data overlaps previous data",
+            RecordError::MissingEnd => "Missing end of file record",
+        };
+
+        write!(f, "{}", s)
+    }
+}
+
+
+
+/// The ways in which a block of a UF2 file can be invalid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlockError {
+    /// The file ends in the middle of the block.
+    Truncated,
+
+    /// One of the magic numbers of the block is not correct.
+    Magic,
+
+    /// The payload of the block does not fit in its data area.
+    PayloadSize,
+
+    /// The block number is not lower than the number of blocks.
+    Number,
+
+    /// The payload of the block overlaps the payload of a previous block.
+    Overlap,
+}
+
+impl core::fmt::Display for BlockError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        let s = match *self {
+            BlockError::Truncated   => "Truncated block",
+            BlockError::Magic       => "Bad magic number",
+            BlockError::PayloadSize => "Payload larger than the data area",
+            BlockError::Number      => "Block number out of range",
+            BlockError::Overlap     => "Payload overlaps previous data",
+        };
+
+        write!(f, "{}", s)
+    }
+}
@@ -0,0 +1,276 @@
+//! Intel HEX format.
+//! Writes memory contents as Intel HEX records, with the same records and layout as the GNU
+//! binutils (`objcopy -O ihex`), and reads Intel HEX files into memory images.
+
+
+
+use crate::error::{ Error, RecordError };
+
+use super::{
+    MemoryImage,
+    text::{ self, Line },
+};
+
+
+
+/// Data record (`00`).
+const DATA: u8 = 0x00;
+
+/// End of file record (`01`).
+const EOF: u8 = 0x01;
+
+/// Extended segment address record (`02`).
+const EXTENDED_SEGMENT: u8 = 0x02;
+
+/// Start segment address record (`03`).
+const START_SEGMENT: u8 = 0x03;
+
+/// Extended linear address record (`04`).
+const EXTENDED_LINEAR: u8 = 0x04;
+
+/// Start linear address record (`05`).
+const START_LINEAR: u8 = 0x05;
+
+
+
+/// The records used to reach the addresses above 64 KiB.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HexAddressing {
+    /// Extended segment address records for the addresses below 1 MiB and extended linear
+    /// address records above, as done by the GNU binutils.
+    Auto,
+
+    /// Extended linear address records only (32 bit addresses).
+    Linear,
+
+    /// Extended segment address records only (20 bit addresses).
+    Segment,
+}
+
+impl core::fmt::Display for HexAddressing {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        let s = match *self {
+            HexAddressing::Auto => "Auto",
+            HexAddressing::Linear => "Linear",
+            HexAddressing::Segment => "Segment",
+        };
+
+        write!(f, "{}", s)
+    }
+}
+
+
+
+/// Options of the Intel HEX writer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HexOptions {
+    /// Maximum number of data bytes of each record (1 to 255).
+    pub record: u8,
+
+    /// Records used to reach the addresses above 64 KiB.
+    pub addressing: HexAddressing,
+
+    /// Start address of the program, written in a start address record if it is not 0.
+    pub start: Option<u64>,
+}
+
+impl Default for HexOptions {
+    fn default() -> Self {
+        Self { record: 16, addressing: HexAddressing::Auto, start: None }
+    }
+}
+
+
+
+/// Writes the given chunks of data (address and contents, sorted by address) as Intel HEX records.
+/// Each chunk starts a new data record, and the records do not cross 64 KiB boundaries.
+pub fn write<'a, I: IntoIterator<Item = (u64, &'a [u8])>>(chunks: I, options: &HexOptions) -> Result<String, Error> {
+    let mut output = String::new();
+
+    // Current extended segment and extended linear base addresses.
+    let mut segbase: u64 = 0;
+    let mut extbase: u64 = 0;
+
+    let size = core::cmp::max(options.record, 1) as usize;
+
+    for (address, data) in chunks {
+        let mut address = text::address(address)?;
+        let mut data = data;
+
+        // Check the chunk fits in the address space of the format.
+        let limit = match options.addressing {
+            HexAddressing::Segment => 0x10_0000,
+            _ => 0x1_0000_0000,
+        };
+
+        if (address + data.len() as u64) > limit {
+            return Err( Error::Overflow(address) );
+        }
+
+        while !data.is_empty() {
+            // Set a new base address if the address is out of the current 64 KiB window.
+            if (address < (segbase + extbase)) || (address > (segbase + extbase + 0xFFFF)) {
+                let segment = match options.addressing {
+                    HexAddressing::Auto => (extbase == 0) && (address <= 0xF_FFFF),
+                    HexAddressing::Linear => false,
+                    HexAddressing::Segment => true,
+                };
+
+                if segment {
+                    segbase = address & 0xF_0000;
+                    record( &mut output, EXTENDED_SEGMENT, 0, &((segbase >> 4) as u16).to_be_bytes() );
+                } else {
+                    // Clear the segment base, as some readers add both bases.
+                    if segbase != 0 {
+                        record( &mut output, EXTENDED_SEGMENT, 0, &[0, 0] );
+                        segbase = 0;
+                    }
+
+                    extbase = address & 0xFFFF_0000;
+                    record( &mut output, EXTENDED_LINEAR, 0, &((extbase >> 16) as u16).to_be_bytes() );
+                }
+            }
+
+            // Write the record, without crossing the 64 KiB window.
+            let offset = address - (segbase + extbase);
+            let len = core::cmp::min( core::cmp::min(data.len(), size) as u64, 0x1_0000 - offset ) as usize;
+
+            record( &mut output, DATA, offset as u16, &data[..len] );
+
+            address += len as u64;
+            data = &data[len..];
+        }
+    }
+
+    // Write the start address.
+    match options.start.map(text::address).transpose()? {
+        None | Some(0) => (),
+
+        Some(start) => {
+            let segment = match options.addressing {
+                HexAddressing::Auto => start <= 0xF_FFFF,
+                HexAddressing::Linear => false,
+                HexAddressing::Segment => true,
+            };
+
+            if segment {
+                if start > 0xF_FFFF {
+                    return Err( Error::Overflow(start) );
+                }
+
+                record( &mut output, START_SEGMENT, 0, &[(start >> 12) as u8 & 0xF0, 0, (start >> 8) as u8, start as u8] );
+            } else {
+                record( &mut output, START_LINEAR, 0, &(start as u32).to_be_bytes() );
+            }
+        },
+    }
+
+    record( &mut output, EOF, 0, &[] );
+
+    Ok( output )
+}
+
+/// Reads an Intel HEX file into a memory image.
+/// The checksum and length of all records are checked, data records must not overlap and the file
+/// must end with an end of file record. The start address record sets the start of the image.
+pub fn read(text: &str) -> Result<MemoryImage, Error> {
+    let mut image = MemoryImage::new();
+
+    // Base address of the data records, from the extended address records.
+    let mut base: u64 = 0;
+
+    let mut ended = false;
+
+    for line in text::lines(text) {
+        // No records are allowed after the end of file record.
+        if ended {
+            return Err( line.error(1, RecordError::Order) );
+        }
+
+        if !line.text.starts_with(':') {
+            return Err( line.error(1, RecordError::Start) );
+        }
+
+        // Check the length and the checksum of the record.
+        let bytes = line.bytes(2)?;
+
+        if (bytes.len() < 5) || (bytes.len() != (bytes[0] as usize + 5)) {
+            return Err( line.error(2, RecordError::Length) );
+        }
+
+        if bytes.iter().fold(0u8, |sum, byte| sum.wrapping_add(*byte)) != 0 {
+            return Err( line.error(2 * bytes.len(), RecordError::Checksum) );
+        }
+
+        let offset = u16::from_be_bytes( [bytes[1], bytes[2]] );
+        let data = &bytes[4..bytes.len() - 1];
+
+        // Gets the 16 or 32 bit value of an address record.
+        let value = |size: usize| match data.len() == size {
+            true => Ok( data.iter().fold(0u64, |value, byte| (value << 8) | u64::from(*byte)) ),
+            _ => Err( line.error(2, RecordError::Length) ),
+        };
+
+        match bytes[3] {
+            DATA => insert(&mut image, &line, base, offset, data)?,
+
+            EOF => match data.is_empty() {
+                true => ended = true,
+                _ => return Err( line.error(2, RecordError::Length) ),
+            },
+
+            EXTENDED_SEGMENT => base = value(2)? << 4,
+
+            START_SEGMENT => {
+                let start = value(4)?;
+                image.start = Some( ((start >> 16) << 4) + (start & 0xFFFF) );
+            },
+
+            EXTENDED_LINEAR => base = value(2)? << 16,
+
+            START_LINEAR => image.start = Some( value(4)? ),
+
+            _ => return Err( line.error(8, RecordError::Type) ),
+        }
+    }
+
+    match ended {
+        true => Ok( image ),
+        _ => Err( text::end(text).error(1, RecordError::MissingEnd) ),
+    }
+}
+
+/// Internal function to insert the data of a record into the image.
+/// The addresses of a record wrap around at the end of the 64 KiB window of its base address.
+fn insert(image: &mut MemoryImage, line: &Line, base: u64, offset: u16, data: &[u8]) -> Result<(), Error> {
+    // Split the data at the end of the window.
+    let split = core::cmp::min( data.len(), 0x1_0000 - offset as usize );
+    let (first, second) = data.split_at(split);
+
+    for (address, data) in [(base + u64::from(offset), first), (base, second)] {
+        match image.insert(address, data) {
+            Err( Error::Overlap(_) ) => return Err( line.error(10, RecordError::Overlap) ),
+            result => result?,
+        }
+    }
+
+    Ok( () )
+}
+
+/// Internal function to write a record with its checksum.
+fn record(output: &mut String, kind: u8, address: u16, data: &[u8]) {
+    use core::fmt::Write;
+
+    let [high, low] = address.to_be_bytes();
+
+    // The checksum is the two's complement of the sum of all the bytes of the record.
+    let sum = data.iter().fold( (data.len() as u8).wrapping_add(high).wrapping_add(low).wrapping_add(kind), |sum, byte| sum.wrapping_add(*byte) );
+
+    let _ = write!(output, ":{:02X}{:04X}{:02X}", data.len(), address, kind);
+
+    for byte in data {
+        let _ = write!(output, "{:02X}", byte);
+    }
+
+    let _ = write!(output, "{:02X}\r\n", sum.wrapping_neg());
+}
@@ -0,0 +1,313 @@
+//! Memory images.
+//! A sparse image of the memory of a target, made of contiguous regions of bytes, as it is
+//! written by a programmer (e.g. the contents of the flash of a microcontroller).
+
+
+
+pub mod ihex;
+pub mod srec;
+pub mod uf2;
+
+mod text;
+
+
+
+pub use ihex::{ HexAddressing, HexOptions };
+pub use srec::{ SrecOptions, SrecWidth };
+pub use uf2::{ Family, Uf2Options };
+
+use crate::error::Error;
+
+
+
+/// A contiguous region of bytes of a memory image.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Region {
+    /// Address of the first byte of the region.
+    pub(super) address: u64,
+
+    /// Contents of the region.
+    pub(super) data: Vec<u8>,
+}
+
+impl Region {
+    /// Returns the address of the first byte of the region.
+    pub fn address(&self) -> u64 {
+        self.address
+    }
+
+    /// Returns the address after the last byte of the region.
+    pub fn end(&self) -> u64 {
+        self.address + self.data.len() as u64
+    }
+
+    /// Returns the contents of the region.
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+}
+
+
+
+/// A sparse memory image.
+/// The regions are kept sorted by address, and adjacent regions are merged as they are inserted.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MemoryImage {
+    /// Contiguous regions of the image, sorted by address and neither overlapping nor adjacent.
+    regions: Vec<Region>,
+
+    /// Start address of the program (e.g. the entry point of an ELF object), if known.
+    start: Option<u64>,
+}
+
+impl MemoryImage {
+    /// Creates an empty memory image.
+    pub fn new() -> Self {
+        Self { regions: Vec::new(), start: None }
+    }
+
+    /// Reads an Intel HEX file into a memory image.
+    pub fn from_ihex(text: &str) -> Result<Self, Error> {
+        ihex::read(text)
+    }
+
+    /// Reads an S-record file (S19, S28 or S37) into a memory image.
+    pub fn from_srec(text: &str) -> Result<Self, Error> {
+        srec::read(text)
+    }
+
+    /// Reads a UF2 file into a memory image, keeping only the blocks of the given family if any.
+    pub fn from_uf2(data: &[u8], family: Option<Family>) -> Result<Self, Error> {
+        uf2::read(data, family)
+    }
+
+    /// Inserts the given data at the given address, merging it with the adjacent regions.
+    /// Data that overlaps the image is an error, with the address of the first overlapping byte.
+    pub fn insert(&mut self, address: u64, data: &[u8]) -> Result<(), Error> {
+        // Check the data fits in the address space.
+        let end = match address.checked_add( data.len() as u64 ) {
+            Some(end) => end,
+            _ => return Err( Error::Overflow(address) ),
+        };
+
+        if data.is_empty() {
+            return Ok( () );
+        }
+
+        // Get the region that follows the data.
+        let index = self.regions.partition_point(|region| region.address <= address);
+
+        // Check the data does not overlap the previous or the next region.
+        if let Some(previous) = index.checked_sub(1).map(|index| &self.regions[index]) {
+            if previous.end() > address {
+                return Err( Error::Overlap(address) );
+            }
+        }
+
+        if let Some(next) = self.regions.get(index) {
+            if next.address < end {
+                return Err( Error::Overlap(next.address) );
+            }
+        }
+
+        // Merge with the previous region or insert a new one.
+        let index = match index.checked_sub(1) {
+            Some(previous) if self.regions[previous].end() == address => {
+                self.regions[previous].data.extend_from_slice(data);
+                previous
+            },
+
+            _ => {
+                self.regions.insert( index, Region { address, data: data.to_vec() } );
+                index
+            },
+        };
+
+        // Merge with the next region.
+        if self.regions.get(index + 1).is_some_and(|next| next.address == end) {
+            let next = self.regions.remove(index + 1);
+            self.regions[index].data.extend_from_slice( &next.data );
+        }
+
+        Ok( () )
+    }
+
+    /// Writes the given data at the given address, replacing the contents of the image it overlaps
+    /// and merging it with the adjacent regions.
+    pub fn write(&mut self, address: u64, data: &[u8]) -> Result<(), Error> {
+        // Check the data fits in the address space.
+        let end = match address.checked_add( data.len() as u64 ) {
+            Some(end) => end,
+            _ => return Err( Error::Overflow(address) ),
+        };
+
+        if data.is_empty() {
+            return Ok( () );
+        }
+
+        // Get the regions that overlap or are adjacent to the data.
+        let first = self.regions.partition_point(|region| region.end() < address);
+        let last = self.regions.partition_point(|region| region.address <= end);
+
+        let mut merged: Vec<u8> = Vec::new();
+        let mut start = address;
+
+        // Keep the contents of the first region before the data.
+        if let Some(region) = self.regions[first..last].first().filter(|region| region.address < address) {
+            merged.extend_from_slice( &region.data[..(address - region.address) as usize] );
+            start = region.address;
+        }
+
+        merged.extend_from_slice(data);
+
+        // Keep the contents of the last region after the data.
+        if let Some(region) = self.regions[first..last].last().filter(|region| region.end() > end) {
+            merged.extend_from_slice( &region.data[(end - region.address) as usize..] );
+        }
+
+        self.regions.splice( first..last, core::iter::once( Region { address: start, data: merged } ) );
+
+        Ok( () )
+    }
+
+    /// Writes all the regions of the given image over this image.
+    /// The start address of the given image is kept, if it has one.
+    pub fn overlay(&mut self, image: &MemoryImage) -> Result<(), Error> {
+        for region in &image.regions {
+            self.write( region.address, &region.data )?;
+        }
+
+        if image.start.is_some() {
+            self.start = image.start;
+        }
+
+        Ok( () )
+    }
+
+    /// Fills the gaps between regions of up to `max` bytes with the given byte, merging the regions.
+    /// Use `u64::MAX` to fill all the gaps, which leaves a single region.
+    pub fn fill(&mut self, max: u64, byte: u8) {
+        let mut regions: Vec<Region> = Vec::with_capacity( self.regions.len() );
+
+        for region in self.regions.drain(..) {
+            match regions.last_mut() {
+                Some(last) if (region.address - last.end()) <= max => {
+                    let gap = (region.address - last.end()) as usize;
+
+                    last.data.resize( last.data.len() + gap, byte );
+                    last.data.extend_from_slice( &region.data );
+                },
+
+                _ => regions.push( region ),
+            }
+        }
+
+        self.regions = regions;
+    }
+
+    /// Extends the regions to the boundaries of the pages of the given size, filling with the given byte.
+    /// Regions that end up in the same or in adjacent pages are merged. Pages of 0 or 1 bytes leave the image unchanged.
+    pub fn align(&mut self, page: u64, byte: u8) {
+        if page <= 1 {
+            return;
+        }
+
+        // Gets the start and end of the pages of a region, clamped to the address space.
+        let pages = |region: &Region| {
+            let start = region.address - (region.address % page);
+
+            let end = match region.end() % page {
+                0 => region.end(),
+                rem => region.end().saturating_add(page - rem),
+            };
+
+            (start, end)
+        };
+
+        // Merge the regions that share pages, remembering the end of the pages of each.
+        let mut regions: Vec<(Region, u64)> = Vec::with_capacity( self.regions.len() );
+
+        for region in self.regions.drain(..) {
+            let (start, end) = pages(&region);
+
+            match regions.last_mut() {
+                Some((last, stop)) if start <= *stop => {
+                    let gap = (region.address - last.end()) as usize;
+
+                    last.data.resize( last.data.len() + gap, byte );
+                    last.data.extend_from_slice( &region.data );
+                    *stop = core::cmp::max(*stop, end);
+                },
+
+                _ => {
+                    let mut data = vec![byte; (region.address - start) as usize];
+                    data.extend_from_slice( &region.data );
+
+                    regions.push( (Region { address: start, data }, end) );
+                },
+            }
+        }
+
+        // Fill the regions up to the end of their last page.
+        self.regions = regions.into_iter()
+            .map(|(mut region, end)| {
+                let size = (end - region.address) as usize;
+                region.data.resize( size, byte );
+                region
+            })
+            .collect();
+    }
+
+    /// Returns the contiguous regions of the image, sorted by address.
+    pub fn regions(&self) -> &[Region] {
+        &self.regions
+    }
+
+    /// Returns the start address of the program, if known.
+    pub fn start(&self) -> Option<u64> {
+        self.start
+    }
+
+    /// Sets the start address of the program.
+    pub fn set_start(&mut self, start: Option<u64>) {
+        self.start = start;
+    }
+
+    /// Returns the bytes at the given address, if they are all in the same region.
+    pub fn read(&self, address: u64, len: usize) -> Option<&[u8]> {
+        let index = self.regions.partition_point(|region| region.address <= address).checked_sub(1)?;
+        let region = &self.regions[index];
+
+        let offset = (address - region.address) as usize;
+        region.data.get( offset..offset.checked_add(len)? )
+    }
+
+    /// Returns the number of bytes in the image.
+    pub fn size(&self) -> u64 {
+        self.regions.iter().map(|region| region.data.len() as u64).sum()
+    }
+
+    /// Returns `true` if the image has no bytes.
+    pub fn is_empty(&self) -> bool {
+        self.regions.is_empty()
+    }
+
+    /// Writes the image as Intel HEX records, starting a new data record at each region.
+    /// Unless a start address is given in the options, the start address of the image is used.
+    pub fn to_ihex(&self, options: &HexOptions) -> Result<String, Error> {
+        let start = options.start.or(self.start);
+        ihex::write( self.regions.iter().map(|region| (region.address, &region.data[..])), &HexOptions { start, ..*options } )
+    }
+
+    /// Writes the image as S-records, starting a new data record at each region.
+    /// Unless a start address is given in the options, the start address of the image is used.
+    pub fn to_srec(&self, options: &SrecOptions) -> Result<String, Error> {
+        let start = options.start.or(self.start);
+        srec::write( self.regions.iter().map(|region| (region.address, &region.data[..])), &SrecOptions { start, ..options.clone() } )
+    }
+
+    /// Writes the image as a UF2 file, with the payloads aligned to their size.
+    pub fn to_uf2(&self, options: &Uf2Options) -> Result<Vec<u8>, Error> {
+        uf2::write(self, options)
+    }
+}
@@ -0,0 +1,255 @@
+//! Motorola S-record format.
+//! Writes memory contents as S-records (S19, S28 or S37), with the same records and layout as the
+//! GNU binutils (`objcopy -O srec`), and reads S-record files into memory images.
+
+
+
+use crate::error::{ Error, RecordError };
+
+use super::{
+    MemoryImage,
+    text,
+};
+
+
+
+/// The address width of the records of an S-record file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SrecWidth {
+    /// The smallest width that fits the highest address of the data.
+    Auto,
+
+    /// 16 bit addresses (S1 data records and S9 termination record).
+    S19,
+
+    /// 24 bit addresses (S2 data records and S8 termination record).
+    S28,
+
+    /// 32 bit addresses (S3 data records and S7 termination record).
+    S37,
+}
+
+impl SrecWidth {
+    /// Returns the size in bytes of the addresses, or 0 for the automatic width.
+    pub const fn size(&self) -> usize {
+        match *self {
+            SrecWidth::Auto => 0,
+            SrecWidth::S19 => 2,
+            SrecWidth::S28 => 3,
+            SrecWidth::S37 => 4,
+        }
+    }
+}
+
+impl core::fmt::Display for SrecWidth {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        let s = match *self {
+            SrecWidth::Auto => "Auto",
+            SrecWidth::S19 => "S19",
+            SrecWidth::S28 => "S28",
+            SrecWidth::S37 => "S37",
+        };
+
+        write!(f, "{}", s)
+    }
+}
+
+
+
+/// Options of the S-record writer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SrecOptions {
+    /// Module name written in the S0 header record, up to 40 bytes.
+    /// The GNU binutils write the name of the output file.
+    pub header: String,
+
+    /// Maximum number of data bytes of each record, limited by the record size of the address width.
+    pub record: u8,
+
+    /// Address width of the records.
+    pub width: SrecWidth,
+
+    /// Write an S5 (or S6) record with the number of data records.
+    pub count: bool,
+
+    /// Start address of the program, written in the termination record.
+    pub start: Option<u64>,
+}
+
+impl Default for SrecOptions {
+    fn default() -> Self {
+        Self { header: String::new(), record: 16, width: SrecWidth::Auto, count: false, start: None }
+    }
+}
+
+
+
+/// Writes the given chunks of data (address and contents, sorted by address) as S-records.
+/// Each chunk starts a new data record.
+pub fn write<'a, I: IntoIterator<Item = (u64, &'a [u8])>>(chunks: I, options: &SrecOptions) -> Result<String, Error> {
+    let chunks = chunks.into_iter()
+        .map(|(address, data)| Ok( (text::address(address)?, data) ))
+        .collect::<Result<Vec<_>, Error>>()?;
+
+    // Get the address width that fits the highest address of the data.
+    let last = chunks.iter()
+        .filter(|(_, data)| !data.is_empty())
+        .map(|(address, data)| address + data.len() as u64 - 1)
+        .max()
+        .unwrap_or(0);
+
+    let size = match options.width {
+        SrecWidth::Auto if last <= 0xFFFF => 2,
+        SrecWidth::Auto if last <= 0xFF_FFFF => 3,
+        SrecWidth::Auto => 4,
+        width => width.size(),
+    };
+
+    // Check all the data fits in the address width.
+    let limit = 1u64 << (8 * size);
+
+    if let Some((address, _)) = chunks.iter().find(|(address, data)| (address + data.len() as u64) > limit) {
+        return Err( Error::Overflow(*address) );
+    }
+
+    let mut output = String::new();
+
+    // Write the header.
+    let header = options.header.as_bytes();
+    record( &mut output, 0, 2, 0, &header[..core::cmp::min(header.len(), 40)] );
+
+    // Write the data records.
+    let len = core::cmp::min( core::cmp::max(options.record, 1) as usize, 254 - size );
+    let mut count = 0;
+
+    for (address, data) in chunks {
+        for (index, chunk) in data.chunks(len).enumerate() {
+            record( &mut output, size - 1, size, address + (index * len) as u64, chunk );
+            count += 1;
+        }
+    }
+
+    // Write the number of data records.
+    if options.count {
+        match count {
+            0..=0xFFFF => record( &mut output, 5, 2, count, &[] ),
+            0x1_0000..=0xFF_FFFF => record( &mut output, 6, 3, count, &[] ),
+            _ => (),
+        }
+    }
+
+    // Write the termination record with the start address.
+    let start = text::address( options.start.unwrap_or(0) )?;
+
+    if start >= limit {
+        return Err( Error::Overflow(start) );
+    }
+
+    record( &mut output, 11 - size, size, start, &[] );
+
+    Ok( output )
+}
+
+/// Reads an S-record file into a memory image.
+/// The checksum and length of all records are checked, data records must not overlap, the header
+/// must be the first record, the count must match the data records, and the file must end with a
+/// termination record, which sets the start of the image.
+pub fn read(text: &str) -> Result<MemoryImage, Error> {
+    let mut image = MemoryImage::new();
+
+    // Number of data records read.
+    let mut data = 0;
+
+    let mut counted = false;
+    let mut ended = false;
+
+    for (index, line) in text::lines(text).enumerate() {
+        // No records are allowed after the termination record.
+        if ended {
+            return Err( line.error(1, RecordError::Order) );
+        }
+
+        if !line.text.starts_with('S') {
+            return Err( line.error(1, RecordError::Start) );
+        }
+
+        // Get the size of the address of the record.
+        let kind = line.text.as_bytes().get(1).copied().unwrap_or(b' ');
+
+        let size = match kind {
+            b'0' | b'1' | b'5' | b'9' => 2,
+            b'2' | b'6' | b'8' => 3,
+            b'3' | b'7' => 4,
+            _ => return Err( line.error(2, RecordError::Type) ),
+        };
+
+        // Check the length and the checksum of the record.
+        let bytes = line.bytes(3)?;
+
+        if (bytes.len() < (size + 2)) || (bytes.len() != (bytes[0] as usize + 1)) {
+            return Err( line.error(3, RecordError::Length) );
+        }
+
+        if bytes.iter().fold(0u8, |sum, byte| sum.wrapping_add(*byte)) != 0xFF {
+            return Err( line.error(1 + 2 * bytes.len(), RecordError::Checksum) );
+        }
+
+        let address = bytes[1..=size].iter().fold(0u64, |value, byte| (value << 8) | u64::from(*byte));
+        let contents = &bytes[size + 1..bytes.len() - 1];
+
+        match kind {
+            // The header must be the first record.
+            b'0' if index != 0 => return Err( line.error(1, RecordError::Order) ),
+            b'0' => (),
+
+            // Data records must come before the count record.
+            b'1' | b'2' | b'3' if counted => return Err( line.error(1, RecordError::Order) ),
+
+            b'1' | b'2' | b'3' => {
+                match image.insert(address, contents) {
+                    Err( Error::Overlap(_) ) => return Err( line.error(5 + 2 * size, RecordError::Overlap) ),
+                    result => result?,
+                }
+
+                data += 1;
+            },
+
+            // Count records hold the number of data records in the address field.
+            b'5' | b'6' if !contents.is_empty() => return Err( line.error(3, RecordError::Length) ),
+            b'5' | b'6' if address != data => return Err( line.error(5, RecordError::Count) ),
+            b'5' | b'6' => counted = true,
+
+            // Termination records hold the start address.
+            _ if !contents.is_empty() => return Err( line.error(3, RecordError::Length) ),
+
+            _ => {
+                image.start = Some( address );
+                ended = true;
+            },
+        }
+    }
+
+    match ended {
+        true => Ok( image ),
+        _ => Err( text::end(text).error(1, RecordError::MissingEnd) ),
+    }
+}
+
+/// Internal function to write a record of the given type and address size with its checksum.
+fn record(output: &mut String, kind: usize, size: usize, address: u64, data: &[u8]) {
+    use core::fmt::Write;
+
+    let address = &address.to_be_bytes()[8 - size..];
+    let count = (size + data.len() + 1) as u8;
+
+    // The checksum is the one's complement of the sum of the count, address and data bytes.
+    let sum = address.iter().chain(data).fold(count, |sum, byte| sum.wrapping_add(*byte));
+
+    let _ = write!(output, "S{}{:02X}", kind, count);
+
+    for byte in address.iter().chain(data) {
+        let _ = write!(output, "{:02X}", byte);
+    }
+
+    let _ = write!(output, "{:02X}\r\n", !sum);
+}
@@ -0,0 +1,68 @@
+//! Common parsing and writing of the text object file formats.
+//! Both Intel HEX and S-record files are made of lines with a start character followed by the
+//! bytes of the record as pairs of hexadecimal digits.
+
+
+
+use crate::error::{ Error, RecordError };
+
+
+
+/// A line of a text object file.
+pub(super) struct Line<'a> {
+    /// Number of the line, starting at 1.
+    pub(super) number: usize,
+
+    /// Contents of the line, without the line ending and the trailing whitespace.
+    pub(super) text: &'a str,
+}
+
+impl<'a> Line<'a> {
+    /// Returns the error of the given kind at the given column of the line.
+    pub(super) fn error(&self, column: usize, kind: RecordError) -> Error {
+        Error::BadRecord { line: self.number, column, kind }
+    }
+
+    /// Decodes the pairs of hexadecimal digits of the line, starting at the given column.
+    pub(super) fn bytes(&self, column: usize) -> Result<Vec<u8>, Error> {
+        let digits = self.text.as_bytes().get(column - 1..).unwrap_or(&[]);
+
+        // Get the value of each digit.
+        let value = |index: usize| match digits.get(index).and_then(|digit| (*digit as char).to_digit(16)) {
+            Some(value) => Ok( value as u8 ),
+            _ => Err( self.error(column + index, RecordError::Digit) ),
+        };
+
+        (0..digits.len()).step_by(2)
+            .map(|index| match digits.len() - index {
+                1 => Err( self.error(column + index + 1, RecordError::Length) ),
+                _ => Ok( (value(index)? << 4) | value(index + 1)? ),
+            })
+            .collect()
+    }
+}
+
+
+
+/// Returns the lines of the given text that are not blank.
+pub(super) fn lines(text: &str) -> impl Iterator<Item = Line<'_>> {
+    text.lines()
+        .enumerate()
+        .map(|(index, text)| Line { number: index + 1, text: text.trim_end() })
+        .filter(|line| !line.text.is_empty())
+}
+
+/// Returns the line after the last line of the given text, to report a missing record.
+pub(super) fn end(text: &str) -> Line<'_> {
+    Line { number: text.lines().count() + 1, text: "" }
+}
+
+/// Checks an address fits in the 32 bits of the text formats.
+/// Sign extended 32 bit addresses (e.g. of 64 bit MIPS objects) are truncated.
+pub(super) fn address(address: u64) -> Result<u64, Error> {
+    match address {
+        0..=0xFFFF_FFFF => Ok( address ),
+        0xFFFF_FFFF_8000_0000..=0xFFFF_FFFF_FFFF_FFFF => Ok( address & 0xFFFF_FFFF ),
+        _ => Err( Error::Overflow(address) ),
+    }
+}
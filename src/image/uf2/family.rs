@@ -0,0 +1,164 @@
+//! Known UF2 family IDs.
+//! The family ID of a UF2 block tells the bootloader which chip the payload is built for.
+//! The values are those of the UF2 specification (`uf2families.json`).
+
+
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Family {
+    SAMD21        ,
+    SAMD51        ,
+    NRF52         ,
+    NRF52833      ,
+    NRF52840      ,
+    STM32F0       ,
+    STM32F1       ,
+    STM32F2       ,
+    STM32F3       ,
+    STM32F4       ,
+    STM32F7       ,
+    STM32G0       ,
+    STM32G4       ,
+    STM32H7       ,
+    STM32L4       ,
+    STM32WB       ,
+    ESP8266       ,
+    ESP32         ,
+    ESP32S2       ,
+    ESP32S3       ,
+    ESP32C3       ,
+    RP2040        ,
+    RP2350ArmS    ,
+    RP2350RiscV   ,
+    MIMXRT10XX    ,
+    Other(u32)    ,
+}
+
+impl Family {
+    /// All the known families.
+    pub const KNOWN: [Family; 25] = [
+        Family::SAMD21, Family::SAMD51, Family::NRF52, Family::NRF52833, Family::NRF52840,
+        Family::STM32F0, Family::STM32F1, Family::STM32F2, Family::STM32F3, Family::STM32F4,
+        Family::STM32F7, Family::STM32G0, Family::STM32G4, Family::STM32H7, Family::STM32L4,
+        Family::STM32WB, Family::ESP8266, Family::ESP32, Family::ESP32S2, Family::ESP32S3,
+        Family::ESP32C3, Family::RP2040, Family::RP2350ArmS, Family::RP2350RiscV, Family::MIMXRT10XX,
+    ];
+
+    /// Returns the known family with the given name (e.g. `RP2040`, `nRF52840` or `ESP32-S2`),
+    /// ignoring the case and the dashes.
+    pub fn from_name(name: &str) -> Option<Family> {
+        let normalize = |name: &str| name.chars().filter(|c| *c != '-').collect::<String>().to_ascii_lowercase();
+        let name = normalize(name);
+
+        Self::KNOWN.iter().copied().find(|family| normalize( &family.to_string() ) == name)
+    }
+}
+
+impl core::convert::From<u32> for Family {
+    fn from(id: u32) -> Family {
+        use Family::*;
+
+        match id {
+            0x68ED2B88 => SAMD21,
+            0x55114460 => SAMD51,
+            0x1B57745F => NRF52,
+            0x621E937A => NRF52833,
+            0xADA52840 => NRF52840,
+            0x647824B3 => STM32F0,
+            0x5EE21072 => STM32F1,
+            0x5D1A0A2E => STM32F2,
+            0x6B846188 => STM32F3,
+            0x57755A57 => STM32F4,
+            0x53B80F00 => STM32F7,
+            0x300F5633 => STM32G0,
+            0x4C71240A => STM32G4,
+            0x6DB66082 => STM32H7,
+            0x00FF6919 => STM32L4,
+            0x70D16653 => STM32WB,
+            0x7EAB61ED => ESP8266,
+            0x1C5F21B0 => ESP32,
+            0xBFDD4EEE => ESP32S2,
+            0xC47E5767 => ESP32S3,
+            0xD42BA06C => ESP32C3,
+            0xE48BFF56 => RP2040,
+            0xE48BFF59 => RP2350ArmS,
+            0xE48BFF5A => RP2350RiscV,
+            0x4FB2D5BD => MIMXRT10XX,
+
+            _ => Other(id),
+        }
+    }
+}
+
+impl core::convert::From<Family> for u32 {
+    fn from(family: Family) -> u32 {
+        use Family::*;
+
+        match family {
+            SAMD21      => 0x68ED2B88,
+            SAMD51      => 0x55114460,
+            NRF52       => 0x1B57745F,
+            NRF52833    => 0x621E937A,
+            NRF52840    => 0xADA52840,
+            STM32F0     => 0x647824B3,
+            STM32F1     => 0x5EE21072,
+            STM32F2     => 0x5D1A0A2E,
+            STM32F3     => 0x6B846188,
+            STM32F4     => 0x57755A57,
+            STM32F7     => 0x53B80F00,
+            STM32G0     => 0x300F5633,
+            STM32G4     => 0x4C71240A,
+            STM32H7     => 0x6DB66082,
+            STM32L4     => 0x00FF6919,
+            STM32WB     => 0x70D16653,
+            ESP8266     => 0x7EAB61ED,
+            ESP32       => 0x1C5F21B0,
+            ESP32S2     => 0xBFDD4EEE,
+            ESP32S3     => 0xC47E5767,
+            ESP32C3     => 0xD42BA06C,
+            RP2040      => 0xE48BFF56,
+            RP2350ArmS  => 0xE48BFF59,
+            RP2350RiscV => 0xE48BFF5A,
+            MIMXRT10XX  => 0x4FB2D5BD,
+
+            Other(id) => id,
+        }
+    }
+}
+
+impl core::fmt::Display for Family {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        use Family::*;
+
+        let arg = match *self {
+            SAMD21      => String::from("SAMD21"),
+            SAMD51      => String::from("SAMD51"),
+            NRF52       => String::from("nRF52"),
+            NRF52833    => String::from("nRF52833"),
+            NRF52840    => String::from("nRF52840"),
+            STM32F0     => String::from("STM32F0"),
+            STM32F1     => String::from("STM32F1"),
+            STM32F2     => String::from("STM32F2"),
+            STM32F3     => String::from("STM32F3"),
+            STM32F4     => String::from("STM32F4"),
+            STM32F7     => String::from("STM32F7"),
+            STM32G0     => String::from("STM32G0"),
+            STM32G4     => String::from("STM32G4"),
+            STM32H7     => String::from("STM32H7"),
+            STM32L4     => String::from("STM32L4"),
+            STM32WB     => String::from("STM32WB"),
+            ESP8266     => String::from("ESP8266"),
+            ESP32       => String::from("ESP32"),
+            ESP32S2     => String::from("ESP32-S2"),
+            ESP32S3     => String::from("ESP32-S3"),
+            ESP32C3     => String::from("ESP32-C3"),
+            RP2040      => String::from("RP2040"),
+            RP2350ArmS  => String::from("RP2350-ARM-S"),
+            RP2350RiscV => String::from("RP2350-RISCV"),
+            MIMXRT10XX  => String::from("MIMXRT10XX"),
+            Other(id)   => format!("Unknown (0x{:08X})", id),
+        };
+
+        write!(f, "{}", arg)
+    }
+}
@@ -0,0 +1,162 @@
+//! UF2 (USB Flashing Format) files.
+//! A UF2 file is a sequence of 512 byte blocks, each with the target address and payload of a part
+//! of the flash, as copied to the mass storage drive of a UF2 bootloader.
+//! Writes memory images as UF2 files and reads UF2 files back into memory images.
+
+
+
+mod family;
+
+
+
+pub use family::Family;
+
+use crate::error::{ BlockError, Error };
+
+use super::MemoryImage;
+
+
+
+/// Size of a block.
+const BLOCK: usize = 512;
+
+/// Size of the data area of a block.
+const DATA: usize = 476;
+
+/// First magic number, at the start of the block.
+const MAGIC_START0: u32 = 0x0A324655;
+
+/// Second magic number, after the first.
+const MAGIC_START1: u32 = 0x9E5D5157;
+
+/// Final magic number, at the end of the block.
+const MAGIC_END: u32 = 0x0AB16F30;
+
+/// The block is not meant for the main flash (e.g. comments) and must be skipped.
+const NOT_MAIN_FLASH: u32 = 0x0000_0001;
+
+/// The family ID field of the block is present.
+const FAMILY_ID_PRESENT: u32 = 0x0000_2000;
+
+
+
+/// Options of the UF2 writer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Uf2Options {
+    /// Family of the target, written in the family ID field of every block.
+    /// Without a family, the field holds the file size (0), as the bootloaders without family IDs expect.
+    pub family: Option<Family>,
+
+    /// Number of payload bytes of each block, which is also the page the blocks are aligned to.
+    /// Most bootloaders only accept 256 byte payloads.
+    pub payload: u16,
+
+    /// Byte used to fill the payloads up to the boundaries of the pages (0, as `uf2conv.py` and `elf2uf2`).
+    pub fill: u8,
+}
+
+impl Default for Uf2Options {
+    fn default() -> Self {
+        Self { family: None, payload: 256, fill: 0 }
+    }
+}
+
+
+
+/// Writes the given memory image as a UF2 file.
+/// The regions are extended to the payload boundaries and split into one block per payload.
+pub fn write(image: &MemoryImage, options: &Uf2Options) -> Result<Vec<u8>, Error> {
+    let payload = core::cmp::min( core::cmp::max(options.payload, 1) as usize, DATA );
+
+    // Align the regions to the payloads.
+    let mut image = image.clone();
+    image.align( payload as u64, options.fill );
+
+    // Get the address of each block, checking they fit in 32 bits.
+    let mut blocks = Vec::new();
+
+    for region in image.regions() {
+        if region.end() > 0x1_0000_0000 {
+            return Err( Error::Overflow( region.address() ) );
+        }
+
+        for (index, chunk) in region.data().chunks(payload).enumerate() {
+            blocks.push( (region.address() + (index * payload) as u64, chunk) );
+        }
+    }
+
+    let (flags, family) = match options.family {
+        Some(family) => (FAMILY_ID_PRESENT, u32::from(family)),
+        _ => (0, 0),
+    };
+
+    let mut output = Vec::with_capacity( blocks.len() * BLOCK );
+
+    for (number, (address, chunk)) in blocks.iter().enumerate() {
+        // Write the header.
+        for field in [MAGIC_START0, MAGIC_START1, flags, *address as u32, chunk.len() as u32, number as u32, blocks.len() as u32, family] {
+            output.extend_from_slice( &field.to_le_bytes() );
+        }
+
+        // Write the payload, padded with zeros up to the end of the data area.
+        output.extend_from_slice( chunk );
+        output.resize( output.len() + DATA - chunk.len(), 0 );
+
+        output.extend_from_slice( &MAGIC_END.to_le_bytes() );
+    }
+
+    Ok( output )
+}
+
+/// Reads a UF2 file into a memory image.
+/// The magic numbers, payload size and block number of all blocks are checked, and payloads must
+/// not overlap. Blocks not meant for the main flash are skipped, as are the blocks of other families
+/// if a family is given.
+pub fn read(data: &[u8], family: Option<Family>) -> Result<MemoryImage, Error> {
+    let mut image = MemoryImage::new();
+
+    for (index, block) in data.chunks(BLOCK).enumerate() {
+        let error = |kind| Error::BadBlock { index, kind };
+
+        if block.len() != BLOCK {
+            return Err( error(BlockError::Truncated) );
+        }
+
+        // Gets the little endian word at the given offset.
+        let word = |offset: usize| u32::from_le_bytes( [block[offset], block[offset + 1], block[offset + 2], block[offset + 3]] );
+
+        if (word(0) != MAGIC_START0) || (word(4) != MAGIC_START1) || (word(BLOCK - 4) != MAGIC_END) {
+            return Err( error(BlockError::Magic) );
+        }
+
+        let flags = word(8);
+        let address = word(12);
+        let size = word(16) as usize;
+
+        if size > DATA {
+            return Err( error(BlockError::PayloadSize) );
+        }
+
+        if word(20) >= word(24) {
+            return Err( error(BlockError::Number) );
+        }
+
+        // Skip the blocks that are not for the main flash or for the given family, compared by their raw ID.
+        if (flags & NOT_MAIN_FLASH) != 0 {
+            continue;
+        }
+
+        if let Some(family) = family {
+            if ((flags & FAMILY_ID_PRESENT) == 0) || (word(28) != u32::from(family)) {
+                continue;
+            }
+        }
+
+        match image.insert( u64::from(address), &block[32..32 + size] ) {
+            Err( Error::Overlap(_) ) => return Err( error(BlockError::Overlap) ),
+            result => result?,
+        }
+    }
+
+    Ok( image )
+}
@@ -6,3 +6,9 @@
 
 pub mod common;
 pub mod elf;
+pub mod error;
+pub mod image;
+
+
+
+pub use error::Error;
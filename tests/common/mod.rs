@@ -0,0 +1,427 @@
+//! Common utilities of the integration tests.
+//! Builds small ELF file images of any class and endianness.
+
+#![allow(dead_code)]
+
+
+
+/// A section of a test image.
+pub struct Section {
+    /// Name of the section.
+    pub name: &'static str,
+
+    /// Raw section type.
+    pub stype: u32,
+
+    /// Raw section flags.
+    pub flags: u64,
+
+    /// Virtual address of the section.
+    pub addr: u64,
+
+    /// Contents of the section.
+    pub data: Vec<u8>,
+
+    /// Size in memory of a section without contents (`SHT_NOBITS`).
+    pub nobits: u64,
+
+    /// Link field of the section.
+    pub link: u32,
+
+    /// Info field of the section.
+    pub info: u32,
+
+    /// Alignment of the section.
+    pub align: u64,
+
+    /// Entry size of the section.
+    pub entsize: u64,
+}
+
+impl Section {
+    /// Creates a section with the given name, type and contents.
+    pub fn new(name: &'static str, stype: u32, data: Vec<u8>) -> Self {
+        Self { name, stype, flags: 0, addr: 0, data, nobits: 0, link: 0, info: 0, align: 1, entsize: 0 }
+    }
+
+    /// Sets the flags and the address of the section.
+    pub fn alloc(mut self, flags: u64, addr: u64) -> Self {
+        self.flags = flags;
+        self.addr = addr;
+        self
+    }
+
+    /// Sets the alignment of the section.
+    pub fn align(mut self, align: u64) -> Self {
+        self.align = align;
+        self
+    }
+
+    /// Sets the link and info fields of the section.
+    pub fn link(mut self, link: u32, info: u32) -> Self {
+        self.link = link;
+        self.info = info;
+        self
+    }
+
+    /// Sets the entry size of the section.
+    pub fn entsize(mut self, entsize: u64) -> Self {
+        self.entsize = entsize;
+        self
+    }
+}
+
+/// A segment of a test image, covering a range of sections.
+pub struct Program {
+    /// Raw program type.
+    pub ptype: u32,
+
+    /// Raw program flags.
+    pub flags: u32,
+
+    /// Index of the first and last builder sections covered by the segment.
+    pub sections: (usize, usize),
+
+    /// Physical address of the segment.
+    pub paddr: u64,
+}
+
+/// A symbol of a test image.
+pub struct Sym {
+    /// Name of the symbol.
+    pub name: &'static str,
+
+    /// Value of the symbol.
+    pub value: u64,
+
+    /// Size of the symbol.
+    pub size: u64,
+
+    /// Raw info field (bind and type).
+    pub info: u8,
+
+    /// Raw other field.
+    pub other: u8,
+
+    /// Section index of the symbol.
+    pub shndx: u16,
+}
+
+impl Sym {
+    /// Creates a symbol with the given name, value, size, info and section.
+    pub fn new(name: &'static str, value: u64, size: u64, info: u8, shndx: u16) -> Self {
+        Self { name, value, size, info, other: 0, shndx }
+    }
+}
+
+/// A test ELF image builder.
+/// Builder section `i` becomes ELF section `i + 1`, followed by `.symtab`,
+/// `.strtab` (if there are symbols) and `.shstrtab`.
+pub struct Builder {
+    /// `true` for 64 bit files.
+    pub class64: bool,
+
+    /// `true` for big endian files.
+    pub big: bool,
+
+    /// Raw file type.
+    pub filetype: u16,
+
+    /// Raw machine.
+    pub machine: u16,
+
+    /// Entry point.
+    pub entry: u64,
+
+    /// Raw architecture flags.
+    pub flags: u32,
+
+    /// Sections of the image.
+    pub sections: Vec<Section>,
+
+    /// Segments of the image.
+    pub programs: Vec<Program>,
+
+    /// Symbols of the image (without the null symbol).
+    pub symbols: Vec<Sym>,
+}
+
+impl Builder {
+    /// Creates an empty executable image builder.
+    pub fn new(class64: bool, big: bool, machine: u16) -> Self {
+        Self { class64, big, filetype: 2, machine, entry: 0, flags: 0, sections: Vec::new(), programs: Vec::new(), symbols: Vec::new() }
+    }
+
+    /// Encodes a 16 bit integer.
+    pub fn half(&self, v: u16) -> Vec<u8> {
+        match self.big {
+            true => v.to_be_bytes().to_vec(),
+            _ => v.to_le_bytes().to_vec(),
+        }
+    }
+
+    /// Encodes a 32 bit integer.
+    pub fn word(&self, v: u32) -> Vec<u8> {
+        match self.big {
+            true => v.to_be_bytes().to_vec(),
+            _ => v.to_le_bytes().to_vec(),
+        }
+    }
+
+    /// Encodes a 64 bit integer.
+    pub fn xword(&self, v: u64) -> Vec<u8> {
+        match self.big {
+            true => v.to_be_bytes().to_vec(),
+            _ => v.to_le_bytes().to_vec(),
+        }
+    }
+
+    /// Encodes an address of the class.
+    pub fn addr(&self, v: u64) -> Vec<u8> {
+        match self.class64 {
+            true => self.xword(v),
+            _ => self.word(v as u32),
+        }
+    }
+
+    /// Encodes a relocation entry, with an explicit addend if one is given.
+    pub fn reloc(&self, offset: u64, symbol: u64, kind: u64, addend: Option<i64>) -> Vec<u8> {
+        let info = match self.class64 {
+            true => (symbol << 32) | kind,
+            _ => (symbol << 8) | kind,
+        };
+
+        let mut entry = self.addr(offset);
+        entry.extend( self.addr(info) );
+
+        if let Some(addend) = addend {
+            entry.extend( self.addr(addend as u64) );
+        }
+
+        entry
+    }
+
+    /// Encodes a symbol table entry with the given name offset.
+    pub fn sym(&self, name: u32, sym: &Sym) -> Vec<u8> {
+        let mut entry = self.word(name);
+
+        if self.class64 {
+            entry.push( sym.info );
+            entry.push( sym.other );
+            entry.extend( self.half(sym.shndx) );
+            entry.extend( self.xword(sym.value) );
+            entry.extend( self.xword(sym.size) );
+        } else {
+            entry.extend( self.word(sym.value as u32) );
+            entry.extend( self.word(sym.size as u32) );
+            entry.push( sym.info );
+            entry.push( sym.other );
+            entry.extend( self.half(sym.shndx) );
+        }
+
+        entry
+    }
+
+    /// Returns the ELF index of the `.symtab` section.
+    pub fn symtab(&self) -> u32 {
+        self.sections.len() as u32 + 1
+    }
+
+    /// Builds the file image.
+    pub fn build(&self) -> Vec<u8> {
+        let inc = if self.class64 { 8 } else { 4 };
+        let ehsize = 40 + (3 * inc);
+        let phentsize = 8 + (6 * inc);
+        let shentsize = 16 + (6 * inc);
+        let symsize = 8 + (2 * inc);
+
+        // Collect all sections, adding the symbol and string tables.
+        let mut sections: Vec<Section> = Vec::new();
+
+        for s in &self.sections {
+            sections.push( Section { name: s.name, stype: s.stype, flags: s.flags, addr: s.addr, data: s.data.clone(), nobits: s.nobits, link: s.link, info: s.info, align: s.align, entsize: s.entsize } );
+        }
+
+        if !self.symbols.is_empty() {
+            let mut strtab = vec![0u8];
+            let mut symtab = vec![0u8; symsize];
+            let mut locals = 1;
+
+            for sym in &self.symbols {
+                let name = strtab.len() as u32;
+                strtab.extend_from_slice( sym.name.as_bytes() );
+                strtab.push(0);
+
+                if (sym.info >> 4) == 0 {
+                    locals += 1;
+                }
+
+                symtab.extend( self.sym(name, sym) );
+            }
+
+            let strndx = self.symtab() + 1;
+            sections.push( Section::new(".symtab", 2, symtab).link(strndx, locals).align(inc as u64).entsize(symsize as u64) );
+            sections.push( Section::new(".strtab", 3, strtab) );
+        }
+
+        // Build the section names.
+        let mut shstrtab = vec![0u8];
+        let mut names = Vec::new();
+
+        for s in sections.iter().map(|s| s.name).chain( core::iter::once(".shstrtab") ) {
+            names.push( shstrtab.len() as u32 );
+            shstrtab.extend_from_slice( s.as_bytes() );
+            shstrtab.push(0);
+        }
+
+        sections.push( Section::new(".shstrtab", 3, shstrtab) );
+
+        // Lay out the file.
+        let phoff = if self.programs.is_empty() { 0 } else { ehsize };
+        let mut offset = ehsize + (self.programs.len() * phentsize);
+        let mut offsets = Vec::new();
+
+        for s in &sections {
+            let align = core::cmp::max(s.align, 1) as usize;
+            offset = offset.div_ceil(align) * align;
+            offsets.push(offset);
+
+            if s.stype != 8 {
+                offset += s.data.len();
+            }
+        }
+
+        let shoff = offset.div_ceil(8) * 8;
+        let mut image = vec![0u8; shoff + ((sections.len() + 1) * shentsize)];
+
+        // File header.
+        image[0..4].copy_from_slice( &[0x7F, b'E', b'L', b'F'] );
+        image[4] = if self.class64 { 2 } else { 1 };
+        image[5] = if self.big { 2 } else { 1 };
+        image[6] = 1;
+
+        let mut header = Vec::new();
+        header.extend( self.half(self.filetype) );
+        header.extend( self.half(self.machine) );
+        header.extend( self.word(1) );
+        header.extend( self.addr(self.entry) );
+        header.extend( self.addr(phoff as u64) );
+        header.extend( self.addr(shoff as u64) );
+        header.extend( self.word(self.flags) );
+        header.extend( self.half(ehsize as u16) );
+        header.extend( self.half(phentsize as u16) );
+        header.extend( self.half(self.programs.len() as u16) );
+        header.extend( self.half(shentsize as u16) );
+        header.extend( self.half(sections.len() as u16 + 1) );
+        header.extend( self.half(sections.len() as u16) );
+        image[16..ehsize].copy_from_slice( &header );
+
+        // Program headers.
+        for (i, p) in self.programs.iter().enumerate() {
+            let (first, last) = p.sections;
+            let start = offsets[first];
+            let vaddr = sections[first].addr;
+            let filesz = (first..=last).filter(|j| sections[*j].stype != 8).map(|j| offsets[j] + sections[j].data.len()).max().unwrap_or(start) - start;
+            let memsz = (first..=last).map(|j| sections[j].addr + core::cmp::max(sections[j].data.len() as u64, sections[j].nobits)).max().unwrap_or(vaddr) - vaddr;
+
+            let mut ph = Vec::new();
+            ph.extend( self.word(p.ptype) );
+
+            if self.class64 {
+                ph.extend( self.word(p.flags) );
+            }
+
+            ph.extend( self.addr(start as u64) );
+            ph.extend( self.addr(vaddr) );
+            ph.extend( self.addr(p.paddr) );
+            ph.extend( self.addr(filesz as u64) );
+            ph.extend( self.addr(memsz) );
+
+            if !self.class64 {
+                ph.extend( self.word(p.flags) );
+            }
+
+            // Note segments take the alignment of their notes.
+            let align = match p.ptype {
+                4 => core::cmp::max(sections[first].align, 4),
+                _ => 4,
+            };
+
+            ph.extend( self.addr(align) );
+
+            let at = ehsize + (i * phentsize);
+            image[at..at+phentsize].copy_from_slice( &ph );
+        }
+
+        // Section contents and headers.
+        for (i, s) in sections.iter().enumerate() {
+            if s.stype != 8 {
+                image[offsets[i]..offsets[i]+s.data.len()].copy_from_slice( &s.data );
+            }
+
+            let size = if s.stype == 8 { s.nobits } else { s.data.len() as u64 };
+
+            let mut sh = Vec::new();
+            sh.extend( self.word(names[i]) );
+            sh.extend( self.word(s.stype) );
+            sh.extend( self.addr(s.flags) );
+            sh.extend( self.addr(s.addr) );
+            sh.extend( self.addr(offsets[i] as u64) );
+            sh.extend( self.addr(size) );
+            sh.extend( self.word(s.link) );
+            sh.extend( self.word(s.info) );
+            sh.extend( self.addr(s.align) );
+            sh.extend( self.addr(s.entsize) );
+
+            let at = shoff + ((i + 1) * shentsize);
+            image[at..at+shentsize].copy_from_slice( &sh );
+        }
+
+        image
+    }
+}
+
+
+
+/// Removes the section headers from a file image, leaving only the segments.
+pub fn strip(image: &mut [u8], class64: bool) {
+    let (shoff, shnum) = match class64 {
+        true => (0x28..0x30, 0x3C..0x40),
+        _ => (0x20..0x24, 0x30..0x34),
+    };
+
+    image[shoff].iter_mut().for_each(|b| *b = 0);
+    image[shnum].iter_mut().for_each(|b| *b = 0);
+}
+
+/// Builds a small firmware-like image with code, data, bss and symbols.
+pub fn firmware(class64: bool, big: bool) -> Builder {
+    let mut b = Builder::new(class64, big, 0x28);
+    b.entry = 0x0800_0001;
+
+    b.sections.push( Section::new(".text", 1, vec![0x70, 0x47, 0x00, 0xBF, 0x01, 0x20, 0x70, 0x47]).alloc(0x6, 0x0800_0000).align(4) );
+    b.sections.push( Section::new(".rodata", 1, b"micro-elf\0".to_vec()).alloc(0x2, 0x0800_0008).align(1) );
+
+    let mut data = b.word(0xDEAD_BEEF);
+    data.extend( b.word(3) );
+    b.sections.push( Section::new(".data", 1, data).alloc(0x3, 0x2000_0000).align(4) );
+
+    let mut bss = Section::new(".bss", 8, Vec::new()).alloc(0x3, 0x2000_0008).align(4);
+    bss.nobits = 0x20;
+    b.sections.push( bss );
+
+    b.sections.push( Section::new(".comment", 1, b"GCC: test\0".to_vec()) );
+
+    b.programs.push( Program { ptype: 1, flags: 0x5, sections: (0, 1), paddr: 0x0800_0000 } );
+    b.programs.push( Program { ptype: 1, flags: 0x6, sections: (2, 3), paddr: 0x0800_0014 } );
+
+    b.symbols.push( Sym::new("fw.c", 0, 0, 0x04, 0xFFF1) );
+    b.symbols.push( Sym::new("helper", 0x0800_0005, 4, 0x02, 1) );
+    b.symbols.push( Sym::new("main", 0x0800_0001, 4, 0x12, 1) );
+    b.symbols.push( Sym::new("banner", 0x0800_0008, 10, 0x11, 2) );
+    b.symbols.push( Sym::new("counter", 0x2000_0004, 4, 0x11, 3) );
+    b.symbols.push( Sym { name: "hidden", value: 0x2000_0000, size: 4, info: 0x21, other: 2, shndx: 3 } );
+    b.symbols.push( Sym::new("__bss_start", 0x2000_0008, 0, 0x10, 4) );
+
+    b
+}
@@ -0,0 +1,147 @@
+//! Compressed section tests.
+//! The compression headers of the `SHF_COMPRESSED` and `.zdebug_*` sections must be parsed
+//! and their contents decompressed to the size given by the header.
+
+mod common;
+
+
+
+use micro_elf::{
+    elf::{
+        ELFObject,
+        data::compression::CompressionType,
+    },
+    error::Error,
+};
+
+
+
+/// Uncompressed contents of the compressed sections.
+const PAYLOAD: &[u8] = b"micro-elf compressed section contents\nmicro-elf compressed section contents\nmicro-elf compressed section contents\n";
+
+/// The payload compressed as a zlib stream.
+const ZLIB: [u8; 48] = [
+    0x78, 0xDA, 0xCB, 0xCD, 0x4C, 0x2E, 0xCA, 0xD7, 0x4D, 0xCD, 0x49, 0x53, 0x48, 0xCE, 0xCF, 0x2D,
+    0x28, 0x4A, 0x2D, 0x2E, 0x4E, 0x4D, 0x51, 0x28, 0x4E, 0x4D, 0x2E, 0xC9, 0xCC, 0xCF, 0x03, 0x0A,
+    0xE5, 0x95, 0xA4, 0xE6, 0x95, 0x14, 0x73, 0xE5, 0x52, 0x51, 0x15, 0x00, 0xCD, 0x65, 0x2B, 0x81,
+];
+
+/// The payload compressed as a Zstandard frame.
+const ZSTD: [u8; 53] = [
+    0x28, 0xB5, 0x2F, 0xFD, 0x20, 0x72, 0x65, 0x01, 0x00, 0x72, 0xC2, 0x08, 0x0F, 0xC0, 0xB7, 0x01,
+    0xC2, 0x3B, 0x85, 0x05, 0x41, 0x89, 0x55, 0xB1, 0xD9, 0x4F, 0x01, 0x0B, 0x05, 0x30, 0xCD, 0x69,
+    0xEF, 0xD8, 0x29, 0x6B, 0x1C, 0x61, 0x44, 0x5F, 0xA9, 0x77, 0x06, 0x71, 0xF8, 0x95, 0x2A, 0x01,
+    0x00, 0xC7, 0x84, 0xAA, 0x27,
+];
+
+/// Encodes a compression header (`Elf_Chdr`) followed by the compressed data.
+fn chdr(builder: &common::Builder, kind: u32, size: u64, data: &[u8]) -> Vec<u8> {
+    let mut content = builder.word(kind);
+
+    if builder.class64 {
+        content.extend( builder.word(0) );
+    }
+
+    content.extend( builder.addr(size) );
+    content.extend( builder.addr(1) );
+    content.extend_from_slice( data );
+
+    content
+}
+
+/// Builds a relocatable object with compressed debug sections.
+fn compressed(class64: bool, big: bool) -> Vec<u8> {
+    let mut builder = common::Builder::new(class64, big, if class64 { 0x3E } else { 0x03 });
+    builder.filetype = 1;
+
+    // A legacy section has the magic and the big endian size before the stream.
+    let mut legacy = b"ZLIB".to_vec();
+    legacy.extend_from_slice( &(PAYLOAD.len() as u64).to_be_bytes() );
+    legacy.extend_from_slice( &ZLIB );
+
+    let sections = [
+        (".debug_info",   chdr(&builder, 1, PAYLOAD.len() as u64, &ZLIB), 0x800),
+        (".debug_str",    chdr(&builder, 2, PAYLOAD.len() as u64, &ZSTD), 0x830),
+        (".zdebug_line",  legacy, 0),
+        (".debug_abbrev", PAYLOAD.to_vec(), 0),
+        (".debug_frame",  chdr(&builder, 0x99, PAYLOAD.len() as u64, &ZLIB), 0x800),
+        (".debug_ranges", chdr(&builder, 1, PAYLOAD.len() as u64 - 1, &ZLIB), 0x800),
+        (".debug_loc",    builder.word(1), 0x800),
+    ];
+
+    for (name, data, flags) in sections.iter().cloned() {
+        let mut section = common::Section::new(name, 1, data);
+        section.flags = flags;
+        builder.sections.push( section );
+    }
+
+    builder.build()
+}
+
+#[test]
+fn compression_header() {
+    for &class64 in &[false, true] {
+        for &big in &[false, true] {
+            let elf = ELFObject::parse( compressed(class64, big) ).unwrap();
+
+            // Parse the headers of the compressed sections.
+            for (name, kind, headersize) in &[(".debug_info", CompressionType::Zlib, if class64 { 24 } else { 12 }), (".debug_str", CompressionType::Zstd, if class64 { 24 } else { 12 }), (".zdebug_line", CompressionType::Zlib, 12)] {
+                let header = elf.compression_header(*name).unwrap().unwrap();
+
+                assert_eq!(header.kind(), *kind);
+                assert_eq!(header.size(), PAYLOAD.len() as u64);
+                assert_eq!(header.headersize(), *headersize);
+            }
+
+            assert_eq!(elf.compression_header(".debug_frame").unwrap().unwrap().kind(), CompressionType::Other(0x99));
+
+            // Uncompressed sections are returned as they are.
+            assert_eq!(elf.compression_header(".debug_abbrev").unwrap(), None);
+            assert_eq!(&*elf.decompressed_content(".debug_abbrev").unwrap(), PAYLOAD);
+
+            // Unknown algorithms and truncated headers are errors.
+            assert_eq!(elf.decompressed_content(".debug_frame"), Err( Error::UnsupportedCompression(0x99) ));
+            assert!(matches!(elf.decompressed_content(".debug_loc"), Err( Error::OutOfBounds { .. } )));
+            assert_eq!(elf.decompressed_content(".debug_missing"), Err( Error::MissingSection ));
+            assert_eq!(elf.compression_header(".debug_missing"), Err( Error::MissingSection ));
+            assert_eq!(elf.compression_header(elf.sections().len()), Err( Error::MissingSection ));
+
+            // The decoders are only available with their features.
+            if cfg!(not(feature = "zlib")) {
+                assert_eq!(elf.decompressed_content(".debug_info"), Err( Error::UnsupportedCompression(1) ));
+            }
+
+            if cfg!(not(feature = "zstd")) {
+                assert_eq!(elf.decompressed_content(".debug_str"), Err( Error::UnsupportedCompression(2) ));
+            }
+        }
+    }
+}
+
+#[cfg(feature = "zlib")]
+#[test]
+fn zlib() {
+    for &class64 in &[false, true] {
+        for &big in &[false, true] {
+            let elf = ELFObject::parse( compressed(class64, big) ).unwrap();
+
+            assert_eq!(&*elf.decompressed_content(".debug_info").unwrap(), PAYLOAD);
+            assert_eq!(&*elf.decompressed_content(".zdebug_line").unwrap(), PAYLOAD);
+
+            // The data must decompress to the size of the header.
+            assert!(matches!(elf.decompressed_content(".debug_ranges"), Err( Error::BadCompressedData { .. } )));
+        }
+    }
+}
+
+#[cfg(feature = "zstd")]
+#[test]
+fn zstd() {
+    for &class64 in &[false, true] {
+        for &big in &[false, true] {
+            let elf = ELFObject::parse( compressed(class64, big) ).unwrap();
+
+            assert_eq!(&*elf.decompressed_content(".debug_str").unwrap(), PAYLOAD);
+        }
+    }
+}
@@ -0,0 +1,14 @@
+// Source of the test fixtures. Built with:
+//   gcc [-m32] -Os -fno-asynchronous-unwind-tables -c fw.c -o le{32,64}.o
+//   gcc [-m32] -Os -nostdlib -static -fno-asynchronous-unwind-tables -Wl,--build-id=none \
+//       -Wl,-z,max-page-size=16 -Wl,-z,noseparate-code -Wl,-z,norelro fw.c -o le{32,64}.elf
+// The golden files of the writers are the output of GNU objcopy 2.40:
+//   objcopy -O ihex le{32,64}.elf le{32,64}.hex
+//   objcopy -O srec le32.elf out.srec && mv out.srec le32.srec
+
+int counter = 3;
+static int table[4] = { 1, 2, 3, 4 };
+const char banner[] = "micro-elf";
+static int helper(int x) { return x * table[x & 3]; }
+int compute(int x) { return helper(x) + counter; }
+void _start(void) { counter = compute(counter); for (;;); }
@@ -0,0 +1,78 @@
+//! Demangling tests.
+//! Rust (legacy and v0) and C++ symbol names must be demangled, with or without the hash, and be
+//! usable by the name lookup and the symbolizer.
+
+#![cfg(feature = "demangle")]
+
+mod common;
+
+
+
+use micro_elf::elf::ELFObject;
+
+
+
+/// Legacy Rust name of `core::fmt::write`.
+const LEGACY: &str = "_ZN4core3fmt5write17h0123456789abcdefE";
+
+/// Rust v0 name of `app::handler`.
+const V0: &str = "_RNvCs1234_3app7handler";
+
+/// Itanium C++ name of `drv::Uart::send(char const*)`.
+const CPP: &str = "_ZN3drv4Uart4sendEPKc";
+
+
+
+/// Builds the firmware with mangled functions.
+fn mangled(class64: bool, big: bool) -> common::Builder {
+    let mut builder = common::firmware(class64, big);
+
+    builder.symbols.push( common::Sym::new(LEGACY, 0x0800_0010, 2, 0x12, 1) );
+    builder.symbols.push( common::Sym::new(V0, 0x0800_0012, 2, 0x12, 1) );
+    builder.symbols.push( common::Sym::new(CPP, 0x0800_0014, 2, 0x12, 1) );
+
+    builder
+}
+
+#[test]
+fn demangled() {
+    let elf = ELFObject::parse( mangled(false, false).build() ).unwrap();
+
+    let demangled = |name: &str, hash: bool| elf.symbol(name)[0].demangled(hash).into_owned();
+
+    assert_eq!(demangled(LEGACY, false), "core::fmt::write");
+    assert_eq!(demangled(LEGACY, true), "core::fmt::write::h0123456789abcdef");
+    assert_eq!(demangled(V0, false), "app::handler");
+    assert_eq!(demangled(V0, true), "app[3c1c0]::handler");
+    assert_eq!(demangled(CPP, false), "drv::Uart::send(char const*)");
+
+    // Plain names are not changed.
+    assert_eq!(demangled("main", false), "main");
+    assert!( !elf.symbol("main")[0].prettyprint().contains("Demangled") );
+    assert!( elf.symbol(LEGACY)[0].prettyprint().contains("  - Demangled: core::fmt::write\n") );
+}
+
+#[test]
+fn lookup_demangled() {
+    for &class64 in &[false, true] {
+        for &big in &[false, true] {
+            let elf = ELFObject::parse( mangled(class64, big).build() ).unwrap();
+
+            // Both the mangled and the demangled names are found.
+            for &(name, value) in &[("core::fmt::write", 0x0800_0010), (LEGACY, 0x0800_0010), ("app::handler", 0x0800_0012), ("drv::Uart::send(char const*)", 0x0800_0014), ("main", 0x0800_0001)] {
+                let values = elf.symbol_demangled(name).iter().map(|symbol| u64::from( symbol.value() )).collect::<Vec<_>>();
+                assert_eq!(values, [value]);
+            }
+
+            assert!( elf.symbol("core::fmt::write").is_empty() );
+            assert!( elf.symbol_demangled("core::fmt::write::h0123456789abcdef").is_empty() );
+            assert!( elf.symbol_in_file_demangled("fw.c", "app::handler").is_some() );
+
+            // The symbolizer describes addresses with the demangled names.
+            let symbolizer = elf.symbolizer();
+            assert_eq!(symbolizer.describe_demangled(0x0800_0011).as_deref(), Some("core::fmt::write+0x1"));
+            assert_eq!(symbolizer.describe_demangled(0x0800_0014).as_deref(), Some("drv::Uart::send(char const*)+0x0"));
+            assert_eq!(symbolizer.describe(0x0800_0012).as_deref(), Some("_RNvCs1234_3app7handler+0x0"));
+        }
+    }
+}
@@ -0,0 +1,346 @@
+//! Dynamic section tests.
+//! The entries and the dynamic symbols must be decoded through the segments when the section headers are stripped.
+
+mod common;
+
+
+
+use micro_elf::elf::{
+    ELFObject,
+    data::{ DynamicEntry, HashTable },
+};
+
+use std::sync::Arc;
+
+
+
+/// Virtual address of the dynamic section.
+const DYNAMIC: u64 = 0x1000;
+
+/// Builds a shared object with a dynamic section followed by its string table.
+fn shared(class64: bool, big: bool) -> common::Builder {
+    let mut builder = common::Builder::new(class64, big, 0x28);
+    builder.filetype = 3;
+
+    let strings = b"\0libc.so.6\0libm.so.6\0libfw.so\0$ORIGIN/lib\0".to_vec();
+
+    let entries = [
+        (1, 1), (1, 11), (14, 21), (29, 30), (5, 0), (10, strings.len() as u64),
+        (0x6FFF_FFFB, 0x0800_0001), (0x6FFF_FEF5, 0x1234), (25, 0x4000), (27, 16), (0x7000_0001, 7), (0, 0), (0, 0),
+    ];
+
+    // The string table follows the dynamic section in the file and in memory.
+    let inc = if class64 { 8 } else { 4 };
+    let strtab = DYNAMIC + (entries.len() * 2 * inc) as u64;
+
+    let mut data = Vec::new();
+
+    for (tag, value) in &entries {
+        let value = if *tag == 5 { strtab } else { *value };
+
+        data.extend( builder.addr(*tag) );
+        data.extend( builder.addr(value) );
+    }
+
+    builder.sections.push( common::Section::new(".dynamic", 6, data).alloc(0x3, DYNAMIC).align(8).link(2, 0).entsize(2 * inc as u64) );
+    builder.sections.push( common::Section::new(".dynstr", 3, strings).alloc(0x2, strtab) );
+
+    builder.programs.push( common::Program { ptype: 1, flags: 0x6, sections: (0, 1), paddr: DYNAMIC } );
+    builder.programs.push( common::Program { ptype: 2, flags: 0x6, sections: (0, 0), paddr: DYNAMIC } );
+
+    builder
+}
+
+/// Symbols exported by the shared objects, as name, value, size and information.
+const EXPORTS: [(&str, u64, u64, u8); 4] = [("fw_init", 0x2000, 0x40, 0x12), ("fw_read", 0x2040, 0x80, 0x12), ("fw_write", 0x20C0, 0x80, 0x12), ("fw_version", 0x3000, 4, 0x11)];
+
+/// Hash function of the SysV hash table.
+fn sysv(name: &str) -> u32 {
+    name.bytes().fold(0u32, |h, c| {
+        let h = (h << 4).wrapping_add( u32::from(c) );
+        (h ^ ((h & 0xF000_0000) >> 24)) & 0x0FFF_FFFF
+    })
+}
+
+/// Hash function of the GNU hash table.
+fn gnu(name: &str) -> u32 {
+    name.bytes().fold(5381u32, |h, c| h.wrapping_mul(33).wrapping_add( u32::from(c) ))
+}
+
+/// Builds a shared object exporting the test symbols through a SysV or a GNU hash table.
+fn exported(class64: bool, big: bool, gnuhash: bool) -> common::Builder {
+    let mut builder = common::Builder::new(class64, big, 0x28);
+    builder.filetype = 3;
+
+    let inc = if class64 { 8 } else { 4 };
+
+    // The GNU hash table requires the symbols sorted by bucket.
+    let nbuckets = 3;
+    let mut exports = EXPORTS.to_vec();
+
+    if gnuhash {
+        exports.sort_by_key(|(name, _, _, _)| gnu(name) % nbuckets);
+    }
+
+    // Build the string and symbol tables.
+    let mut strings = b"\0libfw.so\0".to_vec();
+    let mut symbols = vec![0u8; 8 + (2 * inc)];
+
+    for (name, value, size, info) in &exports {
+        let index = strings.len() as u32;
+        strings.extend_from_slice( name.as_bytes() );
+        strings.push(0);
+
+        // The symbols are absolute.
+        symbols.extend( builder.sym(index, &common::Sym::new(name, *value, *size, *info, 0xFFF1)) );
+    }
+
+    // Build the hash table.
+    let count = exports.len() as u32 + 1;
+    let mut hash = Vec::new();
+
+    if gnuhash {
+        let (shift, bits) = (6, 8 * inc as u32);
+        let mut bloom = 0u64;
+        let mut buckets = vec![0u32; nbuckets as usize];
+        let mut chains = Vec::new();
+
+        for (i, (name, _, _, _)) in exports.iter().enumerate() {
+            let h = gnu(name);
+            bloom |= (1 << (h % bits)) | (1 << ((h >> shift) % bits));
+
+            let bucket = (h % nbuckets) as usize;
+
+            if buckets[bucket] == 0 {
+                buckets[bucket] = i as u32 + 1;
+            }
+
+            let last = exports.get(i + 1).is_none_or(|next| (gnu(next.0) % nbuckets) as usize != bucket);
+            chains.push( (h & !1) | u32::from(last) );
+        }
+
+        for word in [nbuckets, 1, 1, shift] {
+            hash.extend( builder.word(word) );
+        }
+
+        hash.extend( builder.addr(bloom) );
+        buckets.iter().chain( chains.iter() ).for_each(|word| hash.extend( builder.word(*word) ));
+    } else {
+        let mut buckets = vec![0u32; nbuckets as usize];
+        let mut chains = vec![0u32; count as usize];
+
+        for (i, (name, _, _, _)) in exports.iter().enumerate() {
+            let bucket = (sysv(name) % nbuckets) as usize;
+            chains[i + 1] = buckets[bucket];
+            buckets[bucket] = i as u32 + 1;
+        }
+
+        for word in [nbuckets, count].iter().chain( buckets.iter() ).chain( chains.iter() ) {
+            hash.extend( builder.word(*word) );
+        }
+    }
+
+    // The tables follow the dynamic section in the file and in memory.
+    let hashtag = if gnuhash { 0x6FFF_FEF5 } else { 4 };
+    let strtab = DYNAMIC + (7 * 2 * inc) as u64;
+    let symtab = strtab + strings.len() as u64;
+    let hashtab = symtab + symbols.len() as u64;
+
+    let entries = [(14, 1), (hashtag, hashtab), (5, strtab), (6, symtab), (10, strings.len() as u64), (11, 8 + (2 * inc) as u64), (0, 0)];
+    let mut data = Vec::new();
+
+    for (tag, value) in &entries {
+        data.extend( builder.addr(*tag) );
+        data.extend( builder.addr(*value) );
+    }
+
+    let (hashname, hashtype) = if gnuhash { (".gnu.hash", 0x6FFF_FFF6) } else { (".hash", 5) };
+
+    builder.sections.push( common::Section::new(".dynamic", 6, data).alloc(0x3, DYNAMIC).align(8).link(2, 0).entsize(2 * inc as u64) );
+    builder.sections.push( common::Section::new(".dynstr", 3, strings).alloc(0x2, strtab) );
+    builder.sections.push( common::Section::new(".dynsym", 11, symbols).alloc(0x2, symtab).link(2, 1).entsize(8 + (2 * inc) as u64) );
+    builder.sections.push( common::Section::new(hashname, hashtype, hash).alloc(0x2, hashtab).link(3, 0) );
+
+    builder.programs.push( common::Program { ptype: 1, flags: 0x6, sections: (0, 3), paddr: DYNAMIC } );
+    builder.programs.push( common::Program { ptype: 2, flags: 0x6, sections: (0, 0), paddr: DYNAMIC } );
+
+    builder
+}
+
+#[test]
+fn dynamic_entries() {
+    for &class64 in &[false, true] {
+        for &big in &[false, true] {
+            let mut image = shared(class64, big).build();
+
+            let expected = {
+                let elf = ELFObject::parse( Arc::<[u8]>::from( image.clone() ) ).unwrap();
+                elf.dynamic().unwrap()
+            };
+
+            let inc = if class64 { 8 } else { 4 };
+
+            assert_eq!(expected, [
+                DynamicEntry::Needed( "libc.so.6".to_string() ),
+                DynamicEntry::Needed( "libm.so.6".to_string() ),
+                DynamicEntry::SoName( "libfw.so".to_string() ),
+                DynamicEntry::RunPath( "$ORIGIN/lib".to_string() ),
+                DynamicEntry::StrTab( DYNAMIC + (26 * inc) ),
+                DynamicEntry::StrSize(42),
+                DynamicEntry::Flags1(0x0800_0001),
+                DynamicEntry::GnuHash(0x1234),
+                DynamicEntry::InitArray(0x4000),
+                DynamicEntry::InitArraySize(16),
+                DynamicEntry::Other { tag: 0x7000_0001, value: 7 },
+            ]);
+
+            assert_eq!(expected[1].tag(), 1);
+            assert_eq!(format!("{}", expected[2]), "SONAME [libfw.so]");
+
+            // The same entries are found without the section headers.
+            common::strip(&mut image, class64);
+
+            let elf = ELFObject::parse( Arc::<[u8]>::from(image) ).unwrap();
+            assert!( elf.sections().is_empty() );
+            assert_eq!(elf.dynamic().unwrap(), expected);
+        }
+    }
+}
+
+#[test]
+fn no_dynamic() {
+    let elf = ELFObject::parse( Arc::<[u8]>::from( common::firmware(true, false).build() ) ).unwrap();
+    assert!( elf.dynamic().unwrap().is_empty() );
+}
+
+#[test]
+fn dynamic_symbols() {
+    for &gnuhash in &[false, true] {
+        for &class64 in &[false, true] {
+            for &big in &[false, true] {
+                let mut image = exported(class64, big, gnuhash).build();
+
+                let expected = {
+                    let elf = ELFObject::parse( Arc::<[u8]>::from( image.clone() ) ).unwrap();
+                    assert!( elf.symbols().is_empty() );
+
+                    elf.dynamic_symbols().iter().map(|s| (s.name().clone(), u64::from( s.value() ), u64::from( s.size() ), s.is_function())).collect::<Vec<_>>()
+                };
+
+                assert_eq!(expected.len(), EXPORTS.len() + 1);
+                assert_eq!(expected[0], (String::new(), 0, 0, false));
+
+                for (name, value, size, info) in &EXPORTS {
+                    assert!( expected.contains( &(name.to_string(), *value, *size, (info & 0xF) == 2) ) );
+                }
+
+                // The same symbols are found through the dynamic section, counted by the hash table.
+                common::strip(&mut image, class64);
+
+                let elf = ELFObject::parse( Arc::<[u8]>::from(image) ).unwrap();
+                let symbols = elf.dynamic_symbols().iter().map(|s| (s.name().clone(), u64::from( s.value() ), u64::from( s.size() ), s.is_function())).collect::<Vec<_>>();
+                assert_eq!(symbols, expected);
+            }
+        }
+    }
+}
+
+#[test]
+fn lookup_dynamic() {
+    for &gnuhash in &[false, true] {
+        for &class64 in &[false, true] {
+            for &big in &[false, true] {
+                let mut image = exported(class64, big, gnuhash).build();
+
+                for stripped in [false, true] {
+                    if stripped {
+                        common::strip(&mut image, class64);
+                    }
+
+                    let elf = ELFObject::parse( Arc::<[u8]>::from( image.clone() ) ).unwrap();
+
+                    // The table of the object is found and describes all the symbols.
+                    let table = elf.hash_table().unwrap();
+                    assert_eq!(matches!(table, HashTable::Gnu(_)), gnuhash);
+                    assert_eq!(table.count(), EXPORTS.len() + 1);
+
+                    for (name, value, _, _) in &EXPORTS {
+                        let symbol = elf.lookup_dynamic(name).unwrap();
+                        assert_eq!(symbol.name(), name);
+                        assert_eq!(u64::from( symbol.value() ), *value);
+                    }
+
+                    for name in &["fw_erase", "fw_init2", "fw_", ""] {
+                        assert!( elf.lookup_dynamic(name).is_none() );
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn hash_out_of_file() {
+    for &class64 in &[false, true] {
+        for &big in &[false, true] {
+            let builder = exported(class64, big, true);
+            let mut image = builder.build();
+            common::strip(&mut image, class64);
+
+            // The loadable segment claims 16 MiB of file data and the GNU hash table is past the end of the file.
+            let (ehsize, filesz) = if class64 { (0x40, 0x20) } else { (0x34, 0x10) };
+            let inc = if class64 { 8 } else { 4 };
+
+            let at = ehsize + filesz;
+            image[at..at+inc].copy_from_slice( &builder.addr(0x100_0000) );
+
+            let tag = builder.addr(0x6FFF_FEF5);
+            let at = image.windows(inc).position(|window| window == &tag[..]).unwrap() + inc;
+            image[at..at+inc].copy_from_slice( &builder.addr(DYNAMIC + 0x80_0000) );
+
+            // The object still parses, without the hash table nor the symbols it counts.
+            let elf = ELFObject::parse( Arc::<[u8]>::from(image) ).unwrap();
+
+            assert!( elf.hash_table().is_none() );
+            assert!( elf.dynamic_symbols().is_empty() );
+            assert_eq!(elf.dynamic().unwrap()[1], DynamicEntry::GnuHash(DYNAMIC + 0x80_0000));
+        }
+    }
+}
+
+#[test]
+fn malformed_symbols() {
+    for &class64 in &[false, true] {
+        for &big in &[false, true] {
+            let mut builder = exported(class64, big, false);
+
+            // The dynamic symbol table is not linked to a string table.
+            builder.sections[2].link = 0;
+
+            // The headers, the sections and the hash table are still available, without the dynamic symbols.
+            let elf = ELFObject::parse( Arc::<[u8]>::from( builder.build() ) ).unwrap();
+
+            assert_eq!(elf.sections().len(), 6);
+            assert_eq!(elf.programs().len(), 2);
+            assert!( elf.dynamic_symbols().is_empty() );
+            assert!( elf.hash_table().is_some() );
+
+            // Without the sections, `DT_SYMTAB` points past the end of the file.
+            builder.sections[2].link = 2;
+
+            let mut image = builder.build();
+            common::strip(&mut image, class64);
+
+            // The dynamic section follows the two program headers and `DT_SYMTAB` is its fourth entry.
+            let (ehsize, inc): (usize, usize) = if class64 { (0x40, 8) } else { (0x34, 4) };
+            let at = (ehsize + (2 * (8 + (6 * inc)))).next_multiple_of(8) + (3 * 2 * inc) + inc;
+            image[at..at+inc].copy_from_slice( &builder.addr(DYNAMIC + 0x80_0000) );
+
+            let elf = ELFObject::parse( Arc::<[u8]>::from(image) ).unwrap();
+
+            assert!( elf.dynamic_symbols().is_empty() );
+            assert!( elf.hash_table().is_some() );
+            assert_eq!(elf.dynamic().unwrap()[3], DynamicEntry::SymTab(DYNAMIC + 0x80_0000));
+        }
+    }
+}
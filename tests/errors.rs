@@ -0,0 +1,92 @@
+//! Parse error tests.
+//! Malformed files must fail with the exact error, which must describe where the file is malformed.
+
+mod common;
+
+
+
+use micro_elf::{
+    elf::ELFObject,
+    error::{ Error, Table },
+};
+
+use std::sync::Arc;
+
+
+
+/// Offset of the Section Header Table of the 32 bit firmware image.
+const SHOFF: usize = 0x190;
+
+/// Offset of the section name table of the 32 bit firmware image.
+const SHSTRTAB: usize = 0x150;
+
+/// Parses the image, which must fail, and returns the error.
+fn error(image: Vec<u8>) -> Error {
+    ELFObject::parse( Arc::<[u8]>::from(image) ).unwrap_err()
+}
+
+/// Builds the 32 bit little endian firmware image, checking the offsets of its tables.
+fn image() -> Vec<u8> {
+    let image = common::firmware(false, false).build();
+    let read32 = |at: usize| u32::from_le_bytes( [image[at], image[at + 1], image[at + 2], image[at + 3]] ) as usize;
+
+    assert_eq!(read32(0x20), SHOFF);
+
+    let shstrndx = u16::from_le_bytes( [image[0x32], image[0x33]] ) as usize;
+    assert_eq!(read32(SHOFF + (shstrndx * 40) + 0x10), SHSTRTAB);
+
+    image
+}
+
+#[test]
+fn bad_magic() {
+    let mut image = image();
+    image[0x01] = b'e';
+
+    let err = error(image);
+    assert_eq!(err, Error::BadMagic);
+    assert_eq!(err.to_string(), "Bad ELF magic number");
+}
+
+#[test]
+fn unsupported_class() {
+    let mut image = image();
+    image[0x04] = 3;
+
+    let err = error(image);
+    assert_eq!(err, Error::UnsupportedClass(3));
+    assert_eq!(err.to_string(), "Unsupported ELF class 3");
+}
+
+#[test]
+fn bad_header_size() {
+    // The file header of a 32 bit file claims the size of a 64 bit one.
+    let mut image = image();
+    image[0x28..0x2A].copy_from_slice( &64u16.to_le_bytes() );
+
+    let err = error(image);
+    assert_eq!(err, Error::BadHeaderSize { table: Table::FileHeader, expected: 52, found: 64 });
+    assert_eq!(err.to_string(), "Bad File header entry size: expected 52 bytes, found 64 bytes");
+}
+
+#[test]
+fn truncated_sections() {
+    // The file ends in the middle of the third section header.
+    let mut image = image();
+    image.truncate( SHOFF + (2 * 40) + 10 );
+
+    let err = error(image);
+    assert_eq!(err, Error::OutOfBounds { table: Table::SectionHeaders, index: 2, offset: SHOFF + (2 * 40) });
+    assert_eq!(err.to_string(), "Section Header Table entry 2 at offset 0x1E0 is out of bounds");
+}
+
+#[test]
+fn bad_name_index() {
+    // The name of the first section after the null section is past the end of the section name table.
+    let mut image = image();
+    image[SHOFF + 40..SHOFF + 44].copy_from_slice( &0x1000u32.to_le_bytes() );
+
+    let err = error(image);
+    assert_eq!(err, Error::BadNameIndex { table: Table::SectionHeaders, index: 1, offset: SHSTRTAB + 0x1000 });
+    assert_eq!(err.to_string(), "Section Header Table entry 1 has a name out of bounds at offset 0x1150");
+}
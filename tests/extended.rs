@@ -0,0 +1,146 @@
+//! Extended numbering tests.
+//! Objects with more than 0xFF00 sections or 0xFFFF segments must store the counts and indices
+//! through the first section and the extended section indices, and be written back the same way.
+
+mod common;
+
+
+
+use common::{ Builder, Program, Section, Sym };
+
+use micro_elf::elf::ELFObject;
+
+use std::sync::Arc;
+
+
+
+/// Number of empty sections between the first and the last sections of the image.
+const FILLER: usize = 0xFF10;
+
+/// Number of segments of the image (`PN_XNUM`).
+const SEGMENTS: usize = 0xFFFF;
+
+/// Index of the last builder section, past the reserved section indices.
+const FAR: u32 = FILLER as u32 + 2;
+
+/// Number of sections of the image, with the null section and the symbol and string tables.
+const SECTIONS: u32 = FAR + 5;
+
+/// Builds a 32 bit image with too many sections and segments for the file header and a symbol in
+/// the last section, with the counts and indices escaped as the ELF rules require.
+/// Without the extended section indices, the symbol is in the first section instead.
+fn extended(shndx: bool) -> Vec<u8> {
+    let mut b = Builder::new(false, false, 0x28);
+
+    b.sections.push( Section::new(".text", 1, vec![0x70, 0x47, 0x00, 0xBF]).alloc(0x6, 0x1000).align(4) );
+
+    for _ in 0..FILLER {
+        b.sections.push( Section::new(".filler", 1, Vec::new()) );
+    }
+
+    b.sections.push( Section::new(".far", 1, b"far\0".to_vec()) );
+
+    b.symbols.push( Sym::new("near", 0x1000, 4, 0x12, 1) );
+
+    if shndx {
+        // The extended section indices of the null, near and far symbols.
+        let mut indices = b.word(0);
+        indices.extend( b.word(0) );
+        indices.extend( b.word(FAR) );
+
+        // The symbol table comes after this section.
+        let symtab = b.symtab() + 1;
+        b.sections.push( Section::new(".symtab_shndx", 18, indices).link(symtab, 0).align(4).entsize(4) );
+
+        b.symbols.push( Sym::new("far", 0, 4, 0x11, 0xFFFF) );
+    } else {
+        b.symbols.push( Sym::new("far", 0, 4, 0x11, 1) );
+    }
+
+    b.programs.push( Program { ptype: 1, flags: 0x5, sections: (0, 0), paddr: 0x1000 } );
+
+    for _ in 1..SEGMENTS {
+        b.programs.push( Program { ptype: 0, flags: 0, sections: (0, 0), paddr: 0 } );
+    }
+
+    let mut image = b.build();
+
+    // Escape the counts and the section name table index in the file header.
+    let shoff = u32::from_le_bytes( [image[0x20], image[0x21], image[0x22], image[0x23]] ) as usize;
+
+    image[0x2C..0x2E].copy_from_slice( &0xFFFFu16.to_le_bytes() );
+    image[0x30..0x32].copy_from_slice( &0u16.to_le_bytes() );
+    image[0x32..0x34].copy_from_slice( &0xFFFFu16.to_le_bytes() );
+
+    // Store the real values in the first section.
+    let sections = match shndx {
+        true => SECTIONS,
+        _ => SECTIONS - 1,
+    };
+
+    image[shoff + 0x14..shoff + 0x18].copy_from_slice( &sections.to_le_bytes() );
+    image[shoff + 0x18..shoff + 0x1C].copy_from_slice( &(sections - 1).to_le_bytes() );
+    image[shoff + 0x1C..shoff + 0x20].copy_from_slice( &(SEGMENTS as u32).to_le_bytes() );
+
+    image
+}
+
+/// Checks the counts, names and symbol section indices of the object.
+fn check(elf: &ELFObject<Arc<[u8]>>) {
+    assert_eq!(elf.sections().len(), SECTIONS as usize);
+    assert_eq!(elf.programs().len(), SEGMENTS);
+
+    // The section names are found through the escaped section name table index.
+    assert_eq!(elf.sections()[1].name(), ".text");
+    assert_eq!(elf.sections()[FAR as usize].name(), ".far");
+    assert_eq!(elf.sections()[SECTIONS as usize - 1].name(), ".shstrtab");
+
+    // The overflowed section index is resolved through the extended section indices.
+    let names = elf.symbols().iter().map(|symbol| (symbol.name().clone(), symbol.shidx())).collect::<Vec<_>>();
+    assert_eq!(names, [(String::new(), 0), (String::from("near"), 1), (String::from("far"), FAR)]);
+}
+
+#[test]
+fn parse() {
+    let image = extended(true);
+    let elf = ELFObject::parse( Arc::<[u8]>::from( &image[..] ) ).unwrap();
+
+    check(&elf);
+
+    // The unmodified object is written back with the same escapes.
+    assert!(elf.to_bytes() == image, "The written image differs from the original");
+}
+
+#[test]
+fn layout() {
+    let mut elf = ELFObject::parse( Arc::<[u8]>::from( extended(true) ) ).unwrap();
+
+    // Laying the file out again regenerates the first section and the extended section indices.
+    assert!(elf.rename_section(".far", ".distant"));
+    elf.section_mut(".distant").unwrap().set_name(".far");
+
+    let written = elf.to_bytes();
+    assert_eq!(&written[0x2C..0x34], &[0xFF, 0xFF, 0x28, 0x00, 0x00, 0x00, 0xFF, 0xFF]);
+
+    check( &ELFObject::parse( Arc::<[u8]>::from(written) ).unwrap() );
+}
+
+#[test]
+fn create_indices() {
+    let mut elf = ELFObject::parse( Arc::<[u8]>::from( extended(false) ) ).unwrap();
+    assert!(elf.section(".symtab_shndx").is_none());
+
+    // Moving a symbol past the reserved section indices creates the extended section indices.
+    elf.symbol_mut(2).unwrap().set_shidx(FAR);
+
+    let written = ELFObject::parse( Arc::<[u8]>::from( elf.to_bytes() ) ).unwrap();
+    assert_eq!(written.sections().len(), SECTIONS as usize);
+
+    let shndx = written.section(".symtab_shndx").unwrap();
+    let symtab = written.sections().iter().position(|section| section.name() == ".symtab").unwrap();
+    assert_eq!(shndx.link() as usize, symtab);
+    assert_eq!(usize::from( shndx.filesize() ), 3 * 4);
+
+    let names = written.symbols().iter().map(|symbol| (symbol.name().clone(), symbol.shidx())).collect::<Vec<_>>();
+    assert_eq!(names, [(String::new(), 0), (String::from("near"), 1), (String::from("far"), FAR)]);
+}
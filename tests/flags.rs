@@ -0,0 +1,94 @@
+//! Architecture flag tests.
+//! The flags of the file header must be decoded according to the architecture of the object.
+
+mod common;
+
+
+
+use micro_elf::elf::{
+    ELFObject,
+    data::header::{ ArchFlags, ArmFloat, AvrFamily, MipsAbi, MipsArch, RiscVFloat },
+};
+
+
+
+/// Parses an empty object of the given architecture with the given flags.
+fn flags(class64: bool, machine: u16, flags: u32) -> ArchFlags {
+    let mut builder = common::Builder::new(class64, false, machine);
+    builder.flags = flags;
+
+    ELFObject::parse( builder.build() ).unwrap().archflags()
+}
+
+#[test]
+fn arm() {
+    // Cortex-M objects with the hard and soft float ABIs.
+    let hard = match flags(false, 0x28, 0x0500_0400) {
+        ArchFlags::Arm(arm) => arm,
+        other => panic!("Not ARM flags: {:?}", other),
+    };
+
+    assert_eq!(hard.eabi(), 5);
+    assert_eq!(hard.float(), ArmFloat::Hard);
+    assert!(!hard.be8() && !hard.pic());
+    assert_eq!(hard.to_string(), "Version5 EABI, hard-float ABI");
+
+    let soft = match flags(false, 0x28, 0x0580_0200) {
+        ArchFlags::Arm(arm) => arm,
+        other => panic!("Not ARM flags: {:?}", other),
+    };
+
+    assert_eq!(soft.float(), ArmFloat::Soft);
+    assert!(soft.be8());
+    assert_eq!(soft.to_string(), "Version5 EABI, soft-float ABI, BE8");
+
+    // Objects that do not state the float ABI.
+    match flags(false, 0x28, 0x0000_0020) {
+        ArchFlags::Arm(arm) => {
+            assert_eq!((arm.eabi(), arm.float(), arm.pic()), (0, ArmFloat::Unspecified, true));
+        },
+        other => panic!("Not ARM flags: {:?}", other),
+    }
+}
+
+#[test]
+fn riscv() {
+    for &(raw, rvc, float, rve, tso) in &[(0x1, true, RiscVFloat::Soft, false, false), (0x3, true, RiscVFloat::Single, false, false), (0x5, true, RiscVFloat::Double, false, true), (0x6, false, RiscVFloat::Quad, false, false), (0x8, false, RiscVFloat::Soft, true, false)] {
+        let raw = raw | if tso { 0x10 } else { 0 };
+
+        match flags(true, 0xF3, raw) {
+            ArchFlags::RiscV(riscv) => {
+                assert_eq!((riscv.rvc(), riscv.float(), riscv.rve(), riscv.tso()), (rvc, float, rve, tso));
+            },
+            other => panic!("Not RISC-V flags: {:?}", other),
+        }
+    }
+
+    assert_eq!(flags(true, 0xF3, 0x15).to_string(), "RVC, double-float ABI, TSO");
+}
+
+#[test]
+fn mips() {
+    for &(class64, raw, arch, abi) in &[(false, 0x7000_1004, MipsArch::Mips32R2, MipsAbi::O32), (true, 0xA000_0404, MipsArch::Mips64R6, MipsAbi::Unspecified), (true, 0x6000_0024, MipsArch::Mips64, MipsAbi::N32), (false, 0x1000_3000, MipsArch::Mips2, MipsAbi::EABI32)] {
+        match flags(class64, 0x08, raw) {
+            ArchFlags::Mips(mips) => assert_eq!((mips.arch(), mips.abi()), (arch, abi)),
+            other => panic!("Not MIPS flags: {:?}", other),
+        }
+    }
+
+    assert_eq!(flags(false, 0x08, 0x7000_1004).to_string(), "mips32r2, o32");
+}
+
+#[test]
+fn avr() {
+    for &(raw, family) in &[(0x85, AvrFamily::Avr5), (0x6B, AvrFamily::XMega7), (0x64, AvrFamily::AvrTiny), (0x19, AvrFamily::Avr25), (0x7F, AvrFamily::Other(0x7F))] {
+        assert_eq!(flags(false, 0x53, raw), ArchFlags::Avr(family));
+    }
+
+    assert_eq!(flags(false, 0x53, 0x85).to_string(), "avr5");
+}
+
+#[test]
+fn other() {
+    assert_eq!(flags(true, 0x3E, 0x1234), ArchFlags::Other(0x1234));
+}
@@ -0,0 +1,135 @@
+//! Intel HEX tests.
+//! The records must match the output of `objcopy -O ihex`, with the extended address records
+//! and the record length given in the options, and read back into the same memory image.
+
+mod common;
+
+
+
+use micro_elf::{
+    elf::ELFObject,
+    error::{ Error, RecordError },
+    image::{ HexAddressing, HexOptions, MemoryImage },
+};
+
+
+
+/// Joins the records with the line endings of the writer.
+fn lines(records: &[&str]) -> String {
+    records.iter().map(|record| format!("{}\r\n", record)).collect()
+}
+
+#[test]
+fn elf() {
+    // The data is written at its load address and the entry point is the start address.
+    let elf = ELFObject::parse( common::firmware(false, false).build() ).unwrap();
+
+    let expected = lines(&[
+        ":020000040800F2",
+        ":08000000704700BF01207047AA",
+        ":0A0008006D6963726F2D656C660070",
+        ":08001400EFBEADDE03000000A9",
+        ":0400000508000001EE",
+        ":00000001FF",
+    ]);
+
+    assert_eq!(elf.to_ihex( &HexOptions::default() ).unwrap(), expected);
+
+    // Extended segment records cannot reach the flash.
+    let options = HexOptions { addressing: HexAddressing::Segment, ..HexOptions::default() };
+    assert_eq!(elf.to_ihex(&options), Err( Error::Overflow(0x0800_0000) ));
+}
+
+#[test]
+fn objcopy() {
+    // The golden files are the output of `objcopy -O ihex` for the test objects.
+    for name in &["le32", "le64"] {
+        let dir = std::path::Path::new( env!("CARGO_MANIFEST_DIR") ).join("tests/data");
+
+        let elf = ELFObject::parse( std::fs::read( dir.join(format!("{}.elf", name)) ).unwrap() ).unwrap();
+        let expected = std::fs::read_to_string( dir.join(format!("{}.hex", name)) ).unwrap();
+
+        assert_eq!(elf.to_ihex( &HexOptions::default() ).unwrap(), expected, "{}.hex", name);
+    }
+}
+
+#[test]
+fn addressing() {
+    // Data that crosses a 64 KiB boundary below 1 MiB.
+    let mut image = MemoryImage::new();
+    image.insert(0xFFF8, &(0..16).collect::<Vec<u8>>()).unwrap();
+
+    let auto = HexOptions { start: Some(0x1_2345), ..HexOptions::default() };
+
+    assert_eq!(image.to_ihex(&auto).unwrap(), lines(&[
+        ":08FFF8000001020304050607E5",
+        ":020000021000EC",
+        ":0800000008090A0B0C0D0E0F9C",
+        ":040000031000234581",
+        ":00000001FF",
+    ]));
+
+    let linear = HexOptions { addressing: HexAddressing::Linear, ..auto };
+
+    assert_eq!(image.to_ihex(&linear).unwrap(), lines(&[
+        ":08FFF8000001020304050607E5",
+        ":020000040001F9",
+        ":0800000008090A0B0C0D0E0F9C",
+        ":04000005000123458E",
+        ":00000001FF",
+    ]));
+
+    // Shorter records, without start address.
+    let short = HexOptions { record: 4, ..HexOptions::default() };
+    let output = image.to_ihex(&short).unwrap();
+
+    assert!( output.starts_with( &lines(&[":04FFF80000010203FF", ":04FFFC0004050607EB", ":020000021000EC"]) ) );
+    assert_eq!(output.lines().count(), 6);
+
+    // Addresses past 32 bits cannot be written.
+    let mut image = MemoryImage::new();
+    image.insert(0x1_0000_0000, &[0]).unwrap();
+    assert_eq!(image.to_ihex( &HexOptions::default() ), Err( Error::Overflow(0x1_0000_0000) ));
+}
+
+#[test]
+fn read() {
+    // The firmware written by objcopy reads back into the image of the object.
+    let elf = ELFObject::parse( common::firmware(false, false).build() ).unwrap();
+    let hex = elf.to_ihex( &HexOptions::default() ).unwrap();
+
+    assert_eq!(MemoryImage::from_ihex(&hex).unwrap(), elf.memory_image().unwrap());
+
+    // Extended segment addresses, with data that wraps around the end of the segment.
+    let image = MemoryImage::from_ihex( &lines(&[
+        ":0401000001020304F1",
+        ":020000021000EC",
+        ":04FFFE0005060708E5",
+        ":0400000312340010A3",
+        ":00000001FF",
+    ]) ).unwrap();
+
+    let regions = image.regions().iter().map(|region| (region.address(), region.data().to_vec())).collect::<Vec<_>>();
+    assert_eq!(regions, [(0x100, vec![1, 2, 3, 4]), (0x1_0000, vec![7, 8]), (0x1_FFFE, vec![5, 6])]);
+    assert_eq!(image.start(), Some(0x1_2350));
+}
+
+#[test]
+fn read_errors() {
+    let error = |text: &str| match MemoryImage::from_ihex(text) {
+        Err( Error::BadRecord { line, column, kind } ) => (line, column, kind),
+        other => panic!("Unexpected result: {:?}", other),
+    };
+
+    assert_eq!(error("0401000001020304F1\n"), (1, 1, RecordError::Start));
+    assert_eq!(error(":04010000010G0304F1\n"), (1, 13, RecordError::Digit));
+    assert_eq!(error(":0501000001020304F1\n"), (1, 2, RecordError::Length));
+    assert_eq!(error(":0401000001020304F\n"), (1, 19, RecordError::Length));
+    assert_eq!(error(":0401000001020304F2\n"), (1, 18, RecordError::Checksum));
+    assert_eq!(error(":00000006FA\n"), (1, 8, RecordError::Type));
+
+    // Blank lines are skipped, but counted.
+    assert_eq!(error("\r\n:00000001FF\r\n\r\n:0401000001020304F1\r\n"), (4, 1, RecordError::Order));
+    assert_eq!(error(":0401000001020304F1\n:0401000001020304F1\n:00000001FF\n"), (2, 10, RecordError::Overlap));
+    assert_eq!(error(":0401000001020304F1\n\n"), (3, 1, RecordError::MissingEnd));
+}
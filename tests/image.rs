@@ -0,0 +1,151 @@
+//! Memory image tests.
+//! The loadable segments must be placed at their load addresses without their zero filled part,
+//! and the regions of the image must be merged, filled and aligned as requested.
+
+mod common;
+
+
+
+use micro_elf::{
+    elf::ELFObject,
+    error::Error,
+    image::MemoryImage,
+};
+
+
+
+/// Returns the address and contents of the regions of an image.
+fn regions(image: &MemoryImage) -> Vec<(u64, Vec<u8>)> {
+    image.regions().iter().map(|region| (region.address(), region.data().to_vec())).collect()
+}
+
+#[test]
+fn segments() {
+    for &class64 in &[false, true] {
+        for &big in &[false, true] {
+            let builder = common::firmware(class64, big);
+
+            let mut text = vec![0x70, 0x47, 0x00, 0xBF, 0x01, 0x20, 0x70, 0x47];
+            text.extend_from_slice( b"micro-elf\0" );
+
+            let mut data = builder.word(0xDEAD_BEEF);
+            data.extend( builder.word(3) );
+
+            // The initialized data is placed at its load address in flash, without the bss.
+            let image = ELFObject::parse( builder.build() ).unwrap().memory_image().unwrap();
+            assert_eq!(regions(&image), [(0x0800_0000, text.clone()), (0x0800_0014, data.clone())]);
+            assert_eq!(image.size(), 0x1A);
+
+            // Adjacent segments are merged.
+            let mut builder = common::firmware(class64, big);
+            builder.programs[1].paddr = 0x0800_0012;
+
+            let image = ELFObject::parse( builder.build() ).unwrap().memory_image().unwrap();
+            assert_eq!(image.regions().len(), 1);
+            assert_eq!(image.read(0x0800_0010, 4), Some( &[b'f', 0, data[0], data[1]][..] ));
+
+            // Overlapping segments are an error.
+            let mut builder = common::firmware(class64, big);
+            builder.programs[1].paddr = 0x0800_0010;
+
+            assert_eq!(ELFObject::parse( builder.build() ).unwrap().memory_image(), Err( Error::Overlap(0x0800_0010) ));
+
+            // Without physical addresses, the segments are placed at their virtual addresses.
+            let mut builder = common::firmware(class64, big);
+            builder.programs.iter_mut().for_each(|program| program.paddr = 0);
+
+            let image = ELFObject::parse( builder.build() ).unwrap().memory_image().unwrap();
+            assert_eq!(regions(&image), [(0x0800_0000, text.clone()), (0x2000_0000, data.clone())]);
+        }
+    }
+}
+
+#[test]
+fn insert() {
+    let mut image = MemoryImage::new();
+    assert!( image.is_empty() );
+
+    image.insert(0x100, &[1, 2]).unwrap();
+    image.insert(0x104, &[5]).unwrap();
+    image.insert(0xFE, &[0xFF]).unwrap();
+    assert_eq!(regions(&image), [(0xFE, vec![0xFF]), (0x100, vec![1, 2]), (0x104, vec![5])]);
+
+    // Data that fills a gap merges both neighbours.
+    image.insert(0x102, &[3, 4]).unwrap();
+    image.insert(0xFF, &[0]).unwrap();
+    assert_eq!(regions(&image), [(0xFE, vec![0xFF, 0, 1, 2, 3, 4, 5])]);
+
+    assert_eq!(image.insert(0x104, &[0]), Err( Error::Overlap(0x104) ));
+    assert_eq!(image.insert(0xF0, &[0; 0x10]), Err( Error::Overlap(0xFE) ));
+    assert_eq!(image.insert(u64::MAX, &[0; 2]), Err( Error::Overflow(u64::MAX) ));
+
+    assert_eq!(image.read(0x100, 5), Some( &[1, 2, 3, 4, 5][..] ));
+    assert_eq!(image.read(0x100, 6), None);
+    assert_eq!(image.read(0xF0, 1), None);
+}
+
+#[test]
+fn fill_and_align() {
+    let mut image = MemoryImage::new();
+    image.insert(0x1002, &[1, 2]).unwrap();
+    image.insert(0x1008, &[3]).unwrap();
+    image.insert(0x1020, &[4]).unwrap();
+
+    // Only the gaps up to the given size are filled.
+    let mut filled = image.clone();
+    filled.fill(8, 0xFF);
+    assert_eq!(regions(&filled), [(0x1002, vec![1, 2, 0xFF, 0xFF, 0xFF, 0xFF, 3]), (0x1020, vec![4])]);
+
+    filled.fill(u64::MAX, 0xFF);
+    assert_eq!(filled.regions().len(), 1);
+    assert_eq!(filled.size(), 0x1F);
+
+    // Regions are extended to whole pages, and merged when they share a page.
+    let mut aligned = image.clone();
+    aligned.align(0x10, 0xFF);
+
+    let mut first = vec![0xFF; 0x10];
+    first[2..4].copy_from_slice( &[1, 2] );
+    first[8] = 3;
+
+    let mut second = vec![0xFF; 0x10];
+    second[0] = 4;
+
+    assert_eq!(regions(&aligned), [(0x1000, first), (0x1020, second)]);
+
+    aligned.align(0x40, 0);
+    assert_eq!(regions(&aligned).len(), 1);
+    assert_eq!(aligned.read(0x1000, 1), Some( &[0xFF][..] ));
+    assert_eq!(aligned.read(0x1010, 0x10), Some( &[0; 0x10][..] ));
+    assert_eq!(aligned.size(), 0x40);
+
+    // Pages of one byte do not change the image.
+    let mut unchanged = image.clone();
+    unchanged.align(1, 0xFF);
+    assert_eq!(unchanged, image);
+}
+
+#[test]
+fn overlay() {
+    let mut image = MemoryImage::new();
+    image.insert(0x100, &[1, 2, 3, 4]).unwrap();
+    image.insert(0x108, &[5, 6]).unwrap();
+    image.insert(0x110, &[7]).unwrap();
+
+    // Written data replaces the contents it overlaps and merges with the regions it touches.
+    image.write(0x102, &[0xA, 0xB, 0xC, 0xD, 0xE, 0xF, 0xA]).unwrap();
+    assert_eq!(regions(&image), [(0x100, vec![1, 2, 0xA, 0xB, 0xC, 0xD, 0xE, 0xF, 0xA, 6]), (0x110, vec![7])]);
+
+    image.write(0x111, &[8]).unwrap();
+    image.write(0xF0, &[0]).unwrap();
+    assert_eq!(regions(&image), [(0xF0, vec![0]), (0x100, vec![1, 2, 0xA, 0xB, 0xC, 0xD, 0xE, 0xF, 0xA, 6]), (0x110, vec![7, 8])]);
+
+    // Overlaid images replace the contents and the start address.
+    let mut bootloader = MemoryImage::new();
+    bootloader.insert(0xF0, &[0xFF; 0x22]).unwrap();
+    bootloader.set_start( Some(0xF0) );
+
+    image.overlay(&bootloader).unwrap();
+    assert_eq!(regions(&image), [(0xF0, vec![0xFF; 0x22])]);
+    assert_eq!(image.start(), Some(0xF0));
+}
@@ -0,0 +1,211 @@
+//! Static relocation tests.
+//! Relocatable objects are linked to a fixed address and checked against known encodings.
+
+mod common;
+
+
+
+use micro_elf::{
+    Error,
+    elf::{
+        ELFObject,
+        data::relocation::{ ARM, RISCV, RelocationType },
+    },
+};
+
+use std::{
+    collections::HashMap,
+    sync::Arc,
+};
+
+
+
+/// Builds the symbol map of the tests.
+fn map(symbols: &[(&str, u64)]) -> HashMap<String, u64> {
+    symbols.iter().map(|(name, value)| (name.to_string(), *value)).collect()
+}
+
+/// Reads the 16 bit value at the given offset of the image.
+fn half(image: &[u8], offset: usize, big: bool) -> u16 {
+    let bytes = [image[offset], image[offset + 1]];
+
+    match big {
+        true => u16::from_be_bytes(bytes),
+        _ => u16::from_le_bytes(bytes),
+    }
+}
+
+/// Reads the 32 bit value at the given offset of the image.
+fn word(image: &[u8], offset: usize, big: bool) -> u32 {
+    let bytes = [image[offset], image[offset + 1], image[offset + 2], image[offset + 3]];
+
+    match big {
+        true => u32::from_be_bytes(bytes),
+        _ => u32::from_le_bytes(bytes),
+    }
+}
+
+#[test]
+fn link_thumb() {
+    for &big in &[false, true] {
+        let mut builder = common::Builder::new(false, big, 0x28);
+        builder.filetype = 1;
+
+        // Calls, an address built by `movw` and `movt` and data references.
+        let mut text = Vec::new();
+
+        for half in &[0xF7FF, 0xFFFE, 0xF240, 0x0000, 0xF2C0, 0x0000, 0xF7FF, 0xFFFE] {
+            text.extend( builder.half(*half) );
+        }
+
+        for word in &[2, 0, 0, 0] {
+            text.extend( builder.word(*word) );
+        }
+
+        builder.sections.push( common::Section::new(".text", 1, text).alloc(0x6, 0).align(4) );
+        builder.sections.push( common::Section::new(".data", 1, b"data".to_vec()).alloc(0x3, 0).align(8) );
+
+        let mut bss = common::Section::new(".bss", 8, Vec::new()).alloc(0x3, 0).align(4);
+        bss.nobits = 0x10;
+        builder.sections.push( bss );
+
+        builder.symbols.push( common::Sym::new("banner", 0, 4, 0x01, 2) );
+        builder.symbols.push( common::Sym::new("main", 1, 16, 0x12, 1) );
+        builder.symbols.push( common::Sym::new("counter", 4, 4, 0x11, 3) );
+        builder.symbols.push( common::Sym::new("ext", 0, 0, 0x10, 0) );
+        builder.symbols.push( common::Sym::new("missing", 0, 0, 0x10, 0) );
+        builder.symbols.push( common::Sym::new("optional", 0, 0, 0x20, 0) );
+        builder.symbols.push( common::Sym::new("far", 0, 0, 0x10, 0) );
+
+        let symtab = builder.symtab() + 1;
+        let mut data = Vec::new();
+
+        for (offset, symbol, kind) in &[(0x00, 4, 10), (0x04, 3, 47), (0x08, 3, 48), (0x0C, 7, 10), (0x10, 1, 2), (0x14, 4, 3), (0x18, 6, 2), (0x1C, 5, 2)] {
+            data.extend( builder.reloc(*offset, *symbol, *kind, None) );
+        }
+
+        builder.sections.push( common::Section::new(".rel.text", 9, data).link(symtab, 1).entsize(8) );
+
+        let elf = ELFObject::parse( Arc::<[u8]>::from( builder.build() ) ).unwrap();
+        let linked = elf.link(0x0800_0000, &map(&[("ext", 0x0800_1001), ("far", 0x0A00_0001)])).unwrap();
+
+        // Check the placement of the sections.
+        let placed = linked.sections().iter().map(|s| (s.name().clone(), s.address(), s.nobits())).collect::<Vec<_>>();
+        assert_eq!(placed, [(".text".to_string(), 0x0800_0000, false), (".data".to_string(), 0x0800_0020, false), (".bss".to_string(), 0x0800_0024, true)]);
+        assert_eq!(linked.image().len(), 0x24);
+        assert_eq!(linked.size(), 0x34);
+
+        assert_eq!(linked.symbol("main"), Some(0x0800_0001));
+        assert_eq!(linked.symbol("counter"), Some(0x0800_0028));
+        assert_eq!(linked.symbol("banner"), None);
+
+        // Check the relocated instructions and data.
+        let image = linked.image();
+        let halves = (0..8).map(|i| half(image, 2 * i, big)).collect::<Vec<_>>();
+        assert_eq!(halves, [0xF000, 0xFFFE, 0xF240, 0x0028, 0xF6C0, 0x0000, 0xF7FF, 0xFFFE]);
+
+        let words = (4..8).map(|i| word(image, 4 * i, big)).collect::<Vec<_>>();
+        assert_eq!(words, [0x0800_0022, 0x0000_0FED, 0, 0]);
+        assert_eq!(&image[0x20..], b"data");
+
+        // Check the report.
+        let report = linked.report();
+        assert!( !report.complete() );
+        assert_eq!(report.unresolved(), &["missing"]);
+        assert!( report.unsupported().is_empty() );
+
+        assert_eq!(report.overflowed().len(), 1);
+        assert_eq!(report.overflowed()[0].kind(), RelocationType::ARM(ARM::ThmCall));
+        assert_eq!(report.overflowed()[0].symbol(), "far");
+        assert_eq!(report.overflowed()[0].address(), 0x0800_000C);
+    }
+}
+
+#[test]
+fn link_riscv() {
+    for &class64 in &[false, true] {
+        let mut builder = common::Builder::new(class64, false, 0xF3);
+        builder.filetype = 1;
+
+        // Absolute and relative addressing, including compressed branches.
+        let mut text = Vec::new();
+
+        for word in &[0x0000_0537, 0x0005_0513, 0x00B5_2023, 0x0000_0097, 0x0000_80E7, 0x00B5_0063, 0x0000_00EF] {
+            text.extend( builder.word(*word) );
+        }
+
+        for half in &[0xC101, 0xA001, 0xA001, 0x8082] {
+            text.extend( builder.half(*half) );
+        }
+
+        text.extend( builder.word(0x0000_05B7) );
+
+        builder.sections.push( common::Section::new(".text", 1, text).alloc(0x6, 0).align(4) );
+
+        let mut bss = common::Section::new(".bss", 8, Vec::new()).alloc(0x3, 0).align(4);
+        bss.nobits = 8;
+        builder.sections.push( bss );
+
+        builder.symbols.push( common::Sym::new("main", 0, 0x24, 0x12, 1) );
+        builder.symbols.push( common::Sym::new("counter", 4, 4, 0x11, 2) );
+        builder.symbols.push( common::Sym::new("ext", 0, 0, 0x10, 0) );
+        builder.symbols.push( common::Sym::new("low", 0, 0, 0x10, 0) );
+        builder.symbols.push( common::Sym::new("far", 0, 0, 0x10, 0) );
+        builder.symbols.push( common::Sym::new("high", 0, 0, 0x10, 0) );
+
+        let symtab = builder.symtab() + 1;
+        let mut data = Vec::new();
+
+        let relocations = [
+            (0x00, 2, 26, 0), (0x00, 0, 51, 0), (0x04, 2, 27, 0), (0x08, 2, 28, 0), (0x0C, 3, 18, 0), (0x0C, 3, 23, 0),
+            (0x14, 1, 16, 0), (0x18, 3, 17, 0), (0x1C, 1, 44, 0x16), (0x1E, 4, 45, 0), (0x20, 5, 45, 0), (0x24, 6, 26, 0),
+        ];
+
+        for (offset, symbol, kind, addend) in &relocations {
+            data.extend( builder.reloc(*offset, *symbol, *kind, Some(*addend)) );
+        }
+
+        let entsize = (data.len() / relocations.len()) as u64;
+        builder.sections.push( common::Section::new(".rela.text", 4, data).link(symtab, 1).entsize(entsize) );
+
+        let elf = ELFObject::parse( Arc::<[u8]>::from( builder.build() ) ).unwrap();
+        let symbols = map(&[("ext", 0x2000_1234), ("low", 0x1FFF_F822), ("far", 0x3000_0000), ("high", 0x8000_0000)]);
+        let linked = elf.link(0x2000_0000, &symbols).unwrap();
+
+        assert_eq!(linked.symbol("counter"), Some(0x2000_002C));
+        assert_eq!(linked.size(), 0x30);
+
+        // Check the relocated instructions.
+        let image = linked.image();
+        let words = (0..7).map(|i| word(image, 4 * i, false)).collect::<Vec<_>>();
+        assert_eq!(words, [0x2000_0537, 0x02C5_0513, 0x02B5_2623, 0x0000_1097, 0x2280_80E7, 0xFEB5_06E3, 0x21C0_10EF]);
+
+        let halves = (0..4).map(|i| half(image, 0x1C + (2 * i), false)).collect::<Vec<_>>();
+        assert_eq!(halves, [0xDD6D, 0xB011, 0xA001, 0x8082]);
+
+        // The upper immediate only overflows in 64 bit files, where it is sign extended.
+        let report = linked.report();
+        let overflowed = report.overflowed().iter().map(|issue| (issue.kind(), issue.symbol().clone())).collect::<Vec<_>>();
+
+        match class64 {
+            true => {
+                assert_eq!(word(image, 0x24, false), 0x0000_05B7);
+                assert_eq!(overflowed, [(RelocationType::RISCV(RISCV::RvcJump), "far".to_string()), (RelocationType::RISCV(RISCV::Hi20), "high".to_string())]);
+            },
+            _ => {
+                assert_eq!(word(image, 0x24, false), 0x8000_05B7);
+                assert_eq!(overflowed, [(RelocationType::RISCV(RISCV::RvcJump), "far".to_string())]);
+            },
+        }
+
+        assert!( report.unresolved().is_empty() );
+        assert_eq!(report.unsupported().len(), 1);
+        assert_eq!(report.unsupported()[0].kind(), RelocationType::RISCV(RISCV::PcrelHi20));
+    }
+}
+
+#[test]
+fn link_executable() {
+    let elf = ELFObject::parse( Arc::<[u8]>::from( common::firmware(false, false).build() ) ).unwrap();
+    assert_eq!(elf.link(0, &HashMap::new()).unwrap_err(), Error::NotRelocatable);
+}
@@ -0,0 +1,80 @@
+//! Memory read tests.
+//! The addresses of the loaded image must be translated through the segments, or through the
+//! allocated sections when there are no segments, in both virtual and physical addresses.
+
+mod common;
+
+
+
+use micro_elf::{
+    elf::{
+        ELFObject,
+        data::AddressSpace,
+    },
+    error::Error,
+};
+
+
+
+#[test]
+fn read_segments() {
+    for &class64 in &[false, true] {
+        for &big in &[false, true] {
+            // Read with and without the section headers.
+            let mut stripped = common::firmware(class64, big).build();
+            common::strip(&mut stripped, class64);
+
+            for image in [common::firmware(class64, big).build(), stripped] {
+                let elf = ELFObject::parse( image ).unwrap();
+
+                assert_eq!(&*elf.read_at(0x0800_0000, 4, AddressSpace::Virtual).unwrap(), [0x70, 0x47, 0x00, 0xBF]);
+                assert_eq!(elf.read_cstr_at(0x0800_0008, AddressSpace::Virtual).unwrap(), "micro-elf");
+                assert_eq!(elf.read_cstr_at(0x0800_000E, AddressSpace::Physical).unwrap(), "elf");
+
+                // Initialized data is found at its load address in the physical address space.
+                assert_eq!(elf.read_u32_at(0x2000_0000, AddressSpace::Virtual), Ok(0xDEAD_BEEF));
+                assert_eq!(elf.read_u32_at(0x0800_0014, AddressSpace::Physical), Ok(0xDEAD_BEEF));
+                assert_eq!(elf.read_u16_at(0x0800_0018, AddressSpace::Physical), Ok(if big { 0 } else { 3 }));
+                assert_eq!(elf.read_u32_at(0x2000_0000, AddressSpace::Physical), Err( Error::Unmapped(0x2000_0000) ));
+
+                // The zero filled part of the segment reads as zeros.
+                let mut counter = common::Builder::new(class64, big, 0x28).word(3);
+                counter.extend_from_slice( &[0; 4] );
+
+                assert_eq!(&*elf.read_at(0x2000_0004, 8, AddressSpace::Virtual).unwrap(), &counter[..]);
+                assert_eq!(elf.read_u64_at(0x2000_0020, AddressSpace::Virtual), Ok(0));
+                assert_eq!(elf.read_cstr_at(0x2000_0010, AddressSpace::Virtual).unwrap(), "");
+
+                // Reads past the end of the segment fail at the first unmapped address.
+                assert_eq!(elf.read_at(0x2000_0024, 8, AddressSpace::Virtual), Err( Error::Unmapped(0x2000_0028) ));
+                assert_eq!(elf.read_at(0x0800_0010, 4, AddressSpace::Virtual), Err( Error::Unmapped(0x0800_0012) ));
+                assert_eq!(elf.read_u32_at(0x1000_0000, AddressSpace::Virtual), Err( Error::Unmapped(0x1000_0000) ));
+            }
+        }
+    }
+}
+
+#[test]
+fn read_sections() {
+    for &class64 in &[false, true] {
+        for &big in &[false, true] {
+            // Without segments the allocated sections are used, in both address spaces.
+            let mut builder = common::firmware(class64, big);
+            builder.programs.clear();
+
+            let elf = ELFObject::parse( builder.build() ).unwrap();
+
+            for &space in &[AddressSpace::Virtual, AddressSpace::Physical] {
+                assert_eq!(elf.read_cstr_at(0x0800_0008, space).unwrap(), "micro-elf");
+                assert_eq!(elf.read_u32_at(0x2000_0000, space), Ok(0xDEAD_BEEF));
+
+                // Reads may span several sections, and sections without data read as zeros.
+                let mut data = common::Builder::new(class64, big, 0x28).word(3);
+                data.extend_from_slice( &[0; 8] );
+
+                assert_eq!(&*elf.read_at(0x2000_0004, 12, space).unwrap(), &data[..]);
+                assert_eq!(elf.read_at(0x2000_0020, 16, space), Err( Error::Unmapped(0x2000_0028) ));
+            }
+        }
+    }
+}
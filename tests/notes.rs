@@ -0,0 +1,160 @@
+//! Note tests.
+//! The notes must be found through the note sections and, when they are stripped, through the note segments.
+
+mod common;
+
+
+
+use micro_elf::elf::{
+    ELFObject,
+    data::note::{ AbiOS, NoteData, Property },
+};
+
+use std::sync::Arc;
+
+
+
+/// Build ID of the test images.
+const BUILD_ID: [u8; 20] = [0x15, 0xDF, 0xFF, 0x32, 0x39, 0xAA, 0x7C, 0x3B, 0x16, 0xA7, 0x1E, 0x6B, 0x2E, 0x3B, 0x6E, 0x40, 0x09, 0xDA, 0xB9, 0x98];
+
+/// Encodes a note with the given name, type and descriptor, padded to the given alignment.
+fn note(builder: &common::Builder, name: &str, kind: u32, descriptor: &[u8], align: usize) -> Vec<u8> {
+    let mut data = builder.word(name.len() as u32 + 1);
+    data.extend( builder.word(descriptor.len() as u32) );
+    data.extend( builder.word(kind) );
+
+    data.extend_from_slice( name.as_bytes() );
+    data.resize( (data.len() + 1).next_multiple_of(align), 0 );
+
+    data.extend_from_slice( descriptor );
+    data.resize( data.len().next_multiple_of(align), 0 );
+
+    data
+}
+
+/// Builds an executable with the usual GNU notes.
+/// The property note is aligned to 8 bytes in 64 bit files, like the linkers do.
+fn noted(class64: bool, big: bool) -> common::Builder {
+    let mut builder = common::Builder::new(class64, big, if class64 { 0x3E } else { 0x03 });
+    builder.filetype = 2;
+
+    let align = if class64 { 8 } else { 4 };
+
+    // Control-flow protection features followed by an unknown property.
+    let mut properties = Vec::new();
+
+    for (kind, value) in &[(0xC000_0002, 3), (0xC000_8002, 1)] {
+        properties.extend( builder.word(*kind) );
+        properties.extend( builder.word(4) );
+        properties.extend( builder.word(*value) );
+        properties.resize( properties.len().next_multiple_of(align), 0 );
+    }
+
+    let mut abi = Vec::new();
+
+    for word in &[0, 3, 2, 0] {
+        abi.extend( builder.word(*word) );
+    }
+
+    let property = note(&builder, "GNU", 5, &properties, align);
+    let mut notes = note(&builder, "GNU", 3, &BUILD_ID, 4);
+    notes.extend( note(&builder, "GNU", 1, &abi, 4) );
+    notes.extend( note(&builder, "Go", 4, b"go-build-id", 4) );
+
+    builder.sections.push( common::Section::new(".note.gnu.property", 7, property.clone()).alloc(0x2, 0x400).align(align as u64) );
+    builder.sections.push( common::Section::new(".note", 7, notes.clone()).alloc(0x2, 0x400 + property.len() as u64).align(4) );
+    builder.sections.push( common::Section::new(".note.gnu.gold-version", 7, note(&builder, "GNU", 4, b"gold 1.16\0", 4)).align(4) );
+
+    builder.programs.push( common::Program { ptype: 1, flags: 0x4, sections: (0, 1), paddr: 0x400 } );
+    builder.programs.push( common::Program { ptype: 4, flags: 0x4, sections: (0, 0), paddr: 0x400 } );
+    builder.programs.push( common::Program { ptype: 4, flags: 0x4, sections: (1, 1), paddr: 0x400 + property.len() as u64 } );
+
+    builder
+}
+
+#[test]
+fn notes() {
+    for &class64 in &[false, true] {
+        for &big in &[false, true] {
+            let mut image = noted(class64, big).build();
+
+            for stripped in [false, true] {
+                if stripped {
+                    common::strip(&mut image, class64);
+                }
+
+                let elf = ELFObject::parse( Arc::<[u8]>::from( image.clone() ) ).unwrap();
+                let notes = elf.notes().collect::<Result<Vec<_>, _>>().unwrap();
+
+                // The gold version is not in a segment.
+                let found = notes.iter().map(|n| (n.name(), n.kind(), n.descriptor().len())).collect::<Vec<_>>();
+
+                match stripped {
+                    true => assert_eq!(found, [("GNU", 5, if class64 { 32 } else { 24 }), ("GNU", 3, 20), ("GNU", 1, 16), ("Go", 4, 11)]),
+                    _ => assert_eq!(found, [("GNU", 5, if class64 { 32 } else { 24 }), ("GNU", 3, 20), ("GNU", 1, 16), ("Go", 4, 11), ("GNU", 4, 10)]),
+                }
+
+                // Decode the well-known notes.
+                match notes[0].decode().unwrap() {
+                    NoteData::Properties(properties) => {
+                        assert_eq!(properties[0], Property::X86Features { ibt: true, shstk: true, raw: 3 });
+                        assert!( matches!(properties[1], Property::Other { kind: 0xC000_8002, .. }) );
+                        assert_eq!(properties.len(), 2);
+                    },
+                    data => panic!("Unexpected note {:?}", data),
+                }
+
+                assert_eq!(notes[1].decode(), Some( NoteData::BuildId(&BUILD_ID) ));
+                assert_eq!(notes[2].decode(), Some( NoteData::AbiTag { os: AbiOS::Linux, major: 3, minor: 2, patch: 0 } ));
+                assert_eq!(notes[3].decode(), None);
+
+                if !stripped {
+                    assert_eq!(notes[4].decode(), Some( NoteData::GoldVersion("gold 1.16") ));
+                }
+
+                assert_eq!(format!("{}", notes[1].decode().unwrap()), "Build ID: 15dfff3239aa7c3b16a71e6b2e3b6e4009dab998");
+                assert_eq!(elf.build_id(), Some( &BUILD_ID[..] ));
+            }
+        }
+    }
+}
+
+#[test]
+fn aarch64_properties() {
+    for &big in &[false, true] {
+        let mut builder = common::Builder::new(true, big, 0xB7);
+        builder.filetype = 1;
+
+        let mut properties = builder.word(0xC000_0000);
+        properties.extend( builder.word(4) );
+        properties.extend( builder.word(1) );
+        properties.extend( builder.word(0) );
+
+        let property = note(&builder, "GNU", 5, &properties, 8);
+        builder.sections.push( common::Section::new(".note.gnu.property", 7, property).alloc(0x2, 0).align(8) );
+
+        let elf = ELFObject::parse( Arc::<[u8]>::from( builder.build() ) ).unwrap();
+        let notes = elf.notes().collect::<Result<Vec<_>, _>>().unwrap();
+
+        assert_eq!(notes[0].decode(), Some( NoteData::Properties( vec![Property::AArch64Features { bti: true, pac: false, raw: 1 }] ) ));
+        assert_eq!(elf.build_id(), None);
+    }
+}
+
+#[test]
+fn malformed_note() {
+    let mut builder = common::firmware(false, false);
+
+    // The descriptor size runs past the end of the section.
+    let mut data = note(&builder, "GNU", 3, &BUILD_ID, 4);
+    data[4] = 0x40;
+
+    builder.sections.push( common::Section::new(".note.gnu.build-id", 7, data).align(4) );
+
+    let elf = ELFObject::parse( Arc::<[u8]>::from( builder.build() ) ).unwrap();
+    let notes = elf.notes().collect::<Vec<_>>();
+
+    assert_eq!(notes.len(), 1);
+    assert!( notes[0].is_err() );
+    assert_eq!(elf.build_id(), None);
+}
@@ -0,0 +1,86 @@
+//! Relocation parsing tests.
+
+mod common;
+
+
+
+use micro_elf::elf::{
+    ELFObject,
+    data::relocation::{ ARM, RISCV, RelocationType },
+};
+
+use std::sync::Arc;
+
+
+
+#[test]
+fn arm_rel() {
+    for &big in &[false, true] {
+        let mut builder = common::firmware(false, big);
+        builder.filetype = 1;
+        builder.programs.clear();
+
+        // Call `helper` from `main` and reference `banner` as data.
+        let symtab = builder.symtab() + 1;
+        let mut data = builder.reloc(0x0, 2, 10, None);
+        data.extend( builder.reloc(0x4, 4, 2, None) );
+        data.extend( builder.reloc(0x6, 0, 0, None) );
+
+        builder.sections.push( common::Section::new(".rel.text", 9, data).link(symtab, 1).entsize(8) );
+
+        let elf = ELFObject::parse( Arc::<[u8]>::from( builder.build() ) ).unwrap();
+        let tables = elf.relocations().unwrap();
+
+        assert_eq!(tables.len(), 1);
+        assert!( !tables[0].rela() );
+        assert_eq!(tables[0].target().unwrap().name(), ".text");
+        assert_eq!(tables[0].symtab().unwrap().name(), ".symtab");
+
+        let relocations = tables[0].relocations();
+        assert_eq!(relocations.len(), 3);
+
+        assert_eq!(relocations[0].kind(), RelocationType::ARM(ARM::ThmCall));
+        assert_eq!(relocations[0].symbol().unwrap().name(), "helper");
+        assert_eq!(relocations[0].addend(), None);
+
+        assert_eq!(relocations[1].kind(), RelocationType::ARM(ARM::Abs32));
+        assert_eq!(u64::from( relocations[1].offset() ), 4);
+        assert_eq!(relocations[1].symbol().unwrap().name(), "banner");
+
+        assert_eq!(relocations[2].kind(), RelocationType::ARM(ARM::None));
+        assert!( relocations[2].symbol().is_none() );
+    }
+}
+
+#[test]
+fn riscv_rela() {
+    for &class64 in &[false, true] {
+        let mut builder = common::firmware(class64, false);
+        builder.machine = 0xF3;
+        builder.filetype = 1;
+        builder.programs.clear();
+
+        // Load the address of `counter` with a negative addend.
+        let symtab = builder.symtab() + 1;
+        let mut data = builder.reloc(0x0, 5, 26, Some(-4));
+        data.extend( builder.reloc(0x4, 5, 27, Some(-4)) );
+        data.extend( builder.reloc(0x8, 3, 18, Some(0)) );
+
+        let entsize = (data.len() / 3) as u64;
+        builder.sections.push( common::Section::new(".rela.text", 4, data).link(symtab, 1).entsize(entsize) );
+
+        let elf = ELFObject::parse( Arc::<[u8]>::from( builder.build() ) ).unwrap();
+        let tables = elf.relocations().unwrap();
+        let relocations = tables[0].relocations();
+
+        assert!( tables[0].rela() );
+
+        let kinds = relocations.iter().map(|r| r.kind()).collect::<Vec<_>>();
+        assert_eq!(kinds, [RelocationType::RISCV(RISCV::Hi20), RelocationType::RISCV(RISCV::Lo12I), RelocationType::RISCV(RISCV::Call)]);
+
+        assert_eq!(relocations[0].addend(), Some(-4));
+        assert_eq!(relocations[1].symbol().unwrap().name(), "counter");
+        assert_eq!(relocations[2].symbol().unwrap().name(), "main");
+        assert_eq!(format!("{}", relocations[2].kind()), "R_RISCV_CALL");
+    }
+}
@@ -0,0 +1,92 @@
+//! Serialization round trip tests.
+//! An unmodified object must be written back byte for byte.
+
+mod common;
+
+
+
+use micro_elf::elf::ELFObject;
+
+use std::sync::Arc;
+
+
+
+/// Parses the given image and checks it is written back unchanged.
+fn roundtrip(image: &[u8]) {
+    let elf = ELFObject::parse( Arc::<[u8]>::from(image) ).expect("Failed to parse the image");
+
+    let bytes = elf.to_bytes();
+    assert_eq!(bytes.len(), image.len());
+    assert!(bytes[..] == image[..], "The written image differs from the original");
+
+    let mut written = Vec::new();
+    elf.write(&mut written).expect("Failed to write the image");
+    assert_eq!(written, bytes);
+}
+
+#[test]
+fn roundtrip_built() {
+    for &class64 in &[false, true] {
+        for &big in &[false, true] {
+            let image = common::firmware(class64, big).build();
+            roundtrip(&image);
+
+            // Check the contents survived the trip.
+            let elf = ELFObject::parse( Arc::<[u8]>::from(image) ).unwrap();
+            let names = elf.symbols().iter().map(|s| s.name().clone()).collect::<Vec<_>>();
+
+            assert_eq!(names, ["", "fw.c", "helper", "main", "banner", "counter", "hidden", "__bss_start"]);
+            assert_eq!(elf.programs().len(), 2);
+            assert_eq!(usize::from( elf.section(".bss").unwrap().filesize() ), 0x20);
+        }
+    }
+}
+
+#[test]
+fn roundtrip_fixtures() {
+    for name in &["le32.o", "le64.o", "le32.elf", "le64.elf"] {
+        let path = std::path::Path::new( env!("CARGO_MANIFEST_DIR") ).join("tests/data").join(name);
+        roundtrip( &std::fs::read(path).unwrap() );
+    }
+}
+
+#[test]
+fn roundtrip_unknown() {
+    use common::{ Builder, Program, Section, Sym };
+    use micro_elf::elf::data::{ header::Architecture, section::SectionType, symbol::{ Bind, SymbolType } };
+
+    for &class64 in &[false, true] {
+        // Unknown machine, file type, section types, segment type, bind and symbol type.
+        let mut b = Builder::new(class64, false, 0x1234);
+        b.filetype = 0x0042;
+
+        b.sections.push( Section::new(".text", 1, vec![0x11; 8]).alloc(0x6, 0x1000).align(4) );
+        b.sections.push( Section::new(".os", 0x6000_0010, vec![0x22; 3]) );
+        b.sections.push( Section::new(".processor", 0x7000_0003, vec![0x33; 5]).align(8) );
+        b.sections.push( Section::new(".unknown", 0x20, vec![0x44; 2]) );
+
+        b.programs.push( Program { ptype: 0x10, flags: 0x5, sections: (0, 0), paddr: 0x1000 } );
+        b.symbols.push( Sym::new("odd", 0x1000, 4, 0x57, 1) );
+
+        let mut image = b.build();
+
+        // Non zero identification padding and bytes after all the tables.
+        image[0x09..0x10].copy_from_slice( &[1, 2, 3, 4, 5, 6, 7] );
+        image.extend_from_slice( b"trailer" );
+
+        roundtrip(&image);
+
+        // Check the raw values are kept.
+        let elf = ELFObject::parse( Arc::<[u8]>::from(image) ).unwrap();
+
+        assert_eq!(elf.architecture(), Architecture::None);
+        assert_eq!(elf.machine(), 0x1234);
+
+        let types = elf.sections().iter().map(|section| section.sectiontype()).collect::<Vec<_>>();
+        assert_eq!(types[2..5], [SectionType::OS(0x6000_0010), SectionType::OS(0x7000_0003), SectionType::Other(0x20)]);
+
+        let symbol = &elf.symbol("odd")[0];
+        assert_eq!(symbol.bind(), Bind::Other(5));
+        assert_eq!(symbol.kind(), SymbolType::Other(7));
+    }
+}
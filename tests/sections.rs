@@ -0,0 +1,120 @@
+//! Section editing tests.
+//! Modified objects must be laid out again into a valid file.
+
+mod common;
+
+
+
+use micro_elf::elf::{
+    ELFObject,
+    data::section::SectionType,
+};
+
+use std::sync::Arc;
+
+
+
+/// Writes the object and parses the written image again.
+fn reparse(elf: &ELFObject<Arc<[u8]>>) -> ELFObject<Arc<[u8]>> {
+    ELFObject::parse( Arc::<[u8]>::from( elf.to_bytes() ) ).expect("Failed to parse the written image")
+}
+
+/// Returns the names of the sections of the object.
+fn names(elf: &ELFObject<Arc<[u8]>>) -> Vec<String> {
+    elf.sections().iter().map(|s| s.name().clone()).collect()
+}
+
+#[test]
+fn edit_relocatable() {
+    for &class64 in &[false, true] {
+        for &big in &[false, true] {
+            let mut builder = common::firmware(class64, big);
+            builder.filetype = 1;
+            builder.programs.clear();
+
+            let mut elf = ELFObject::parse( Arc::<[u8]>::from( builder.build() ) ).unwrap();
+
+            // Drop a section in the middle, add, rename and resize others.
+            assert!( elf.remove_section(".rodata").is_some() );
+            assert!( elf.remove_section(0).is_none() );
+            elf.add_section(".fw_meta", SectionType::ProgramData, b"META".to_vec()).set_alignment(16);
+            assert!( elf.rename_section(".data", ".data.fw") );
+            assert!( elf.resize_section(".comment", 4) );
+            assert!( !elf.rename_section(".missing", ".other") );
+
+            let elf = reparse(&elf);
+
+            assert_eq!(names(&elf), ["", ".text", ".data.fw", ".bss", ".comment", ".symtab", ".strtab", ".shstrtab", ".fw_meta"]);
+
+            // Check the contents survived the layout.
+            let content = |name: &str| elf.content( &**elf.section(name).unwrap() ).map(|c| c.iter().cloned().collect::<Vec<u8>>());
+            assert_eq!(content(".fw_meta").unwrap(), b"META");
+            assert_eq!(content(".comment").unwrap(), b"GCC:");
+            assert_eq!(content(".text").unwrap(), [0x70, 0x47, 0x00, 0xBF, 0x01, 0x20, 0x70, 0x47]);
+            assert_eq!(usize::from( elf.section(".fw_meta").unwrap().offset() ) % 16, 0);
+
+            // Check the section indices were remapped.
+            assert_eq!(elf.section(".symtab").unwrap().link(), 6);
+
+            let shidx = elf.symbols().iter().map(|s| (s.name().clone(), s.shidx())).collect::<Vec<_>>();
+            assert_eq!(shidx[4], ("banner".to_string(), 0));
+            assert_eq!(shidx[5], ("counter".to_string(), 2));
+            assert_eq!(shidx[7], ("__bss_start".to_string(), 3));
+            assert_eq!(shidx[1], ("fw.c".to_string(), 0xFFF1));
+        }
+    }
+}
+
+#[test]
+fn edit_executable() {
+    let image = common::firmware(false, false).build();
+    let original = ELFObject::parse( Arc::<[u8]>::from( image ) ).unwrap();
+    let mut elf = ELFObject::parse( original.to_bytes().into() ).unwrap();
+
+    // Sections mapped by a segment cannot grow, but can be renamed.
+    assert!( !elf.resize_section(".text", 0x100) );
+    assert!( elf.rename_section(".text", ".text.fw") );
+    assert!( elf.remove_section(".comment").is_some() );
+
+    let elf = reparse(&elf);
+
+    assert_eq!(names(&elf), ["", ".text.fw", ".rodata", ".data", ".bss", ".symtab", ".strtab", ".shstrtab"]);
+    assert_eq!(elf.programs().len(), 2);
+
+    // Check the mapped sections kept their place in the file.
+    for name in &[".rodata", ".data", ".bss"] {
+        assert_eq!(elf.section(*name).unwrap().offset(), original.section(*name).unwrap().offset());
+    }
+
+    assert_eq!(elf.section(".text.fw").unwrap().offset(), original.section(".text").unwrap().offset());
+}
+
+#[test]
+fn invalid_names() {
+    let mut image = common::firmware(false, false).build();
+
+    // Replaces the first byte of the given name in the string tables.
+    let mut corrupt = |name: &[u8], byte: u8| {
+        let at = image.windows( name.len() ).position(|window| window == name).unwrap();
+        image[at + 1] = byte;
+    };
+
+    corrupt(b"\0.comment\0", 0xFF);
+    corrupt(b"\0helper\0", 0xFE);
+
+    let mut elf = ELFObject::parse( Arc::<[u8]>::from( image ) ).unwrap();
+
+    // The names are converted lossily, keeping their raw bytes.
+    let comment = elf.sections().iter().position(|s| s.raw_name() == b"\xFFcomment").unwrap();
+    assert_eq!(elf.sections()[comment].name(), "\u{FFFD}comment");
+    assert_eq!(elf.symbols()[2].name(), "\u{FFFD}elper");
+
+    // Laying the file out again writes the raw names back.
+    assert!( elf.rename_section(".rodata", ".ro") );
+
+    let elf = reparse(&elf);
+
+    assert_eq!(elf.sections()[comment].raw_name(), b"\xFFcomment");
+    assert_eq!(elf.symbols()[2].raw_name(), b"\xFEelper");
+    assert_eq!(elf.section(".ro").unwrap().raw_name(), b".ro");
+}
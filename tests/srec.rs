@@ -0,0 +1,123 @@
+//! S-record tests.
+//! The records must match the output of `objcopy -O srec` with the address width, header and count
+//! given in the options, read into the memory image with their start address, and invalid records
+//! must be reported at their line and column.
+
+mod common;
+
+
+
+use micro_elf::{
+    elf::ELFObject,
+    error::{ Error, RecordError },
+    image::{ MemoryImage, SrecOptions, SrecWidth },
+};
+
+
+
+/// Joins the records with the line endings of the writer.
+fn lines(records: &[&str]) -> String {
+    records.iter().map(|record| format!("{}\r\n", record)).collect()
+}
+
+#[test]
+fn elf() {
+    // The data is written at its load address and the entry point is the start address.
+    let elf = ELFObject::parse( common::firmware(false, false).build() ).unwrap();
+
+    let expected = lines(&[
+        "S00D00006233326C652E737265637F",
+        "S30D08000000704700BF012070479C",
+        "S30F080000086D6963726F2D656C660062",
+        "S30D08000014EFBEADDE030000009B",
+        "S70508000001F1",
+    ]);
+
+    let options = SrecOptions { header: String::from("b32le.srec"), ..SrecOptions::default() };
+    assert_eq!(elf.to_srec(&options).unwrap(), expected);
+
+    // The flash cannot be reached with 24 bit addresses.
+    let options = SrecOptions { width: SrecWidth::S28, ..SrecOptions::default() };
+    assert_eq!(elf.to_srec(&options), Err( Error::Overflow(0x0800_0000) ));
+}
+
+#[test]
+fn objcopy() {
+    // The golden file is the output of `objcopy -O srec` for the test object, written to `out.srec`.
+    let dir = std::path::Path::new( env!("CARGO_MANIFEST_DIR") ).join("tests/data");
+
+    let elf = ELFObject::parse( std::fs::read( dir.join("le32.elf") ).unwrap() ).unwrap();
+    let expected = std::fs::read_to_string( dir.join("le32.srec") ).unwrap();
+
+    let options = SrecOptions { header: String::from("out.srec"), ..SrecOptions::default() };
+    assert_eq!(elf.to_srec(&options).unwrap(), expected);
+}
+
+#[test]
+fn write() {
+    let mut image = MemoryImage::new();
+    image.insert(0xFFFE, &[1, 2, 3, 4, 5, 6]).unwrap();
+    image.set_start( Some(0x1_0000) );
+
+    // The width fits the highest address, with a count record.
+    let options = SrecOptions { header: String::from("app"), record: 4, count: true, ..SrecOptions::default() };
+    let output = image.to_srec(&options).unwrap();
+
+    assert_eq!(output, lines(&["S0060000617070B8", "S20800FFFE01020304F0", "S2060100020506EB", "S5030002FA", "S804010000FA"]));
+    assert_eq!(MemoryImage::from_srec(&output).unwrap(), image);
+
+    // Forced widths.
+    let mut image = MemoryImage::new();
+    image.insert(0x100, &[1, 2]).unwrap();
+
+    assert_eq!(image.to_srec( &SrecOptions::default() ).unwrap(), lines(&["S0030000FC", "S10501000102F6", "S9030000FC"]));
+    assert!( image.to_srec( &SrecOptions { width: SrecWidth::S37, ..SrecOptions::default() } ).unwrap().contains("\r\nS307000001000102") );
+
+    let options = SrecOptions { width: SrecWidth::S19, start: Some(0x1_0000), ..SrecOptions::default() };
+    assert_eq!(image.to_srec(&options), Err( Error::Overflow(0x1_0000) ));
+}
+
+#[test]
+fn read() {
+    // The firmware written by `objcopy -O srec` reads back into the image of the object.
+    let elf = ELFObject::parse( common::firmware(false, false).build() ).unwrap();
+
+    let image = MemoryImage::from_srec( &lines(&[
+        "S00D00006233326C652E737265637F",
+        "S30D08000000704700BF012070479C",
+        "S30F080000086D6963726F2D656C660062",
+        "S30D08000014EFBEADDE030000009B",
+        "S70508000001F1",
+    ]) ).unwrap();
+
+    assert_eq!(image, elf.memory_image().unwrap());
+
+    // Records of different address sizes, with a count record.
+    let image = MemoryImage::from_srec( &lines(&["S0060000686472BB", "S107010001020304ED", "S20501000009F0", "S5030002FA", "S9030100FB"]) ).unwrap();
+
+    let regions = image.regions().iter().map(|region| (region.address(), region.data().to_vec())).collect::<Vec<_>>();
+    assert_eq!(regions, [(0x100, vec![1, 2, 3, 4]), (0x1_0000, vec![9])]);
+    assert_eq!(image.start(), Some(0x100));
+}
+
+#[test]
+fn read_errors() {
+    let error = |records: &[&str]| match MemoryImage::from_srec( &lines(records) ) {
+        Err( Error::BadRecord { line, column, kind } ) => (line, column, kind),
+        other => panic!("Unexpected result: {:?}", other),
+    };
+
+    assert_eq!(error(&[":107010001020304ED"]), (1, 1, RecordError::Start));
+    assert_eq!(error(&["S4030100FB"]), (1, 2, RecordError::Type));
+    assert_eq!(error(&["S10701000102030XED"]), (1, 16, RecordError::Digit));
+    assert_eq!(error(&["S108010001020304ED"]), (1, 3, RecordError::Length));
+    assert_eq!(error(&["S107010001020304EE"]), (1, 17, RecordError::Checksum));
+
+    // The header comes first, the count matches the data records and the termination comes last.
+    assert_eq!(error(&["S107010001020304ED", "S0060000686472BB"]), (2, 1, RecordError::Order));
+    assert_eq!(error(&["S107010001020304ED", "S5030003F9"]), (2, 5, RecordError::Count));
+    assert_eq!(error(&["S5030000FC", "S107010001020304ED"]), (2, 1, RecordError::Order));
+    assert_eq!(error(&["S9030100FB", "S9030100FB"]), (2, 1, RecordError::Order));
+    assert_eq!(error(&["S107010001020304ED", "S107010001020304ED"]), (2, 9, RecordError::Overlap));
+    assert_eq!(error(&["S107010001020304ED"]), (2, 1, RecordError::MissingEnd));
+}
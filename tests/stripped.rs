@@ -0,0 +1,96 @@
+//! Stripped binary tests.
+//! Files without a symbol table or without section headers must still parse.
+
+mod common;
+
+
+
+use common::{ Builder, Section, Sym };
+
+use micro_elf::{
+    elf::ELFObject,
+    error::{ Error, Table },
+};
+
+
+
+/// Builds a small image with one loaded section and, optionally, symbols.
+fn image(class64: bool, symbols: bool) -> Builder {
+    let mut b = Builder::new(class64, false, 0x28);
+    b.entry = 0x0800_0001;
+
+    b.sections.push( Section::new(".text", 1, vec![0x70, 0x47, 0x00, 0xBF]).alloc(0x6, 0x0800_0000).align(4) );
+    b.programs.push( common::Program { ptype: 1, flags: 0x5, sections: (0, 0), paddr: 0x0800_0000 } );
+
+    if symbols {
+        b.symbols.push( Sym::new("main", 0x0800_0001, 4, 0x12, 1) );
+    }
+
+    b
+}
+
+/// Returns the offset of the `sh_link` field of the `.symtab` header of a 32 bit image.
+fn link(b: &Builder, image: &[u8]) -> usize {
+    let shoff = u32::from_le_bytes([image[0x20], image[0x21], image[0x22], image[0x23]]) as usize;
+
+    shoff + (b.symtab() as usize * 40) + 24
+}
+
+#[test]
+fn no_symtab() {
+    for &class64 in &[false, true] {
+        let elf = ELFObject::parse( image(class64, false).build() ).expect("Failed to parse a file without symbols");
+
+        assert!( elf.symbols().is_empty() );
+        assert!( elf.section(".symtab").is_none() );
+        assert_eq!( elf.programs().len(), 1 );
+        assert_eq!( elf.section(".text").map(|s| usize::from( s.filesize() )), Some(4) );
+    }
+}
+
+#[test]
+fn no_section_headers() {
+    for &class64 in &[false, true] {
+        let mut raw = image(class64, true).build();
+        common::strip(&mut raw, class64);
+
+        let elf = ELFObject::parse( raw ).expect("Failed to parse a file without section headers");
+
+        assert!( elf.sections().is_empty() );
+        assert!( elf.symbols().is_empty() );
+        assert_eq!( elf.programs().len(), 1 );
+    }
+}
+
+#[test]
+fn linked_string_table() {
+    for &class64 in &[false, true] {
+        let mut raw = image(class64, true).build();
+
+        // Rename `.strtab` so it can only be found through the `sh_link` of the symbol table.
+        let at = raw.windows(8).position(|w| w == b".strtab\0").unwrap();
+        raw[at..at+8].copy_from_slice( b".labels\0" );
+
+        let elf = ELFObject::parse( raw ).expect("Failed to parse a file with a renamed string table");
+
+        assert!( elf.section(".strtab").is_none() );
+        assert!( elf.section(".labels").is_some() );
+
+        let names = elf.symbols().iter().map(|s| s.name().clone()).collect::<Vec<_>>();
+        assert_eq!( names, ["", "main"] );
+    }
+}
+
+#[test]
+fn missing_string_table() {
+    let b = image(false, true);
+
+    for &index in &[0, 0xFF] {
+        let mut raw = b.build();
+        let at = link(&b, &raw);
+        raw[at..at+4].copy_from_slice( &(index as u32).to_le_bytes() );
+
+        let err = ELFObject::parse( raw ).unwrap_err();
+        assert_eq!( err, Error::MissingStringTable { table: Table::Symbols, index } );
+    }
+}
@@ -0,0 +1,88 @@
+//! Symbolizer tests.
+//! Addresses must resolve to the best symbol that contains them, with the Thumb bit of the
+//! ARM functions cleared and the symbols without size extended up to the next symbol, within their section.
+
+mod common;
+
+
+
+use micro_elf::elf::ELFObject;
+
+
+
+/// Builds the firmware with overlapping and unsized symbols.
+fn symbolized(class64: bool, big: bool) -> common::Builder {
+    let mut builder = common::firmware(class64, big);
+
+    builder.symbols.push( common::Sym::new("main_alias", 0x0800_0001, 4, 0x22, 1) );
+    builder.symbols.push( common::Sym::new("table", 0x0800_0004, 8, 0x01, 1) );
+    builder.symbols.push( common::Sym::new("vectors", 0x2000_0000, 0, 0x11, 3) );
+    builder.symbols.push( common::Sym::new("buffer", 0x2000_0008, 0, 0x11, 4) );
+
+    builder
+}
+
+#[test]
+fn symbolize() {
+    for &class64 in &[false, true] {
+        for &big in &[false, true] {
+            let elf = ELFObject::parse( symbolized(class64, big).build() ).unwrap();
+            let symbolizer = elf.symbolizer();
+
+            let name = |address: u64| symbolizer.symbolize(address).map(|(symbol, offset)| (symbol.name().clone(), offset));
+
+            // Functions without the Thumb bit, preferred over weak aliases and objects.
+            assert_eq!(name(0x0800_0000), Some( (String::from("main"), 0) ));
+            assert_eq!(name(0x0800_0003), Some( (String::from("main"), 3) ));
+            assert_eq!(name(0x0800_0006), Some( (String::from("helper"), 2) ));
+
+            // Global objects are preferred over local objects.
+            assert_eq!(name(0x0800_000B), Some( (String::from("banner"), 3) ));
+            assert_eq!(symbolizer.describe(0x0800_0011).as_deref(), Some("banner+0x9"));
+            assert_eq!(name(0x0800_0012), None);
+
+            // Symbols without size extend up to the next symbol, or to the end of their section.
+            assert_eq!(name(0x2000_0002), Some( (String::from("vectors"), 2) ));
+            assert_eq!(name(0x2000_0004), Some( (String::from("counter"), 0) ));
+            assert_eq!(name(0x2000_0027), Some( (String::from("buffer"), 0x1F) ));
+            assert_eq!(name(0x2000_0028), None);
+            assert_eq!(name(0x1000_0000), None);
+        }
+    }
+}
+
+#[test]
+fn no_thumb() {
+    // The lowest bit of the functions is only cleared on ARM.
+    let mut builder = symbolized(false, false);
+    builder.machine = 0x03;
+
+    let elf = ELFObject::parse( builder.build() ).unwrap();
+    let symbolizer = elf.symbolizer();
+
+    assert_eq!(symbolizer.describe(0x0800_0001).as_deref(), Some("main+0x0"));
+    assert_eq!(symbolizer.describe(0x0800_0004).as_deref(), Some("main+0x3"));
+    assert_eq!(symbolizer.describe(0x0800_0000).as_deref(), None);
+}
+
+#[test]
+fn section_end() {
+    // The last symbol of the code has no size and the next symbol is in another section.
+    let mut builder = common::Builder::new(false, false, 0x28);
+
+    builder.sections.push( common::Section::new(".text", 1, vec![0x00, 0xBF, 0x00, 0xBF, 0x70, 0x47, 0x00, 0xBF]).alloc(0x6, 0x1000).align(4) );
+    builder.sections.push( common::Section::new(".data", 1, vec![0; 4]).alloc(0x3, 0x2000).align(4) );
+
+    builder.symbols.push( common::Sym::new("reset", 0x1000, 4, 0x12, 1) );
+    builder.symbols.push( common::Sym::new("tail", 0x1004, 0, 0x12, 1) );
+    builder.symbols.push( common::Sym::new("state", 0x2000, 4, 0x11, 2) );
+
+    let elf = ELFObject::parse( builder.build() ).unwrap();
+    let symbolizer = elf.symbolizer();
+
+    // The symbol without size ends with its section, not at the next symbol.
+    assert_eq!(symbolizer.describe(0x1007).as_deref(), Some("tail+0x3"));
+    assert_eq!(symbolizer.describe(0x1008), None);
+    assert_eq!(symbolizer.describe(0x1FFF), None);
+    assert_eq!(symbolizer.describe(0x2000).as_deref(), Some("state+0x0"));
+}
@@ -0,0 +1,144 @@
+//! Symbol editing tests.
+//! Edited symbol tables must keep the local symbols first and their references valid.
+
+mod common;
+
+
+
+use micro_elf::elf::{
+    ELFObject,
+    data::symbol::{ Bind, Symbol, SymbolType, ABS },
+};
+
+use std::sync::Arc;
+
+
+
+/// Builds a relocatable firmware with a relocation against `main`.
+fn relocatable(class64: bool, big: bool) -> common::Builder {
+    let mut builder = common::firmware(class64, big);
+    builder.filetype = 1;
+    builder.programs.clear();
+
+    // The symbol table follows the relocation section.
+    let symtab = builder.symtab() + 1;
+
+    // Relocate the start of the text against `main`, the fourth symbol.
+    let data = builder.reloc(0, 3, 2, None);
+
+    let entsize = data.len() as u64;
+    builder.sections.push( common::Section::new(".rel.text", 9, data).link(symtab, 1).entsize(entsize) );
+
+    builder
+}
+
+/// Returns the symbol index of the relocation in the object.
+fn relocated(elf: &ELFObject<Arc<[u8]>>, class64: bool, big: bool) -> u64 {
+    let content = elf.content( &**elf.section(".rel.text").unwrap() ).unwrap().iter().cloned().collect::<Vec<u8>>();
+
+    let (field, shift) = match class64 {
+        true => (&content[8..16], 32),
+        _ => (&content[4..8], 8),
+    };
+
+    let info = match big {
+        true => field.iter().fold(0u64, |n, b| (n << 8) | u64::from(*b)),
+        _ => field.iter().rev().fold(0u64, |n, b| (n << 8) | u64::from(*b)),
+    };
+
+    info >> shift
+}
+
+#[test]
+fn edit_symbols() {
+    for &class64 in &[false, true] {
+        for &big in &[false, true] {
+            let mut elf = ELFObject::parse( Arc::<[u8]>::from( relocatable(class64, big).build() ) ).unwrap();
+            assert_eq!(relocated(&elf, class64, big), 3);
+
+            // Localize a global in the middle of the table and edit others.
+            assert!( elf.localize_symbol("counter") );
+            assert!( elf.weaken_symbol("banner") );
+            assert!( elf.rename_symbol("helper", "vendor_helper") );
+            assert!( !elf.globalize_symbol("missing") );
+
+            let mut magic = Symbol::new("fw_magic", Bind::Global, SymbolType::Object);
+            magic.set_value(0x1234);
+            magic.set_shidx(ABS);
+            assert_eq!(elf.add_symbol(magic), 8);
+
+            // The null symbol and referenced symbols cannot be removed.
+            assert!( elf.remove_symbol(0).is_none() );
+            assert!( elf.remove_symbol(3).is_none() );
+            assert_eq!(elf.remove_symbol(7).unwrap().name(), "__bss_start");
+
+            let elf = ELFObject::parse( Arc::<[u8]>::from( elf.to_bytes() ) ).unwrap();
+
+            let names = elf.symbols().iter().map(|s| s.name().clone()).collect::<Vec<_>>();
+            assert_eq!(names, ["", "fw.c", "vendor_helper", "counter", "main", "banner", "hidden", "fw_magic"]);
+
+            // Check the locals come first and the relocation follows `main`.
+            assert_eq!(elf.section(".symtab").unwrap().info(), 4);
+            assert_eq!(relocated(&elf, class64, big), 4);
+
+            let symbol = &elf.symbols()[5];
+            assert_eq!(symbol.bind(), Bind::Weak);
+            assert_eq!(u64::from( symbol.value() ), 0x0800_0008);
+
+            let symbol = &elf.symbols()[7];
+            assert_eq!((u64::from( symbol.value() ), symbol.shidx()), (0x1234, ABS));
+        }
+    }
+}
+
+#[test]
+fn create_symbol_table() {
+    let mut builder = common::firmware(true, false);
+    builder.symbols.clear();
+
+    let mut elf = ELFObject::parse( Arc::<[u8]>::from( builder.build() ) ).unwrap();
+    assert!( elf.symbols().is_empty() );
+
+    let mut entry = Symbol::new("reset", Bind::Global, SymbolType::Function);
+    entry.set_value(0x0800_0001);
+    entry.set_shidx(1);
+    assert_eq!(elf.add_symbol(entry), 1);
+
+    let elf = ELFObject::parse( Arc::<[u8]>::from( elf.to_bytes() ) ).unwrap();
+
+    assert_eq!(elf.symbols().len(), 2);
+    assert_eq!(elf.symbols()[1].name(), "reset");
+    assert!( elf.symbols()[1].is_function() );
+}
+
+#[test]
+fn lookup_symbols() {
+    for &class64 in &[false, true] {
+        for &big in &[false, true] {
+            // Add a static function with the same name in another file.
+            let mut builder = common::firmware(class64, big);
+            builder.symbols.insert( 2, common::Sym::new("util.c", 0, 0, 0x04, 0xFFF1) );
+            builder.symbols.insert( 3, common::Sym::new("helper", 0x0800_0007, 2, 0x02, 1) );
+
+            let mut elf = ELFObject::parse( builder.build() ).unwrap();
+
+            // All the matches are returned in symbol table order.
+            let values = elf.symbol("helper").iter().map(|symbol| u64::from( symbol.value() )).collect::<Vec<_>>();
+            assert_eq!(values, [0x0800_0005, 0x0800_0007]);
+
+            assert_eq!(elf.symbol("main").len(), 1);
+            assert!( elf.symbol("missing").is_empty() );
+            assert!( elf.symbol("fw.c").is_empty() );
+
+            // The preceding file symbol tells the matches apart.
+            assert_eq!(elf.symbol_in_file("fw.c", "helper").map(|symbol| u64::from( symbol.value() )), Some(0x0800_0005));
+            assert_eq!(elf.symbol_in_file("util.c", "helper").map(|symbol| u64::from( symbol.value() )), Some(0x0800_0007));
+            assert!( elf.symbol_in_file("main.c", "helper").is_none() );
+
+            // The index follows the edits of the symbols.
+            assert!( elf.rename_symbol("main", "reset") );
+            assert!( elf.symbol("main").is_empty() );
+            assert_eq!(elf.symbol("reset").len(), 1);
+        }
+    }
+}
@@ -0,0 +1,159 @@
+//! UF2 tests.
+//! The blocks must hold the loadable segments in payloads aligned to the target pages, with the
+//! family ID of the options, read back into the same memory image, and invalid blocks must be
+//! reported at their index.
+
+mod common;
+
+
+
+use micro_elf::{
+    elf::ELFObject,
+    error::{ BlockError, Error },
+    image::{ Family, MemoryImage, Uf2Options },
+};
+
+
+
+/// Gets the little endian word at the given offset of the given block.
+fn word(uf2: &[u8], block: usize, offset: usize) -> u32 {
+    let offset = (block * 512) + offset;
+    u32::from_le_bytes( [uf2[offset], uf2[offset + 1], uf2[offset + 2], uf2[offset + 3]] )
+}
+
+#[test]
+fn elf() {
+    // The firmware fits in a single page of the flash.
+    let elf = ELFObject::parse( common::firmware(false, false).build() ).unwrap();
+    let image = elf.memory_image().unwrap();
+
+    let options = Uf2Options { family: Some(Family::RP2040), ..Uf2Options::default() };
+    let uf2 = elf.to_uf2(&options).unwrap();
+
+    assert_eq!(uf2.len(), 512);
+    assert_eq!(word(&uf2, 0, 0), 0x0A324655);
+    assert_eq!(word(&uf2, 0, 4), 0x9E5D5157);
+    assert_eq!(word(&uf2, 0, 8), 0x2000);
+    assert_eq!(word(&uf2, 0, 12), 0x0800_0000);
+    assert_eq!(word(&uf2, 0, 16), 256);
+    assert_eq!(word(&uf2, 0, 20), 0);
+    assert_eq!(word(&uf2, 0, 24), 1);
+    assert_eq!(word(&uf2, 0, 28), 0xE48BFF56);
+    assert_eq!(word(&uf2, 0, 508), 0x0AB16F30);
+
+    // The payload is the image, with the gap between the segments and the rest of the page filled.
+    let mut aligned = image.clone();
+    aligned.align(256, 0);
+    aligned.set_start(None);
+
+    assert_eq!(aligned.regions().len(), 1);
+    assert_eq!(&uf2[32..288], aligned.regions()[0].data());
+    assert_eq!(&uf2[32..50], image.regions()[0].data());
+    assert!(uf2[288..508].iter().all(|byte| *byte == 0));
+
+    // The file reads back into the aligned image, for its own family only.
+    assert_eq!(MemoryImage::from_uf2(&uf2, None).unwrap(), aligned);
+    assert_eq!(MemoryImage::from_uf2(&uf2, Some(Family::RP2040)).unwrap(), aligned);
+    assert!(MemoryImage::from_uf2(&uf2, Some(Family::STM32F4)).unwrap().is_empty());
+}
+
+#[test]
+fn blocks() {
+    let mut image = MemoryImage::new();
+    image.insert(0x1000_00F0, &[0xAA; 0x20]).unwrap();
+    image.insert(0x1000_0400, &[0xBB; 0x10]).unwrap();
+
+    // The first region crosses a page boundary, the second one is in its own page.
+    let options = Uf2Options { family: Some( Family::from(0x1234_5678) ), fill: 0xFF, ..Uf2Options::default() };
+    let uf2 = image.to_uf2(&options).unwrap();
+
+    assert_eq!(uf2.len(), 3 * 512);
+
+    let addresses = (0..3).map(|block| word(&uf2, block, 12)).collect::<Vec<_>>();
+    assert_eq!(addresses, [0x1000_0000, 0x1000_0100, 0x1000_0400]);
+
+    assert!((0..3).all(|block| (word(&uf2, block, 20) == block as u32) && (word(&uf2, block, 24) == 3)));
+    assert!((0..3).all(|block| word(&uf2, block, 28) == 0x1234_5678));
+
+    let decoded = MemoryImage::from_uf2(&uf2, Some( Family::Other(0x1234_5678) )).unwrap();
+    assert_eq!(decoded.read(0x1000_00EF, 3), Some( &[0xFF, 0xAA, 0xAA][..] ));
+    assert_eq!(decoded.read(0x1000_0410, 1), Some( &[0xFF][..] ));
+    assert_eq!(decoded.size(), 0x300);
+
+    // Without a family, the flag and the field are cleared.
+    let uf2 = image.to_uf2( &Uf2Options::default() ).unwrap();
+    assert!((0..3).all(|block| (word(&uf2, block, 8) == 0) && (word(&uf2, block, 28) == 0)));
+
+    // Blocks must fit in 32 bits.
+    let mut image = MemoryImage::new();
+    image.insert(0x1_0000_0000, &[0]).unwrap();
+    assert_eq!(image.to_uf2( &Uf2Options::default() ), Err( Error::Overflow(0x1_0000_0000) ));
+}
+
+#[test]
+fn raw_family() {
+    let mut image = MemoryImage::new();
+    image.insert(0x1000_0000, &[0xAA; 0x10]).unwrap();
+
+    // A known family given by its raw ID selects the same blocks as the named family.
+    let options = Uf2Options { family: Some( Family::Other(0xE48BFF56) ), ..Uf2Options::default() };
+    let uf2 = image.to_uf2(&options).unwrap();
+    assert_eq!(word(&uf2, 0, 28), 0xE48BFF56);
+
+    for family in [Family::RP2040, Family::Other(0xE48BFF56)] {
+        let decoded = MemoryImage::from_uf2(&uf2, Some(family)).unwrap();
+        assert_eq!(decoded.read(0x1000_0000, 0x10), Some( &[0xAA; 0x10][..] ));
+    }
+
+    let uf2 = image.to_uf2( &Uf2Options { family: Some(Family::RP2040), ..Uf2Options::default() } ).unwrap();
+    assert!( !MemoryImage::from_uf2(&uf2, Some( Family::Other(0xE48BFF56) )).unwrap().is_empty() );
+    assert!( MemoryImage::from_uf2(&uf2, Some( Family::Other(0xE48BFF57) )).unwrap().is_empty() );
+}
+
+#[test]
+fn families() {
+    assert_eq!(Family::from_name("RP2040"), Some(Family::RP2040));
+    assert_eq!(Family::from_name("nrf52840"), Some(Family::NRF52840));
+    assert_eq!(Family::from_name("ESP32S2"), Some(Family::ESP32S2));
+    assert_eq!(Family::from_name("stm32f4"), Some(Family::STM32F4));
+    assert_eq!(Family::from_name("Z80"), None);
+
+    // The known families round trip through their ID and their name.
+    for family in Family::KNOWN {
+        assert_eq!(Family::from( u32::from(family) ), family);
+        assert_eq!(Family::from_name( &family.to_string() ), Some(family));
+    }
+
+    assert_eq!(Family::from(0x1234_5678), Family::Other(0x1234_5678));
+}
+
+#[test]
+fn read_errors() {
+    let mut image = MemoryImage::new();
+    image.insert(0x2000, &[1; 0x200]).unwrap();
+
+    let uf2 = image.to_uf2( &Uf2Options::default() ).unwrap();
+
+    let error = |uf2: &[u8]| match MemoryImage::from_uf2(uf2, None) {
+        Err( Error::BadBlock { index, kind } ) => (index, kind),
+        other => panic!("Unexpected result: {:?}", other),
+    };
+
+    // Changes the word at the given offset of the given block.
+    let patch = |block: usize, offset: usize, value: u32| {
+        let mut uf2 = uf2.clone();
+        uf2[block * 512 + offset..block * 512 + offset + 4].copy_from_slice( &value.to_le_bytes() );
+        uf2
+    };
+
+    assert_eq!(error(&uf2[..1000]), (1, BlockError::Truncated));
+    assert_eq!(error(&patch(1, 4, 0)), (1, BlockError::Magic));
+    assert_eq!(error(&patch(0, 508, 0)), (0, BlockError::Magic));
+    assert_eq!(error(&patch(1, 16, 477)), (1, BlockError::PayloadSize));
+    assert_eq!(error(&patch(1, 20, 2)), (1, BlockError::Number));
+    assert_eq!(error(&patch(1, 12, 0x2080)), (1, BlockError::Overlap));
+
+    // Blocks not meant for the main flash are skipped.
+    let decoded = MemoryImage::from_uf2(&patch(1, 8, 1), None).unwrap();
+    assert_eq!(decoded.size(), 0x100);
+}
@@ -0,0 +1,217 @@
+//! Symbol versioning tests.
+//! The versions must be decoded through the segments when the section headers are stripped.
+
+mod common;
+
+
+
+use micro_elf::elf::ELFObject;
+
+use std::sync::Arc;
+
+
+
+/// Virtual address of the dynamic section.
+const DYNAMIC: u64 = 0x1000;
+
+/// Dynamic symbols, as name, section index and raw version.
+const SYMBOLS: [(&str, u16, u16); 5] = [("memcpy", 0, 5), ("exp", 0, 6), ("fw_init", 0xFFF1, 3), ("fw_old", 0xFFF1, 0x8002), ("fw_base", 0xFFF1, 1)];
+
+/// Builds a shared object that defines two versions and requires versions from two libraries.
+fn versioned(class64: bool, big: bool) -> common::Builder {
+    let mut builder = common::Builder::new(class64, big, 0x28);
+    builder.filetype = 3;
+
+    let inc = if class64 { 8 } else { 4 };
+
+    // Build the string table, remembering the offset of each string.
+    let names = [
+        "libc.so.6", "libm.so.6", "libfw.so", "GLIBC_2.2.5", "GLIBC_2.14", "GLIBC_2.29", "FW_1.0", "FW_2.0",
+        "memcpy", "exp", "fw_init", "fw_old", "fw_base",
+    ];
+
+    let mut strings = vec![0u8];
+    let mut offsets = Vec::new();
+
+    for name in &names {
+        offsets.push( strings.len() as u32 );
+        strings.extend_from_slice( name.as_bytes() );
+        strings.push(0);
+    }
+
+    let string = |name: &str| offsets[ names.iter().position(|n| *n == name).unwrap() ];
+
+    // Build the symbol table and the version of each symbol.
+    let mut symbols = vec![0u8; 8 + (2 * inc)];
+    let mut versym = builder.half(0);
+
+    for (name, shndx, version) in &SYMBOLS {
+        symbols.extend( builder.sym(string(name), &common::Sym::new(name, 0x2000, 4, 0x12, *shndx)) );
+        versym.extend( builder.half(*version) );
+    }
+
+    // A SysV hash table with a single empty bucket, which only gives the number of symbols.
+    let mut hash = Vec::new();
+
+    for word in [1, SYMBOLS.len() as u32 + 1, 0].iter().chain( [0; SYMBOLS.len() + 1].iter() ) {
+        hash.extend( builder.word(*word) );
+    }
+
+    // Version definitions: the object itself, `FW_1.0` and `FW_2.0`, which inherits `FW_1.0`.
+    let mut verdef = Vec::new();
+
+    for (i, (flags, index, parents)) in [(1, 1, vec!["libfw.so"]), (0, 2, vec!["FW_1.0"]), (0, 3, vec!["FW_2.0", "FW_1.0"])].iter().enumerate() {
+        let next = if i == 2 { 0 } else { 20 + (8 * parents.len() as u32) };
+
+        verdef.extend( builder.half(1) );
+        verdef.extend( builder.half(*flags) );
+        verdef.extend( builder.half(*index) );
+        verdef.extend( builder.half(parents.len() as u16) );
+        verdef.extend( builder.word(0) );
+        verdef.extend( builder.word(20) );
+        verdef.extend( builder.word(next) );
+
+        for (j, parent) in parents.iter().enumerate() {
+            verdef.extend( builder.word(string(parent)) );
+            verdef.extend( builder.word(if j + 1 == parents.len() { 0 } else { 8 }) );
+        }
+    }
+
+    // Version requirements: two versions of `libc.so.6` and a weak version of `libm.so.6`.
+    let mut verneed = Vec::new();
+
+    for (i, (library, versions)) in [("libc.so.6", vec![("GLIBC_2.2.5", 4, 0), ("GLIBC_2.14", 5, 0)]), ("libm.so.6", vec![("GLIBC_2.29", 6, 2)])].iter().enumerate() {
+        let next = if i == 1 { 0 } else { 16 + (16 * versions.len() as u32) };
+
+        verneed.extend( builder.half(1) );
+        verneed.extend( builder.half(versions.len() as u16) );
+        verneed.extend( builder.word(string(library)) );
+        verneed.extend( builder.word(16) );
+        verneed.extend( builder.word(next) );
+
+        for (j, (name, index, flags)) in versions.iter().enumerate() {
+            verneed.extend( builder.word(0x0D69_6914 + j as u32) );
+            verneed.extend( builder.half(*flags) );
+            verneed.extend( builder.half(*index) );
+            verneed.extend( builder.word(string(name)) );
+            verneed.extend( builder.word(if j + 1 == versions.len() { 0 } else { 16 }) );
+        }
+    }
+
+    // The tables follow the dynamic section in the file and in memory.
+    let entries = 14;
+    let strtab = DYNAMIC + (entries * 2 * inc) as u64;
+    let symtab = strtab + strings.len() as u64;
+    let hashtab = symtab + symbols.len() as u64;
+    let versymtab = hashtab + hash.len() as u64;
+    let verdeftab = versymtab + versym.len() as u64;
+    let verneedtab = verdeftab + verdef.len() as u64;
+
+    let entries = [
+        (1, u64::from( string("libc.so.6") )), (1, u64::from( string("libm.so.6") )), (14, u64::from( string("libfw.so") )),
+        (4, hashtab), (5, strtab), (6, symtab), (10, strings.len() as u64), (11, 8 + (2 * inc) as u64),
+        (0x6FFF_FFF0, versymtab), (0x6FFF_FFFC, verdeftab), (0x6FFF_FFFD, 3), (0x6FFF_FFFE, verneedtab), (0x6FFF_FFFF, 2), (0, 0),
+    ];
+
+    let mut data = Vec::new();
+
+    for (tag, value) in &entries {
+        data.extend( builder.addr(*tag) );
+        data.extend( builder.addr(*value) );
+    }
+
+    builder.sections.push( common::Section::new(".dynamic", 6, data).alloc(0x3, DYNAMIC).align(8).link(2, 0).entsize(2 * inc as u64) );
+    builder.sections.push( common::Section::new(".dynstr", 3, strings).alloc(0x2, strtab) );
+    builder.sections.push( common::Section::new(".dynsym", 11, symbols).alloc(0x2, symtab).link(2, 1).entsize(8 + (2 * inc) as u64) );
+    builder.sections.push( common::Section::new(".hash", 5, hash).alloc(0x2, hashtab).link(3, 0) );
+    builder.sections.push( common::Section::new(".gnu.version", 0x6FFF_FFFF, versym).alloc(0x2, versymtab).link(3, 0).entsize(2) );
+    builder.sections.push( common::Section::new(".gnu.version_d", 0x6FFF_FFFD, verdef).alloc(0x2, verdeftab).link(2, 3) );
+    builder.sections.push( common::Section::new(".gnu.version_r", 0x6FFF_FFFE, verneed).alloc(0x2, verneedtab).link(2, 2) );
+
+    builder.programs.push( common::Program { ptype: 1, flags: 0x6, sections: (0, 6), paddr: DYNAMIC } );
+    builder.programs.push( common::Program { ptype: 2, flags: 0x6, sections: (0, 0), paddr: DYNAMIC } );
+
+    builder
+}
+
+#[test]
+fn symbol_versions() {
+    for &class64 in &[false, true] {
+        for &big in &[false, true] {
+            let mut image = versioned(class64, big).build();
+
+            for stripped in [false, true] {
+                if stripped {
+                    common::strip(&mut image, class64);
+                }
+
+                let elf = ELFObject::parse( Arc::<[u8]>::from( image.clone() ) ).unwrap();
+                assert_eq!(elf.sections().is_empty(), stripped);
+
+                // Check the version of each symbol.
+                let names = elf.dynamic_symbols().iter().map(|s| s.versioned_name()).collect::<Vec<_>>();
+                assert_eq!(names, ["", "memcpy@GLIBC_2.14", "exp@GLIBC_2.29", "fw_init@@FW_2.0", "fw_old@FW_1.0", "fw_base"]);
+
+                let memcpy = elf.dynamic_symbols()[1].version().unwrap();
+                assert_eq!(memcpy.name(), "GLIBC_2.14");
+                assert_eq!(memcpy.library().map(|l| l.as_str()), Some("libc.so.6"));
+                assert!( !memcpy.hidden() );
+
+                let old = elf.dynamic_symbols()[4].version().unwrap();
+                assert_eq!(old.library(), None);
+                assert!( old.hidden() );
+
+                assert!( elf.dynamic_symbols()[5].version().is_none() );
+
+                // Check the versions required from each library.
+                let required = elf.required_versions().iter()
+                    .map(|r| (r.library().clone(), r.versions().iter().map(|v| (v.name().clone(), v.index(), v.weak())).collect::<Vec<_>>()))
+                    .collect::<Vec<_>>();
+
+                assert_eq!(required, [
+                    ("libc.so.6".to_string(), vec![("GLIBC_2.2.5".to_string(), 4, false), ("GLIBC_2.14".to_string(), 5, false)]),
+                    ("libm.so.6".to_string(), vec![("GLIBC_2.29".to_string(), 6, true)]),
+                ]);
+            }
+        }
+    }
+}
+
+#[test]
+fn unversioned() {
+    let elf = ELFObject::parse( Arc::<[u8]>::from( common::firmware(true, false).build() ) ).unwrap();
+
+    assert!( elf.required_versions().is_empty() );
+    assert!( elf.symbols().iter().all(|s| s.version().is_none()) );
+}
+
+#[test]
+fn malformed_tables() {
+    use micro_elf::error::{ Error, Table };
+
+    for &class64 in &[false, true] {
+        for &big in &[false, true] {
+            let mut builder = versioned(class64, big);
+            let inc = if class64 { 8 } else { 4 };
+
+            // The first `DT_NEEDED` entry names a string past the end of the string table.
+            let offset = builder.addr(0xFFFF);
+            builder.sections[0].data[inc..2*inc].copy_from_slice( &offset );
+
+            // The version definitions and requirements are not linked to a string table.
+            builder.sections[5].link = 0;
+            builder.sections[6].link = 0;
+
+            let elf = ELFObject::parse( Arc::<[u8]>::from( builder.build() ) ).unwrap();
+
+            // The headers, the sections and the dynamic symbols are still available, without their versions.
+            assert_eq!(elf.sections().len(), 9);
+            assert_eq!(elf.dynamic_symbols().len(), SYMBOLS.len() + 1);
+            assert!( elf.dynamic_symbols().iter().all(|s| s.version().is_none()) );
+            assert!( elf.required_versions().is_empty() );
+            assert!( elf.hash_table().is_some() );
+
+            assert!( matches!(elf.dynamic(), Err( Error::BadNameIndex { table: Table::Dynamic, index: 0, .. } )) );
+        }
+    }
+}